@@ -0,0 +1,96 @@
+//! If `NVIM_OXI_KEYSETS` is set to the path of the `keysets_defs.generated.h`
+//! header of a Neovim build (`build/src/nvim/auto/` in its source tree), the
+//! fields of its `KeyDict_*` structs are embedded in the crate, and in debug
+//! builds the dictionaries passed to the API functions taking them are
+//! checked against them. See `api::keysets`.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=NVIM_OXI_KEYSETS");
+    println!("cargo:rustc-check-cfg=cfg(nvim_oxi_keysets)");
+
+    let Some(path) = env::var_os("NVIM_OXI_KEYSETS").map(PathBuf::from) else {
+        return;
+    };
+
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let header = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!("NVIM_OXI_KEYSETS: couldn't read {}: {err}", path.display())
+    });
+
+    let keysets = parse_keysets(&header);
+
+    if keysets.is_empty() {
+        panic!(
+            "NVIM_OXI_KEYSETS: no `KeyDict_*` structs in {}",
+            path.display()
+        );
+    }
+
+    let mut out = String::from("&[\n");
+    for (name, fields) in keysets {
+        writeln!(out, "    ({name:?}, &{fields:?}),").unwrap();
+    }
+    out.push(']');
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("keysets.rs"), out).unwrap();
+
+    println!("cargo:rustc-cfg=nvim_oxi_keysets");
+}
+
+/// Returns the name and fields of every keyset defined in `header`, e.g.
+///
+/// ```c
+/// typedef struct {
+///   Object types;
+/// } KeyDict_context;
+/// ```
+///
+/// Newer releases also have an `is_set__<name>_` bitset as first field,
+/// which isn't a key.
+fn parse_keysets(header: &str) -> Vec<(String, Vec<String>)> {
+    let mut keysets = Vec::new();
+    let mut fields = None::<Vec<String>>;
+
+    for line in header.lines().map(str::trim) {
+        if line.starts_with("typedef struct") {
+            fields = Some(Vec::new());
+            continue;
+        }
+
+        let Some(current) = &mut fields else { continue };
+
+        if let Some(rest) = line.strip_prefix('}') {
+            let name = rest.trim().trim_end_matches(';').trim();
+            let name = name.strip_prefix("KeyDict_").or_else(|| {
+                name.strip_prefix("Dict(")
+                    .and_then(|name| name.strip_suffix(')'))
+            });
+
+            if let Some(name) = name {
+                keysets.push((name.to_owned(), fields.take().unwrap()));
+            }
+
+            fields = None;
+            continue;
+        }
+
+        let Some(decl) = line.strip_suffix(';') else { continue };
+
+        if let Some(field) = decl.split_whitespace().last() {
+            let field = field.trim_start_matches('*');
+            if !field.starts_with("is_set__") {
+                current.push(field.to_owned());
+            }
+        }
+    }
+
+    keysets
+}
@@ -0,0 +1,50 @@
+use nvim_types::{error::Error as NvimError, object::Object};
+
+use super::ffi::*;
+use super::opts::*;
+use crate::api::keyset;
+use crate::lua::LUA_INTERNAL_CALL;
+use crate::Result;
+
+/// Binding to `nvim_create_autocmd`.
+///
+/// Creates an autocommand triggered by any of the `events`, returning its
+/// id.
+pub fn create_autocmd<'a, Events>(
+    events: Events,
+    opts: &CreateAutocmdOpts,
+) -> Result<u32>
+where
+    Events: IntoIterator<Item = &'a str>,
+{
+    let events = events.into_iter().map(Object::from).collect::<Object>();
+    let mut err = NvimError::new();
+    let id = unsafe {
+        nvim_create_autocmd(
+            LUA_INTERNAL_CALL,
+            events,
+            &keyset("create_autocmd", opts),
+            &mut err,
+        )
+    };
+    err.into_err_or_flatten(|| Ok(id.try_into()?))
+}
+
+/// Binding to `nvim_del_augroup_by_id`.
+///
+/// Deletes the autocommand group with the given id, together with all the
+/// autocommands it contains.
+pub fn del_augroup_by_id(id: u32) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe { nvim_del_augroup_by_id(id.into(), &mut err) };
+    err.into_err_or_else(|| ())
+}
+
+/// Binding to `nvim_del_autocmd`.
+///
+/// Deletes the autocommand with the given id.
+pub fn del_autocmd(id: u32) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe { nvim_del_autocmd(id.into(), &mut err) };
+    err.into_err_or_else(|| ())
+}
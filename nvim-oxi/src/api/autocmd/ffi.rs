@@ -0,0 +1,22 @@
+use nvim_types::{
+    dictionary::Dictionary,
+    error::Error,
+    object::Object,
+    Integer,
+};
+
+extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/autocmd.c#L404
+    pub(super) fn nvim_create_autocmd(
+        channel_id: u64,
+        event: Object,
+        opts: *const Dictionary,
+        err: *mut Error,
+    ) -> Integer;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/autocmd.c#L747
+    pub(super) fn nvim_del_augroup_by_id(id: Integer, err: *mut Error);
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/autocmd.c#L555
+    pub(super) fn nvim_del_autocmd(id: Integer, err: *mut Error);
+}
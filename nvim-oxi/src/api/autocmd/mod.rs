@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+mod autocmd;
+mod ffi;
+pub mod opts;
+
+pub use autocmd::*;
@@ -0,0 +1,92 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+use crate::api::types::AutocmdCallbackArgs;
+use crate::api::Buffer;
+use crate::lua::LuaFnMut;
+
+/// Whether to delete the autocommand after the callback returns.
+pub type ShouldDeleteAutocmd = bool;
+
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct CreateAutocmdOpts {
+    /// Creates a buffer-local autocommand. Can't be used together with
+    /// `patterns`.
+    #[builder(setter(into, strip_option))]
+    buffer: Option<Buffer>,
+
+    #[builder(setter(custom))]
+    callback: Option<LuaFnMut<AutocmdCallbackArgs, ShouldDeleteAutocmd>>,
+
+    /// Vimscript command to execute instead of a callback.
+    #[builder(setter(into, strip_option))]
+    command: Option<String>,
+
+    #[builder(setter(into, strip_option))]
+    desc: Option<String>,
+
+    /// Id of the group the autocommand belongs to.
+    #[builder(setter(strip_option))]
+    group: Option<u32>,
+
+    nested: bool,
+
+    once: bool,
+
+    #[builder(setter(custom))]
+    patterns: Vec<String>,
+}
+
+impl CreateAutocmdOpts {
+    #[inline(always)]
+    pub fn builder() -> CreateAutocmdOptsBuilder {
+        CreateAutocmdOptsBuilder::default()
+    }
+}
+
+impl CreateAutocmdOptsBuilder {
+    /// Callback invoked when the autocommand is triggered. If it returns
+    /// `true` the autocommand is deleted.
+    pub fn callback<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnMut(AutocmdCallbackArgs) -> crate::Result<ShouldDeleteAutocmd>
+            + 'static,
+    {
+        self.callback = Some(Some(fun.into()));
+        self
+    }
+
+    /// Patterns to match against (see `:h autocmd-pattern`).
+    pub fn patterns<'a, Patterns>(&mut self, patterns: Patterns) -> &mut Self
+    where
+        Patterns: IntoIterator<Item = &'a str>,
+    {
+        self.patterns = Some(patterns.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl From<CreateAutocmdOpts> for Dictionary {
+    fn from(opts: CreateAutocmdOpts) -> Self {
+        let patterns = (!opts.patterns.is_empty())
+            .then(|| Object::from_iter(opts.patterns));
+
+        Self::from_iter([
+            ("buffer", Object::from(opts.buffer.map(|buf| buf.0))),
+            ("callback", opts.callback.into()),
+            ("command", opts.command.into()),
+            ("desc", opts.desc.into()),
+            ("group", opts.group.into()),
+            ("nested", opts.nested.into()),
+            ("once", opts.once.into()),
+            ("pattern", patterns.into()),
+        ])
+    }
+}
+
+impl From<&CreateAutocmdOpts> for Dictionary {
+    fn from(opts: &CreateAutocmdOpts) -> Self {
+        opts.clone().into()
+    }
+}
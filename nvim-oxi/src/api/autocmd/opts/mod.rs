@@ -0,0 +1,3 @@
+mod create_autocmd;
+
+pub use create_autocmd::*;
@@ -1,5 +1,7 @@
 use std::fmt;
-use std::path::PathBuf;
+use std::ops::{Bound, RangeBounds};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use nvim_types::{
     array::Array,
@@ -9,6 +11,7 @@ use nvim_types::{
     BufHandle,
     Integer,
 };
+use serde::Deserialize;
 
 use super::ffi::*;
 use super::opts::*;
@@ -17,13 +20,21 @@ use crate::api::global::opts::{
     GetCommandsOpts,
     SetKeymapOpts,
 };
-use crate::api::types::{CommandInfos, KeymapInfos, Mode};
-use crate::lua::{LuaFnOnce, LUA_INTERNAL_CALL};
+use crate::api::types::{
+    CommandInfos,
+    KeymapInfos,
+    Mode,
+    Namespace,
+    Position,
+};
+use crate::api::{call_function, keyset};
+use crate::lua::{self, LUA_INTERNAL_CALL};
 use crate::object::{FromObject, ToObject};
-use crate::Result;
+use crate::options::BufferOptions;
+use crate::{Error, Result};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub struct Buffer(BufHandle);
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct Buffer(pub(crate) BufHandle);
 
 impl fmt::Display for Buffer {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -37,6 +48,15 @@ impl<H: Into<BufHandle>> From<H> for Buffer {
     }
 }
 
+impl FromStr for Buffer {
+    type Err = crate::Error;
+
+    /// Parses a buffer from either `{id}`, `:{id}` or `Buffer({id})`.
+    fn from_str(s: &str) -> Result<Self> {
+        crate::api::handle::parse(s, "Buffer").map(Self)
+    }
+}
+
 impl Buffer {
     /// Shorthand for `nvim_oxi::api::get_current_buf`.
     #[inline(always)]
@@ -44,6 +64,54 @@ impl Buffer {
         crate::api::get_current_buf()
     }
 
+    /// Creates a buffer from a Vimscript buffer number. Unlike
+    /// `Buffer::from`, buffer number `0` is resolved to the current buffer.
+    /// Fails if the number doesn't fit in a [`BufHandle`].
+    pub fn from_number(bufnr: u32) -> Result<Self> {
+        match bufnr {
+            0 => Ok(Self::current()),
+            n => Ok(Self::from(BufHandle::try_from(n)?)),
+        }
+    }
+
+    /// Returns a handle to the buffer's local options, whose `get`/`set`
+    /// act on this buffer instead of the current one.
+    #[inline(always)]
+    pub fn options(&self) -> BufferOptions {
+        BufferOptions(*self)
+    }
+
+    /// Binding to `nvim_buf_add_highlight`.
+    ///
+    /// Highlights the 0-indexed `cols` of the 0-indexed `line` with
+    /// `hl_group`, e.g. `0..3` or `2..` to highlight until the end of the
+    /// line. Returns the namespace the highlight was added to.
+    pub fn add_highlight<R>(
+        &mut self,
+        ns: Namespace,
+        hl_group: &str,
+        line: usize,
+        cols: R,
+    ) -> Result<Namespace>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = range_to_limits(cols)?;
+        let mut err = NvimError::new();
+        let ns_id = unsafe {
+            nvim_buf_add_highlight(
+                self.0,
+                ns.into(),
+                hl_group.into(),
+                line.try_into()?,
+                start,
+                end,
+                &mut err,
+            )
+        };
+        err.into_err_or_flatten(|| Ok(Namespace(ns_id.try_into()?)))
+    }
+
     /// Binding to `nvim_buf_attach`.
     pub fn attach(
         &self,
@@ -65,20 +133,40 @@ impl Buffer {
 
     /// Binding to `nvim_buf_call`.
     ///
-    /// Calls a closure with the buffer as the temporary current buffer.
+    /// Calls a closure with the buffer as the temporary current buffer,
+    /// returning its output.
     pub fn call<F, R>(&self, fun: F) -> Result<R>
     where
-        R: ToObject + FromObject,
         F: FnOnce(()) -> Result<R> + 'static,
+        R: 'static,
     {
-        let fun = LuaFnOnce::from(fun);
+        let (fun, output) = lua::fn_once_with_output(fun);
         let mut err = NvimError::new();
-        let obj = unsafe { nvim_buf_call(self.0, fun.0, &mut err) };
+        unsafe { nvim_buf_call(self.0, fun.0, &mut err) };
+        fun.unref();
+        err.into_err_or_flatten(|| output.take().expect("fun was called"))
+    }
 
-        err.into_err_or_flatten(move || {
-            fun.unref();
-            R::from_obj(obj)
-        })
+    /// Binding to `nvim_buf_clear_namespace`.
+    ///
+    /// Clears the extmarks and highlights of `ns` in the 0-indexed `lines`,
+    /// e.g. `0..10` or `5..` to clear until the end of the buffer.
+    pub fn clear_namespace<R>(&mut self, ns: Namespace, lines: R) -> Result<()>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = range_to_limits(lines)?;
+        let mut err = NvimError::new();
+        unsafe {
+            nvim_buf_clear_namespace(self.0, ns.into(), start, end, &mut err)
+        };
+        err.into_err_or_else(|| ())
+    }
+
+    /// Clears the extmarks and highlights of `ns` in the whole buffer.
+    #[inline]
+    pub fn clear_namespace_all(&mut self, ns: Namespace) -> Result<()> {
+        self.clear_namespace(ns, ..)
     }
 
     /// Binding to `nvim_buf_create_user_command`.
@@ -96,7 +184,7 @@ impl Buffer {
                 self.0,
                 name.into(),
                 command.to_obj()?,
-                &(opts.into()),
+                &keyset("user_command", opts),
                 &mut err,
             )
         };
@@ -174,7 +262,11 @@ impl Buffer {
     ) -> Result<impl Iterator<Item = CommandInfos>> {
         let mut err = NvimError::new();
         let cmds = unsafe {
-            nvim_buf_get_commands(self.0, &mut (opts.into()), &mut err)
+            nvim_buf_get_commands(
+                self.0,
+                &mut keyset("get_commands", opts),
+                &mut err,
+            )
         };
         err.into_err_or_else(|| {
             cmds.into_iter().flat_map(|(_, cmd)| CommandInfos::from_obj(cmd))
@@ -197,8 +289,11 @@ impl Buffer {
                 &mut err,
             )
         };
-        err.into_err_or_else(|| {
-            maps.into_iter().flat_map(KeymapInfos::from_obj)
+        err.into_err_or_flatten(|| {
+            maps.into_iter()
+                .map(KeymapInfos::from_obj)
+                .collect::<Result<Vec<_>>>()
+                .map(Vec::into_iter)
         })
     }
 
@@ -230,14 +325,30 @@ impl Buffer {
         })
     }
 
+    /// Same as [`get_lines`](Buffer::get_lines), but returns the raw bytes of
+    /// each line without any UTF-8 validation, preserving the contents of
+    /// buffers with other encodings exactly.
+    pub fn get_lines_bytes(
+        &self,
+        start: usize,
+        end: usize,
+        strict_indexing: bool,
+    ) -> Result<Vec<Vec<u8>>> {
+        self.get_lines(start, end, strict_indexing)
+            .map(|lines| lines.map(NvimString::into_bytes).collect())
+    }
+
     /// Binding to `nvim_buf_get_mark`.
     ///
-    /// Returns a tuple `(row, col)` representing the position of the named
-    /// mark. Marks are (1,0)-indexed.
-    pub fn get_mark(&self, name: char) -> Result<(usize, usize)> {
+    /// Returns the (1,0)-indexed position of the named mark, or `None` if
+    /// the mark isn't set.
+    pub fn get_mark(&self, name: char) -> Result<Option<Position>> {
         let mut err = NvimError::new();
         let mark = unsafe { nvim_buf_get_mark(self.0, name.into(), &mut err) };
-        err.into_err_or_flatten(|| <(usize, usize)>::from_obj(mark.into()))
+        err.into_err_or_flatten(|| {
+            let pos = Position::from_obj(mark.into())?;
+            Ok((pos.row != 0).then_some(pos))
+        })
     }
 
     /// Binding to `nvim_buf_get_name`.
@@ -264,6 +375,8 @@ impl Buffer {
     ///
     /// Gets a buffer option value. Fails if the specified type couldn't be
     /// deserialized from the returned object.
+    #[deprecated(note = "`nvim_buf_get_option` is being removed from \
+                         Neovim, use `Buffer::options().get` instead")]
     pub fn get_option<Value>(&self, name: &str) -> Result<Value>
     where
         Value: FromObject,
@@ -360,7 +473,7 @@ impl Buffer {
                 mode.into(),
                 lhs.into(),
                 rhs.unwrap_or_default().into(),
-                &(opts.into()),
+                &keyset("keymap", opts),
                 &mut err,
             )
         };
@@ -401,23 +514,43 @@ impl Buffer {
         err.into_err_or_else(|| ())
     }
 
+    /// Same as [`set_lines`](Buffer::set_lines), but takes the raw bytes of
+    /// each line, which are set as is without any UTF-8 validation.
+    pub fn set_lines_bytes<Int, Line, Lines>(
+        &mut self,
+        start: Int,
+        end: Int,
+        strict_indexing: bool,
+        replacement: Lines,
+    ) -> Result<()>
+    where
+        Int: Into<Integer>,
+        Line: Into<Vec<u8>>,
+        Lines: IntoIterator<Item = Line>,
+    {
+        let replacement = replacement
+            .into_iter()
+            .map(|line| NvimString::from_bytes(line.into()));
+        self.set_lines(start, end, strict_indexing, replacement)
+    }
+
     /// Binding to `nvim_buf_set_mark`.
     ///
-    /// Sets a named mark in the buffer. Marks are (1,0)-indexed, and passing 0
-    /// as `line` deletes the mark.
+    /// Sets a named mark in the buffer at a (1,0)-indexed position. Passing
+    /// 0 as the row deletes the mark.
     pub fn set_mark(
         &mut self,
         name: char,
-        line: usize,
-        col: usize,
+        pos: impl Into<Position>,
     ) -> Result<bool> {
+        let pos = pos.into();
         let mut err = NvimError::new();
         let mark_was_set = unsafe {
             nvim_buf_set_mark(
                 self.0,
                 name.into(),
-                line.try_into()?,
-                col.try_into()?,
+                pos.row.try_into()?,
+                pos.col.try_into()?,
                 Dictionary::new(),
                 &mut err,
             )
@@ -438,6 +571,8 @@ impl Buffer {
     ///
     /// Sets a buffer option value. Passing `None` as value deletes the option
     /// (only works if there's a global fallback).
+    #[deprecated(note = "`nvim_buf_set_option` is being removed from \
+                         Neovim, use `Buffer::options().set` instead")]
     pub fn set_option<V>(&mut self, name: &str, value: V) -> Result<()>
     where
         V: ToObject,
@@ -501,4 +636,154 @@ impl Buffer {
         };
         err.into_err_or_else(|| ())
     }
+
+    /// Binding to `nvim__buf_stats`.
+    ///
+    /// Returns internal statistics about the buffer, useful to debug and
+    /// profile plugins. Unstable, see [`BufferStats`] for details.
+    ///
+    /// [`BufferStats`]: crate::api::types::BufferStats
+    #[cfg(feature = "diagnostics")]
+    pub fn stats(&self) -> Result<crate::api::types::BufferStats> {
+        let mut err = NvimError::new();
+        let stats = unsafe { nvim__buf_stats(self.0, &mut err) };
+        err.into_err_or_flatten(|| FromObject::from_obj(stats.into()))
+    }
+}
+
+impl Buffer {
+    /// Writes the buffer to its file, like `:write` would.
+    pub fn write(&self, opts: &WriteOpts) -> Result<()> {
+        self.write_cmd(opts.cmdline().into())
+            .map_err(|err| write_error(err, || self.get_name()))
+    }
+
+    /// Writes the buffer to `path`, like `:write {path}` would. If `path`
+    /// is not the buffer's file the buffer is not marked as saved.
+    pub fn write_to<P: AsRef<Path>>(
+        &self,
+        path: P,
+        opts: &WriteOpts,
+    ) -> Result<()> {
+        let path = path.as_ref();
+
+        // Paths aren't necessarily valid UTF-8, so they're passed around as
+        // bytes.
+        let escaped = call_function::<_, NvimString>(
+            "fnameescape",
+            [NvimString::from(path.as_os_str().to_owned())],
+        )?;
+
+        let mut cmdline = opts.cmdline().into_bytes();
+        cmdline.push(b' ');
+        cmdline.extend(escaped.as_bytes());
+
+        self.write_cmd(NvimString::from_bytes(cmdline))
+            .map_err(|err| write_error(err, || Ok(path.to_owned())))
+    }
+
+    /// Executes a `:write` command with the buffer as the current one.
+    fn write_cmd(&self, cmdline: NvimString) -> Result<()> {
+        self.call(move |()| {
+            call_function::<_, NvimString>("execute", [cmdline]).map(|_| ())
+        })
+    }
+}
+
+/// Maps the Neovim errors raised by `:write` to the more specific variants
+/// of `Error`.
+fn write_error<P>(err: Error, path: P) -> Error
+where
+    P: FnOnce() -> Result<PathBuf>,
+{
+    let Error::NvimError(ref nvim_err) = err else { return err };
+
+    let variant = match nvim_err.code() {
+        // E13: File exists (add ! to override)
+        Some(13) => Error::FileExistsError,
+        // E212: Can't open file for writing
+        Some(212) => Error::CantOpenForWritingError,
+        _ => return err,
+    };
+
+    path().map(variant).unwrap_or(err)
+}
+
+/// Converts a 0-indexed range into the end-exclusive `(start, end)` pair
+/// taken by the API, where an `end` of `-1` stands for "until the end".
+fn range_to_limits<R>(range: R) -> Result<(Integer, Integer)>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start.try_into()?,
+        Bound::Excluded(&start) => Integer::try_from(start)? + 1,
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&end) => Integer::try_from(end)? + 1,
+        Bound::Excluded(&end) => end.try_into()?,
+        Bound::Unbounded => -1,
+    };
+
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_limits() {
+        assert_eq!(range_to_limits(2..5).unwrap(), (2, 5));
+        assert_eq!(range_to_limits(2..=5).unwrap(), (2, 6));
+        assert_eq!(range_to_limits(3..).unwrap(), (3, -1));
+        assert_eq!(range_to_limits(..).unwrap(), (0, -1));
+    }
+}
+
+#[cfg(feature = "test")]
+mod nvim_tests {
+    use std::{env, fs};
+
+    use super::*;
+    use crate::api;
+
+    #[nvim_oxi::test]
+    fn write_to_existing_file() -> Result<()> {
+        let path = env::temp_dir().join("nvim-oxi-write-to-existing");
+        fs::write(&path, "old")?;
+
+        let mut buf = api::create_buf(true, false)?;
+        buf.set_lines(0, -1, true, ["new"])?;
+
+        let res = buf.write_to(&path, &WriteOpts::default());
+        assert!(matches!(res, Err(Error::FileExistsError(p)) if p == path));
+
+        let opts = WriteOpts::builder().force(true).build().unwrap();
+        buf.write_to(&path, &opts)?;
+        assert_eq!(fs::read_to_string(&path)?, "new\n");
+
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[nvim_oxi::test]
+    fn write_to_non_utf8_path() -> Result<()> {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"nvim-oxi-write-to-\xff file");
+        let path = env::temp_dir().join(name);
+
+        let mut buf = api::create_buf(true, false)?;
+        buf.set_lines(0, -1, true, ["text"])?;
+        buf.write_to(&path, &WriteOpts::default())?;
+
+        assert_eq!(fs::read(&path)?, b"text\n");
+        fs::remove_file(&path)?;
+        Ok(())
+    }
 }
@@ -10,6 +10,24 @@ use nvim_types::{
 };
 
 extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/buffer.c#L1282
+    #[cfg(feature = "diagnostics")]
+    pub(super) fn nvim__buf_stats(
+        buf: BufHandle,
+        err: *mut Error,
+    ) -> Dictionary;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/extmark.c#L920
+    pub(super) fn nvim_buf_add_highlight(
+        buf: BufHandle,
+        ns_id: Integer,
+        hl_group: String,
+        line: Integer,
+        col_start: Integer,
+        col_end: Integer,
+        err: *mut Error,
+    ) -> Integer;
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/buffer.c#L145
     pub(super) fn nvim_buf_attach(
         channel_id: u64,
@@ -26,6 +44,15 @@ extern "C" {
         err: *mut Error,
     ) -> Object;
 
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/extmark.c#L956
+    pub(super) fn nvim_buf_clear_namespace(
+        buf: BufHandle,
+        ns_id: Integer,
+        line_start: Integer,
+        line_end: Integer,
+        err: *mut Error,
+    );
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/buffer.c#L1383
     pub(super) fn nvim_buf_create_user_command(
         buf: BufHandle,
@@ -1,3 +1,5 @@
 mod buf_attach;
+mod write;
 
 pub use buf_attach::*;
+pub use write::*;
@@ -0,0 +1,72 @@
+use derive_builder::Builder;
+
+/// Options passed to `Buffer::write` and `Buffer::write_to`.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct WriteOpts {
+    /// Write even if the file exists, or is readonly (like `:write!`).
+    force: bool,
+
+    /// Append to the file instead of overwriting it (like `:write >>`).
+    append: bool,
+
+    /// Only write the lines in this 1-indexed, inclusive range.
+    #[builder(setter(strip_option))]
+    range: Option<(usize, usize)>,
+
+    /// Don't trigger any `BufWrite*`/`FileWrite*` autocommands.
+    noautocmd: bool,
+}
+
+impl WriteOpts {
+    #[inline(always)]
+    pub fn builder() -> WriteOptsBuilder {
+        WriteOptsBuilder::default()
+    }
+
+    /// Builds the `:write` command line, to which the escaped file name can
+    /// be appended after a space.
+    pub(crate) fn cmdline(&self) -> String {
+        let mut cmdline = String::new();
+
+        if self.noautocmd {
+            cmdline.push_str("noautocmd ");
+        }
+
+        if let Some((start, end)) = self.range {
+            cmdline.push_str(&format!("{start},{end}"));
+        }
+
+        cmdline.push_str("write");
+
+        if self.force {
+            cmdline.push('!');
+        }
+
+        if self.append {
+            cmdline.push_str(" >>");
+        }
+
+        cmdline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cmdline() {
+        let opts = WriteOpts::default();
+        assert_eq!(opts.cmdline(), "write");
+
+        let opts = WriteOpts::builder()
+            .force(true)
+            .append(true)
+            .range((3, 7))
+            .noautocmd(true)
+            .build()
+            .unwrap();
+        assert_eq!(opts.cmdline(), "noautocmd 3,7write! >>");
+    }
+}
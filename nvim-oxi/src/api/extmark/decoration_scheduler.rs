@@ -0,0 +1,246 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use nvim_types::error::Error as NvimError;
+
+use super::ffi::*;
+use super::opts::DecorationProviderOpts;
+use crate::api::types::Namespace;
+use crate::api::{Buffer, Window};
+use crate::Result;
+
+/// A decoration provider that invokes a redraw callback at most once per
+/// redraw cycle per window, and only if something changed since the last
+/// time it ran.
+///
+/// A window needs to be redecorated if it was explicitly invalidated via
+/// [`invalidate_win`](DecorationScheduler::invalidate_win), if the buffer it
+/// displays was invalidated via
+/// [`invalidate_buf`](DecorationScheduler::invalidate_buf), or if its
+/// viewport changed. Invalidations are coalesced: no matter how many of them
+/// happen between two redraws (e.g. from autocommands or `on_lines`
+/// callbacks), a single redraw is scheduled.
+///
+/// Since the callback isn't called on every redraw, the extmarks it places
+/// shouldn't be `ephemeral`.
+#[derive(Clone)]
+pub struct DecorationScheduler {
+    state: Rc<RefCell<SchedulerState>>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    /// Windows that were explicitly invalidated.
+    dirty_wins: HashSet<Window>,
+
+    /// Number of times each buffer was invalidated.
+    buf_ticks: HashMap<Buffer, usize>,
+
+    /// What each window looked like the last time it was decorated.
+    decorated: HashMap<Window, Decorated>,
+
+    /// Windows that have already been handled in the current redraw cycle.
+    handled: HashSet<Window>,
+
+    /// Buffers to be redrawn by the next scheduled flush.
+    to_redraw: HashSet<Buffer>,
+
+    /// Whether a flush has already been scheduled.
+    flush_pending: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Decorated {
+    buf: Buffer,
+    buf_tick: usize,
+    topline: usize,
+    botline: usize,
+}
+
+impl SchedulerState {
+    /// Whether `win` has to be redecorated, marking it as handled for the
+    /// current redraw cycle.
+    fn should_decorate(&mut self, win: Window, current: Decorated) -> bool {
+        if !self.handled.insert(win) {
+            return false;
+        }
+
+        let is_dirty = self.dirty_wins.remove(&win);
+        let has_changed = self.decorated.get(&win) != Some(&current);
+        self.decorated.insert(win, current);
+
+        is_dirty || has_changed
+    }
+
+    /// Forgets about the windows and buffers that were closed or wiped out.
+    fn prune(
+        &mut self,
+        win_is_valid: impl Fn(&Window) -> bool,
+        buf_is_valid: impl Fn(&Buffer) -> bool,
+    ) {
+        self.dirty_wins.retain(&win_is_valid);
+        self.decorated.retain(|win, _| win_is_valid(win));
+        self.buf_ticks.retain(|buf, _| buf_is_valid(buf));
+    }
+}
+
+impl DecorationScheduler {
+    /// Registers a decoration provider for the `ns` namespace which calls
+    /// `on_redraw` with the window, the buffer it displays and the
+    /// 0-indexed `(topline, botline)` of its viewport.
+    pub fn new<F>(ns: Namespace, mut on_redraw: F) -> Result<Self>
+    where
+        F: FnMut(Window, Buffer, usize, usize) -> Result<()> + 'static,
+    {
+        let state = Rc::new(RefCell::new(SchedulerState::default()));
+
+        let on_start = {
+            let state = Rc::clone(&state);
+            move |_| {
+                state.borrow_mut().handled.clear();
+                Ok(true)
+            }
+        };
+
+        let on_win = {
+            let state = Rc::clone(&state);
+            move |(_, win, buf, topline, botline)| {
+                let should_decorate = {
+                    let state = &mut *state.borrow_mut();
+                    let buf_tick =
+                        state.buf_ticks.get(&buf).copied().unwrap_or_default();
+                    let current =
+                        Decorated { buf, buf_tick, topline, botline };
+                    state.should_decorate(win, current)
+                };
+
+                // The borrow has to be released before calling `on_redraw`,
+                // which is free to invalidate other windows.
+                if should_decorate {
+                    on_redraw(win, buf, topline, botline)?;
+                }
+
+                Ok(false)
+            }
+        };
+
+        let opts = DecorationProviderOpts::builder()
+            .on_start(on_start)
+            .on_win(on_win)
+            .build()
+            .expect("all fields have defaults");
+
+        super::set_decoration_provider(ns, opts)?;
+
+        Ok(Self { state })
+    }
+
+    /// Marks every window displaying `buf` as needing to be redecorated.
+    pub fn invalidate_buf(&self, buf: Buffer) {
+        let state = &mut *self.state.borrow_mut();
+        *state.buf_ticks.entry(buf).or_default() += 1;
+        state.to_redraw.insert(buf);
+        self.schedule_flush(state);
+    }
+
+    /// Marks `win` as needing to be redecorated. Invalid windows are
+    /// ignored.
+    pub fn invalidate_win(&self, win: Window) {
+        let Ok(buf) = win.get_buf() else { return };
+        let state = &mut *self.state.borrow_mut();
+        state.dirty_wins.insert(win);
+        state.to_redraw.insert(buf);
+        self.schedule_flush(state);
+    }
+
+    /// Schedules a single redraw of all the invalidated buffers, unless one
+    /// is already pending.
+    fn schedule_flush(&self, state: &mut SchedulerState) {
+        if std::mem::replace(&mut state.flush_pending, true) {
+            return;
+        }
+
+        let state = Rc::clone(&self.state);
+
+        crate::schedule(move |()| {
+            let bufs = {
+                let state = &mut *state.borrow_mut();
+                state.flush_pending = false;
+                state.prune(Window::is_valid, Buffer::is_valid);
+                std::mem::take(&mut state.to_redraw)
+            };
+
+            bufs.into_iter().filter(Buffer::is_valid).try_for_each(redraw)
+        });
+    }
+}
+
+/// Forces a redraw of the whole buffer.
+fn redraw(buf: Buffer) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe { nvim__buf_redraw_range(buf.0, 0, -1, &mut err) };
+    err.into_err_or_else(|| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport(buf_tick: usize, topline: usize) -> Decorated {
+        Decorated { buf: Buffer::from(1), buf_tick, topline, botline: 40 }
+    }
+
+    #[test]
+    fn windows_are_decorated_once_per_cycle() {
+        let mut state = SchedulerState::default();
+        let win = Window::from(1000);
+
+        assert!(state.should_decorate(win, viewport(0, 0)));
+        assert!(!state.should_decorate(win, viewport(0, 5)));
+
+        // Nothing changed since the last cycle.
+        state.handled.clear();
+        assert!(!state.should_decorate(win, viewport(0, 0)));
+    }
+
+    #[test]
+    fn changes_trigger_decorations() {
+        let mut state = SchedulerState::default();
+        let win = Window::from(1000);
+        assert!(state.should_decorate(win, viewport(0, 0)));
+
+        // The viewport was scrolled.
+        state.handled.clear();
+        assert!(state.should_decorate(win, viewport(0, 5)));
+
+        // The buffer was invalidated.
+        state.handled.clear();
+        assert!(state.should_decorate(win, viewport(1, 5)));
+
+        // The window was invalidated.
+        state.handled.clear();
+        state.dirty_wins.insert(win);
+        assert!(state.should_decorate(win, viewport(1, 5)));
+        assert!(state.dirty_wins.is_empty());
+    }
+
+    #[test]
+    fn closed_windows_and_wiped_buffers_are_pruned() {
+        let mut state = SchedulerState::default();
+        let (open, closed) = (Window::from(1000), Window::from(1001));
+        let (loaded, wiped) = (Buffer::from(1), Buffer::from(2));
+
+        state.should_decorate(open, viewport(0, 0));
+        state.should_decorate(closed, viewport(0, 0));
+        state.dirty_wins.insert(closed);
+        state.buf_ticks.insert(loaded, 1);
+        state.buf_ticks.insert(wiped, 1);
+
+        state.prune(|win| *win == open, |buf| *buf == loaded);
+
+        assert_eq!(state.decorated.keys().collect::<Vec<_>>(), [&open]);
+        assert!(state.dirty_wins.is_empty());
+        assert_eq!(state.buf_ticks.keys().collect::<Vec<_>>(), [&loaded]);
+    }
+}
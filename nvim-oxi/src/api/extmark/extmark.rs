@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use nvim_types::error::Error as NvimError;
+
+use super::ffi::*;
+use super::opts::*;
+use crate::api::types::Namespace;
+use crate::object::FromObject;
+use crate::Result;
+
+/// Binding to `nvim_create_namespace`.
+///
+/// Creates a new namespace or gets the id of an existing one. If `name`
+/// is empty a new, anonymous namespace is created.
+pub fn create_namespace(name: &str) -> Namespace {
+    let id = unsafe { nvim_create_namespace(name.into()) };
+    Namespace(id.try_into().expect("namespace ids are always positive"))
+}
+
+/// Binding to `nvim_get_namespaces`.
+///
+/// Returns the named namespaces, keyed by name. Anonymous namespaces aren't
+/// included.
+pub fn get_namespaces() -> HashMap<String, Namespace> {
+    let namespaces = unsafe { nvim_get_namespaces() };
+    FromObject::from_obj(namespaces.into())
+        .expect("namespace ids are always positive")
+}
+
+/// Binding to `nvim_set_decoration_provider`.
+///
+/// Sets or changes a decoration provider for a namespace.
+pub fn set_decoration_provider(
+    ns: Namespace,
+    opts: DecorationProviderOpts,
+) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe { nvim_set_decoration_provider(ns.into(), opts.into(), &mut err) };
+    err.into_err_or_else(|| ())
+}
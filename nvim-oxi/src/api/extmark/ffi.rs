@@ -0,0 +1,30 @@
+use nvim_types::{
+    dictionary::Dictionary,
+    error::Error,
+    string::String,
+    BufHandle,
+    Integer,
+};
+
+extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/buffer.c#L1340
+    pub(super) fn nvim__buf_redraw_range(
+        buf: BufHandle,
+        first: Integer,
+        last: Integer,
+        err: *mut Error,
+    );
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/extmark.c#L41
+    pub(super) fn nvim_create_namespace(name: String) -> Integer;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/extmark.c#L59
+    pub(super) fn nvim_get_namespaces() -> Dictionary;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/extmark.c#L1025
+    pub(super) fn nvim_set_decoration_provider(
+        ns_id: Integer,
+        opts: Dictionary,
+        err: *mut Error,
+    );
+}
@@ -0,0 +1,8 @@
+mod decoration_scheduler;
+#[allow(clippy::module_inception)]
+mod extmark;
+mod ffi;
+pub mod opts;
+
+pub use decoration_scheduler::*;
+pub use extmark::*;
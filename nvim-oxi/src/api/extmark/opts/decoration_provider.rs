@@ -0,0 +1,109 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+use crate::api::{Buffer, Window};
+use crate::lua::LuaFnMut;
+
+/// Arguments passed to the function registered to `on_start`.
+pub type OnStartArgs = (
+    String, // the string literal "start"
+    usize,  // display tick
+);
+
+/// Arguments passed to the function registered to `on_end`. The first
+/// element is the string literal "end".
+pub type OnEndArgs = OnStartArgs;
+
+/// Arguments passed to the function registered to `on_buf`.
+pub type OnBufArgs = (
+    String, // the string literal "buf"
+    Buffer, // buffer
+    usize,  // display tick
+);
+
+/// Arguments passed to the function registered to `on_win`.
+pub type OnWinArgs = (
+    String, // the string literal "win"
+    Window, // window
+    Buffer, // buffer shown in the window
+    usize,  // topline (0-indexed)
+    usize,  // guess of the botline (0-indexed)
+);
+
+/// Arguments passed to the function registered to `on_line`.
+pub type OnLineArgs = (
+    String, // the string literal "line"
+    Window, // window
+    Buffer, // buffer
+    usize,  // row (0-indexed)
+);
+
+/// Returning `false` from `on_start` skips the provider for the current
+/// redraw cycle.
+pub type DontSkipRedrawCycle = bool;
+
+/// Returning `false` from `on_win` skips the `on_line` callbacks for that
+/// window.
+pub type DontSkipOnLines = bool;
+
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct DecorationProviderOpts {
+    #[builder(setter(custom))]
+    on_start: Option<LuaFnMut<OnStartArgs, DontSkipRedrawCycle>>,
+
+    #[builder(setter(custom))]
+    on_buf: Option<LuaFnMut<OnBufArgs, ()>>,
+
+    #[builder(setter(custom))]
+    on_win: Option<LuaFnMut<OnWinArgs, DontSkipOnLines>>,
+
+    #[builder(setter(custom))]
+    on_line: Option<LuaFnMut<OnLineArgs, ()>>,
+
+    #[builder(setter(custom))]
+    on_end: Option<LuaFnMut<OnEndArgs, ()>>,
+}
+
+impl DecorationProviderOpts {
+    #[inline(always)]
+    pub fn builder() -> DecorationProviderOptsBuilder {
+        DecorationProviderOptsBuilder::default()
+    }
+}
+
+macro_rules! luaref_setter {
+    ($name:ident, $args:ident, $ret:ty) => {
+        pub fn $name<F>(&mut self, fun: F) -> &mut Self
+        where
+            F: FnMut($args) -> crate::Result<$ret> + 'static,
+        {
+            self.$name = Some(Some(fun.into()));
+            self
+        }
+    };
+}
+
+impl DecorationProviderOptsBuilder {
+    luaref_setter!(on_start, OnStartArgs, DontSkipRedrawCycle);
+
+    luaref_setter!(on_buf, OnBufArgs, ());
+
+    luaref_setter!(on_win, OnWinArgs, DontSkipOnLines);
+
+    luaref_setter!(on_line, OnLineArgs, ());
+
+    luaref_setter!(on_end, OnEndArgs, ());
+}
+
+impl From<DecorationProviderOpts> for Dictionary {
+    fn from(opts: DecorationProviderOpts) -> Self {
+        Self::from_iter([
+            ("on_start", Object::from(opts.on_start)),
+            ("on_buf", opts.on_buf.into()),
+            ("on_win", opts.on_win.into()),
+            ("on_line", opts.on_line.into()),
+            ("on_end", opts.on_end.into()),
+        ])
+    }
+}
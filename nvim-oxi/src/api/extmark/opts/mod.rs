@@ -0,0 +1,3 @@
+mod decoration_provider;
+
+pub use decoration_provider::*;
@@ -0,0 +1,31 @@
+use super::opts::OptionValueOpts;
+use super::{get_option_value, set_option_value};
+use crate::{Error, Result};
+
+/// Returns the transparency of the popup menu, from 0 (opaque) to 100
+/// (fully transparent).
+pub fn get_pum_blend() -> Result<u8> {
+    get_option_value("pumblend", &OptionValueOpts::default())
+}
+
+/// Sets the transparency of the popup menu, from 0 (opaque) to 100 (fully
+/// transparent). Values above 100 are clamped.
+///
+/// Fails if `'termguicolors'` is off, in which case the option would have
+/// no effect.
+pub fn set_pum_blend(blend: u8) -> Result<()> {
+    require_termguicolors("pumblend")?;
+    set_option_value("pumblend", blend.min(100), &OptionValueOpts::default())
+}
+
+/// Fails unless `'termguicolors'` is set, which `option` needs to have any
+/// effect.
+pub(crate) fn require_termguicolors(option: &'static str) -> Result<()> {
+    match get_option_value("termguicolors", &OptionValueOpts::default())? {
+        true => Ok(()),
+        false => Err(Error::PreconditionFailedError {
+            option,
+            requires: "termguicolors",
+        }),
+    }
+}
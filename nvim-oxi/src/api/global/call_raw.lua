@@ -0,0 +1,6 @@
+local name = ...
+local fun = vim.api[name]
+if fun == nil then
+  error(('%s is not an API function'):format(name), 0)
+end
+return fun(select(2, ...))
@@ -0,0 +1,29 @@
+use nvim_types::object::Object;
+
+use crate::api;
+use crate::object::FromObject;
+use crate::Result;
+
+/// Calls the API function `name` with the given arguments, deserializing its
+/// return value into `Ret`.
+///
+/// Meant for functions this crate doesn't have bindings for yet, e.g. ones
+/// added in a Neovim nightly. The call goes through `vim.api`, so `args`
+/// can contain Lua functions and it's slower than a typed binding.
+///
+/// ```ignore
+/// let args = [Object::from(0), 0.into(), (-1).into(), false.into()];
+/// let lines: Vec<String> = api::call_raw("nvim_buf_get_lines", args)?;
+/// ```
+pub fn call_raw<Args, Ret>(name: &str, args: Args) -> Result<Ret>
+where
+    Args: IntoIterator,
+    Args::Item: Into<Object>,
+    Ret: FromObject,
+{
+    let args = std::iter::once(Object::from(name))
+        .chain(args.into_iter().map(Into::into))
+        .collect::<Vec<_>>();
+
+    api::exec_lua(include_str!("./call_raw.lua"), args)
+}
@@ -0,0 +1,106 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_types::{
+    dictionary::Dictionary,
+    error::Error as NvimError,
+    object::Object,
+    string::String as NvimString,
+};
+
+use super::ffi::*;
+use crate::api::types::RegisterType;
+use crate::api::vimscript::{exec2, opts::ExecOpts};
+use crate::lua::LuaFnMut;
+use crate::Result;
+
+/// The registers backed by the system clipboard.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ClipboardRegister {
+    /// The `+` register.
+    Plus,
+
+    /// The `*` register.
+    Star,
+}
+
+impl ClipboardRegister {
+    #[inline]
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Plus => "+",
+            Self::Star => "*",
+        }
+    }
+}
+
+/// Registers a clipboard provider (see `:h g:clipboard`) that calls `copy`
+/// when text is yanked into a clipboard register and `paste` when it's read
+/// from one, replacing any provider Neovim had already detected.
+///
+/// The lines never include the trailing empty line Neovim uses to mark
+/// linewise and blockwise contents.
+pub fn set_clipboard_provider<Copy, Paste>(
+    name: &str,
+    copy: Copy,
+    paste: Paste,
+) -> Result<()>
+where
+    Copy: FnMut(ClipboardRegister, Vec<NvimString>, RegisterType) -> Result<()>
+        + 'static,
+    Paste: FnMut(ClipboardRegister) -> Result<(Vec<NvimString>, RegisterType)>
+        + 'static,
+{
+    let copy = Rc::new(RefCell::new(copy));
+    let paste = Rc::new(RefCell::new(paste));
+
+    let registers = [ClipboardRegister::Plus, ClipboardRegister::Star];
+
+    let copy_fns = registers.map(|reg| {
+        let copy = Rc::clone(&copy);
+        let fun = LuaFnMut::from(
+            move |(mut lines, regtype): (Vec<NvimString>, String)| {
+                let regtype = regtype.parse::<RegisterType>()?;
+                if regtype != RegisterType::Charwise
+                    && lines.last().is_some_and(NvimString::is_empty)
+                {
+                    lines.pop();
+                }
+                (copy.borrow_mut())(reg, lines, regtype)
+            },
+        );
+        (reg.as_str(), Object::from(fun))
+    });
+
+    let paste_fns = registers.map(|reg| {
+        let paste = Rc::clone(&paste);
+        let fun = LuaFnMut::from(move |()| {
+            let (mut lines, regtype) = (paste.borrow_mut())(reg)?;
+
+            if regtype != RegisterType::Charwise {
+                lines.push(NvimString::default());
+            }
+
+            Ok((lines, regtype.to_string()))
+        });
+        (reg.as_str(), Object::from(fun))
+    });
+
+    let provider = Dictionary::from_iter([
+        ("name", Object::from(name)),
+        ("copy", Dictionary::from_iter(copy_fns).into()),
+        ("paste", Dictionary::from_iter(paste_fns).into()),
+    ]);
+
+    let mut err = NvimError::new();
+    unsafe { nvim_set_var("clipboard".into(), provider.into(), &mut err) };
+    // Make Neovim pick up the new provider.
+    err.into_err_or_flatten(|| {
+        exec2(
+            "unlet! g:loaded_clipboard_provider\nruntime \
+             autoload/provider/clipboard.vim",
+            &ExecOpts::default(),
+        )
+        .map(|_| ())
+    })
+}
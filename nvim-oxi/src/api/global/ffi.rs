@@ -13,9 +13,21 @@ use nvim_types::{
 };
 
 extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L1957
+    #[cfg(feature = "diagnostics")]
+    pub(super) fn nvim__stats() -> Dictionary;
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L1231
     pub(super) fn nvim_chan_send(chan: Integer, data: String, err: *mut Error);
 
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L2279
+    #[cfg(feature = "neovim-nightly")]
+    pub(super) fn nvim_complete_set(
+        index: Integer,
+        opts: *const Dictionary,
+        err: *mut Error,
+    ) -> Dictionary;
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L1057
     pub(super) fn nvim_create_buf(
         listed: bool,
@@ -72,6 +84,13 @@ extern "C" {
         errr: *mut Error,
     ) -> Dictionary;
 
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L456
+    pub(super) fn nvim_exec_lua(
+        code: String,
+        args: Array,
+        err: *mut Error,
+    ) -> Object;
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L235
     pub(super) fn nvim_feedkeys(keys: String, mode: String, escape_ks: bool);
 
@@ -121,6 +140,15 @@ extern "C" {
         error: *mut Error,
     ) -> Dictionary;
 
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L98
+    #[cfg(feature = "neovim-0-9")]
+    pub(super) fn nvim_get_hl(
+        ns_id: Integer,
+        opts: *const Dictionary,
+        arena: *mut libc::c_void,
+        err: *mut Error,
+    ) -> Dictionary;
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L75
     pub(super) fn nvim_get_hl_by_name(
         name: String,
@@ -295,6 +323,12 @@ extern "C" {
         err: *mut Error,
     );
 
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L185
+    pub(super) fn nvim_set_hl_ns(ns_id: Integer, err: *mut Error);
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L205
+    pub(super) fn nvim_set_hl_ns_fast(ns_id: Integer, err: *mut Error);
+
     // https://github.com/neovim/neovim/blob/master/src/nvim/api/vim.c#L1560
     pub(super) fn nvim_set_keymap(
         channel_id: u64,
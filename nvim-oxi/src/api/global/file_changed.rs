@@ -0,0 +1,134 @@
+use std::path::PathBuf;
+
+use nvim_types::string::String as NvimString;
+
+use super::{get_vvar, set_vvar};
+use crate::api::autocmd::{create_autocmd, opts::CreateAutocmdOpts};
+use crate::api::types::CmdInfos;
+use crate::api::vimscript::{call_function, cmd, opts::CmdOpts};
+use crate::api::Buffer;
+use crate::{Error, Result};
+
+/// Why a file has been detected as changed outside of Neovim. See
+/// `:h v:fcs_reason` for details.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FileChangedReason {
+    /// The file no longer exists.
+    Deleted,
+
+    /// The file contents, mode or timestamp changed and the buffer is
+    /// modified.
+    Conflict,
+
+    /// The file contents changed.
+    Changed,
+
+    /// The file mode changed.
+    Mode,
+
+    /// Only the file timestamp changed.
+    Time,
+}
+
+/// What Neovim should do with a buffer whose file has changed. See
+/// `:h v:fcs_choice` for details.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum FileChangedDecision {
+    /// Reload the buffer. Doesn't work if the file was deleted.
+    Reload,
+
+    /// Reload the buffer, detecting the options like when editing a new
+    /// file.
+    Edit,
+
+    /// Ask the user what to do, as if there was no handler.
+    Ask,
+
+    /// Do nothing, the handler has taken care of everything.
+    #[default]
+    Nothing,
+}
+
+impl FileChangedDecision {
+    #[inline]
+    const fn as_str(&self) -> &'static str {
+        use FileChangedDecision::*;
+        match self {
+            Reload => "reload",
+            Edit => "edit",
+            Ask => "ask",
+            Nothing => "",
+        }
+    }
+}
+
+/// Information about a file changed outside of Neovim, passed to the
+/// callback registered with [`on_file_changed`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FileChangedInfo {
+    /// The buffer editing the file.
+    pub buffer: Buffer,
+
+    /// The full path of the changed file.
+    pub file: PathBuf,
+
+    pub reason: FileChangedReason,
+}
+
+/// Checks if the file edited in `buf` was changed outside of Neovim,
+/// triggering the handlers registered with [`on_file_changed`] if it was.
+/// See `:h :checktime` for details.
+pub fn checktime(buf: Buffer) -> Result<()> {
+    let infos = CmdInfos::builder()
+        .cmd("checktime")
+        .args([buf.0.to_string()])
+        .build()
+        .expect("all fields have defaults");
+
+    cmd(&infos, &CmdOpts::default()).map(|_| ())
+}
+
+/// Registers a `FileChangedShell` autocommand which calls `callback` every
+/// time a file is detected as changed outside of Neovim, using its return
+/// value to decide what to do with the buffer. Returns the autocommand's
+/// id.
+pub fn on_file_changed<F>(mut callback: F) -> Result<u32>
+where
+    F: FnMut(FileChangedInfo) -> Result<FileChangedDecision> + 'static,
+{
+    let opts = CreateAutocmdOpts::builder()
+        .callback(move |args| {
+            let reason = match get_vvar::<String>("fcs_reason")?.as_str() {
+                "deleted" => FileChangedReason::Deleted,
+                "conflict" => FileChangedReason::Conflict,
+                "changed" => FileChangedReason::Changed,
+                "mode" => FileChangedReason::Mode,
+                "time" => FileChangedReason::Time,
+                other => {
+                    return Err(Error::DeserializeError(format!(
+                        "unknown v:fcs_reason \"{other}\""
+                    )))
+                },
+            };
+
+            // `args.file` is a `String`, which can't hold a path that isn't
+            // valid UTF-8.
+            let file =
+                call_function::<_, NvimString>("expand", ["<afile>:p"])?;
+
+            let info = FileChangedInfo {
+                buffer: args.buffer,
+                file: file.into(),
+                reason,
+            };
+
+            set_vvar("fcs_choice", callback(info)?.as_str())?;
+
+            Ok(false)
+        })
+        .build()
+        .expect("all fields have defaults");
+
+    create_autocmd(["FileChangedShell"], &opts)
+}
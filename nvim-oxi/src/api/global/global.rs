@@ -1,17 +1,50 @@
+use std::path::PathBuf;
+
 use nvim_types::{
     array::Array,
     dictionary::Dictionary,
     error::Error as NvimError,
     object::Object,
     string::String as NvimString,
+    Integer,
 };
 
-// use super::opts::*;
 use super::ffi::*;
-use crate::{Buffer, Result};
+use super::opts::*;
+use crate::api::types::{
+    ApiMetadata,
+    EditorContext,
+    GotMode,
+    KeymapInfos,
+    Mode,
+    Namespace,
+    OptionInfos,
+    PastePhase,
+    StatuslineInfos,
+};
+use crate::api::{self, keyset, Buffer, TabPage, Window};
+use crate::lua::LUA_INTERNAL_CALL;
+use crate::object::{FromObject, ToObject};
+use crate::Result;
 
 // chan_send
 
+/// Binding to `nvim_complete_set`.
+///
+/// Sets the info displayed next to the completion item at `index` in the
+/// popup menu.
+#[cfg(feature = "neovim-nightly")]
+pub fn complete_set(
+    index: usize,
+    info: &str,
+) -> Result<crate::api::types::CompleteSetInfos> {
+    let opts = Dictionary::from_iter([("info", info)]);
+    let mut err = NvimError::new();
+    let infos =
+        unsafe { nvim_complete_set(index.try_into()?, &opts, &mut err) };
+    err.into_err_or_flatten(|| FromObject::from_obj(infos.into()))
+}
+
 /// Binding to `nvim_create_buf`.
 pub fn create_buf(is_listed: bool, is_scratch: bool) -> Result<Buffer> {
     let mut err = NvimError::new();
@@ -23,11 +56,27 @@ pub fn create_buf(is_listed: bool, is_scratch: bool) -> Result<Buffer> {
 
 // del_current_line
 
-// del_keymap
+/// Binding to `nvim_del_keymap`.
+///
+/// Unmaps a global mapping for the given mode.
+pub fn del_keymap(mode: Mode, lhs: &str) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe {
+        nvim_del_keymap(LUA_INTERNAL_CALL, mode.into(), lhs.into(), &mut err)
+    };
+    err.into_err_or_else(|| ())
+}
 
 // del_mark
 
-// del_user_command
+/// Binding to `nvim_del_user_command`.
+///
+/// Deletes a global user-defined command.
+pub fn del_user_command(name: &str) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe { nvim_del_user_command(name.into(), &mut err) };
+    err.into_err_or_else(|| ())
+}
 
 // del_var
 
@@ -53,17 +102,84 @@ where
     err.into_err_or_else(|| ())
 }
 
-// err_write
+/// Binding to `nvim_err_write`.
+///
+/// Writes a message to the error buffer. The message is displayed once a
+/// newline is written, see [`err_writeln`].
+pub fn err_write<Msg: Into<NvimString>>(msg: Msg) {
+    unsafe { nvim_err_write(msg.into()) }
+}
+
+/// Binding to `nvim_err_writeln`.
+///
+/// Writes a message to the error buffer followed by a newline, displaying
+/// it as an error message.
+pub fn err_writeln<Msg: Into<NvimString>>(msg: Msg) {
+    unsafe { nvim_err_writeln(msg.into()) }
+}
 
-// err_writeln
+/// Binding to `nvim_eval_statusline`.
+///
+/// Evaluates a statusline string, returning the result and its display
+/// width.
+pub fn eval_statusline(
+    str: &str,
+    opts: &EvalStatuslineOpts,
+) -> Result<StatuslineInfos> {
+    let mut err = NvimError::new();
+    let dict = unsafe {
+        nvim_eval_statusline(
+            str.into(),
+            &keyset("eval_statusline", opts),
+            &mut err,
+        )
+    };
+    err.into_err_or_flatten(|| StatuslineInfos::from_obj(dict.into()))
+}
 
-// eval_statusline
+/// Binding to `nvim_exec_lua`.
+///
+/// Executes a chunk of Lua code, which receives `args` as `...`, and
+/// returns its result. Fails if the specified type couldn't be
+/// deserialized from the returned object.
+pub fn exec_lua<Args, Ret>(code: &str, args: Args) -> Result<Ret>
+where
+    Args: IntoIterator,
+    Args::Item: Into<Object>,
+    Ret: FromObject,
+{
+    // Not collected directly into an `Array` since that would skip `nil`s,
+    // shifting the position of the following arguments.
+    let args =
+        Array::from(args.into_iter().map(Into::into).collect::<Vec<_>>());
+    let mut err = NvimError::new();
+    let res = unsafe { nvim_exec_lua(code.into(), args, &mut err) };
+    err.into_err_or_flatten(|| Ret::from_obj(res))
+        .map_err(|err| err.in_function("nvim_exec_lua"))
+}
 
-// feedkeys
+/// Binding to `nvim_feedkeys`.
+///
+/// Sends input keys to Neovim, subject to various quirks controlled by
+/// `mode` flags (see `:h feedkeys()`). Set `escape_ks` to false if `keys`
+/// come from `replace_termcodes`.
+pub fn feedkeys<Keys: Into<NvimString>>(
+    keys: Keys,
+    mode: &str,
+    escape_ks: bool,
+) {
+    unsafe { nvim_feedkeys(keys.into(), mode.into(), escape_ks) }
+}
 
 // get_all_options_info
 
-// get_api_info
+/// Returns the metadata of the API, i.e. `:h api-metadata`, to detect at
+/// runtime which functions and features the running Neovim supports.
+pub fn get_api_info() -> Result<ApiMetadata> {
+    // `nvim_get_api_info` needs a channel, while `api_info()` returns the
+    // same metadata without one.
+    api::call_function("api_info", [] as [Object; 0])
+}
 
 // get_chan_info
 
@@ -73,55 +189,175 @@ where
 
 // get_commands
 
-// get_context
+/// Binding to `nvim_get_context`.
+///
+/// Gets a snapshot of the current editor state.
+pub fn get_context(opts: &GetContextOpts) -> Result<EditorContext> {
+    let mut err = NvimError::new();
+    let ctx = unsafe { nvim_get_context(&keyset("context", opts), &mut err) };
+    err.into_err_or_else(|| EditorContext(ctx))
+}
 
 /// Binding to `nvim_get_current_buf`.
 pub fn get_current_buf() -> Buffer {
     Buffer::from(unsafe { nvim_get_current_buf() })
 }
 
-// get_current_line
+/// Binding to `nvim_get_current_line`.
+///
+/// Gets the current line.
+pub fn get_current_line() -> Result<NvimString> {
+    let mut err = NvimError::new();
+    let line = unsafe { nvim_get_current_line(&mut err) };
+    err.into_err_or_else(|| line)
+}
 
-// get_current_tabpage
+/// Binding to `nvim_get_current_tabpage`.
+pub fn get_current_tabpage() -> TabPage {
+    TabPage::from(unsafe { nvim_get_current_tabpage() })
+}
 
-// get_current_win
+/// Binding to `nvim_get_current_win`.
+pub fn get_current_win() -> Window {
+    Window::from(unsafe { nvim_get_current_win() })
+}
+
+/// Binding to `nvim_get_hl`.
+///
+/// Returns the highlight groups defined in the namespace `ns`, keyed by
+/// name, or the attributes of a single group if `opts` selects one by id or
+/// name.
+#[cfg(feature = "neovim-0-9")]
+pub fn get_hl(ns: Namespace, opts: &GetHighlightOpts) -> Result<Dictionary> {
+    let mut err = NvimError::new();
+    // Without an arena the result is allocated on the heap, and freed when
+    // the `Dictionary` is dropped.
+    let hl = unsafe {
+        nvim_get_hl(
+            ns.into(),
+            &keyset("get_highlight", opts),
+            std::ptr::null_mut(),
+            &mut err,
+        )
+    };
+    err.into_err_or_else(|| hl)
+}
 
 // get_hl_by_id
 
-// get_hl_by_name
+/// Binding to `nvim_get_hl_by_name`.
+///
+/// Returns the attributes of the highlight group `name`, with the colors as
+/// RGB values if `rgb` is true or as cterm color numbers otherwise.
+pub fn get_hl_by_name(name: &str, rgb: bool) -> Result<Dictionary> {
+    let mut err = NvimError::new();
+    let hl = unsafe { nvim_get_hl_by_name(name.into(), rgb, &mut err) };
+    err.into_err_or_else(|| hl)
+}
 
 // get_hl_id_by_name
 
-// get_keymap
+/// Binding to `nvim_get_keymap`.
+///
+/// Returns an iterator over the global `KeymapInfos` of `mode`. Use
+/// `Buffer::get_keymap` for the buffer-local ones.
+pub fn get_keymap(mode: Mode) -> Result<impl Iterator<Item = KeymapInfos>> {
+    let maps = unsafe { nvim_get_keymap(LUA_INTERNAL_CALL, mode.into()) };
+    maps.into_iter()
+        .map(KeymapInfos::from_obj)
+        .collect::<Result<Vec<_>>>()
+        .map(Vec::into_iter)
+}
 
 // get_mark
 
 /// Binding to `nvim_get_mode`.
-pub fn get_mode() -> Dictionary {
-    unsafe { nvim_get_mode() }
-    // (
-    //     dict.get("mode").expect("`mode` key is present"),
-    //     dict.get("blocking").expect("`blocking` key is present"),
-    // )
+///
+/// Returns the current mode and whether Neovim is waiting for input.
+pub fn get_mode() -> Result<GotMode> {
+    GotMode::from_obj(unsafe { nvim_get_mode() }.into())
 }
 
 // get_option
 
-// get_option_info
+/// Binding to `nvim_get_option_info`.
+///
+/// Gets the option information for one option.
+pub fn get_option_info(name: &str) -> Result<OptionInfos> {
+    let mut err = NvimError::new();
+    let infos = unsafe { nvim_get_option_info(name.into(), &mut err) };
+    err.into_err_or_flatten(|| OptionInfos::from_obj(infos.into()))
+}
 
-// get_option_value
+/// Binding to `nvim_get_option_value`.
+///
+/// Gets the value of an option. Fails if the specified type couldn't be
+/// deserialized from the returned object.
+pub fn get_option_value<Value>(
+    name: &str,
+    opts: &OptionValueOpts,
+) -> Result<Value>
+where
+    Value: FromObject,
+{
+    let mut err = NvimError::new();
+    let obj = unsafe {
+        nvim_get_option_value(name.into(), &keyset("option", opts), &mut err)
+    };
+    err.into_err_or_flatten(|| Value::from_obj(obj))
+}
 
 // get_proc
 
 // get_proc_children
 
-// get_runtime_file
+/// Binding to `nvim_get_runtime_file`.
+///
+/// Finds files matching `name` in the runtime directories, e.g.
+/// `"lua/my_plugin/init.lua"`. `name` can contain wildcards. Returns every
+/// match if `all` is true, or only the first one.
+pub fn get_runtime_file(name: &str, all: bool) -> Result<Vec<PathBuf>> {
+    let mut err = NvimError::new();
+    let files = unsafe { nvim_get_runtime_file(name.into(), all, &mut err) };
+    err.into_err_or_flatten(|| FromObject::from_obj(files.into()))
+}
 
-// get_var
+/// Binding to `nvim_get_var`.
+///
+/// Gets a global (g:) variable. Fails if the specified type couldn't be
+/// deserialized from the returned object.
+pub fn get_var<Value>(name: &str) -> Result<Value>
+where
+    Value: FromObject,
+{
+    let mut err = NvimError::new();
+    let obj = unsafe { nvim_get_var(name.into(), &mut err) };
+    err.into_err_or_flatten(|| Value::from_obj(obj))
+}
 
-// get_vvar
+/// Binding to `nvim_get_vvar`.
+///
+/// Gets a `v:` variable. Fails if the specified type couldn't be
+/// deserialized from the returned object.
+pub fn get_vvar<Value>(name: &str) -> Result<Value>
+where
+    Value: FromObject,
+{
+    let mut err = NvimError::new();
+    let obj = unsafe { nvim_get_vvar(name.into(), &mut err) };
+    err.into_err_or_flatten(|| Value::from_obj(obj))
+}
 
-// input
+/// Binding to `nvim_input`.
+///
+/// Queues raw user input, returning the number of bytes actually written.
+/// Unlike [`feedkeys`], key notation like `<CR>` is always interpreted, so
+/// a literal `<` has to be sent as `<LT>`. See also
+/// [`Keys`](crate::api::types::Keys).
+pub fn input<Keys: Into<NvimString>>(keys: Keys) -> Result<usize> {
+    let written = unsafe { nvim_input(keys.into()) };
+    Ok(written.try_into()?)
+}
 
 // input_mouse
 
@@ -137,15 +373,47 @@ pub fn get_mode() -> Dictionary {
 
 // list_wins
 
-// load_context
+/// Binding to `nvim_load_context`.
+///
+/// Sets the editor state from a context.
+pub fn load_context(ctx: &EditorContext) {
+    unsafe { nvim_load_context(ctx.0.clone()) };
+}
 
 // notify
 
-// open_term
+/// Binding to `nvim_open_term`.
+///
+/// Opens a terminal instance in a buffer. Returns the id of the channel
+/// connected to the terminal.
+pub fn open_term(buffer: Buffer, opts: &OpenTermOpts) -> Result<u32> {
+    let mut err = NvimError::new();
+    let chan = unsafe { nvim_open_term(buffer.0, opts.into(), &mut err) };
+    err.into_err_or_flatten(|| Ok(chan.try_into()?))
+}
 
-// out_write
+/// Binding to `nvim_out_write`.
+///
+/// Writes a message to the message area. The message is only displayed
+/// once a newline is written.
+pub fn out_write<Msg: Into<NvimString>>(msg: Msg) {
+    unsafe { nvim_out_write(msg.into()) }
+}
 
-// paste
+/// Binding to `nvim_paste`.
+///
+/// Pastes at cursor, in any mode. Returns `false` if the client should
+/// cancel the paste.
+pub fn paste<Data: Into<NvimString>>(
+    data: Data,
+    crlf: bool,
+    phase: PastePhase,
+) -> Result<bool> {
+    let mut err = NvimError::new();
+    let go_on =
+        unsafe { nvim_paste(data.into(), crlf, phase.into(), &mut err) };
+    err.into_err_or_else(|| go_on)
+}
 
 // put
 
@@ -159,7 +427,29 @@ pub fn replace_termcodes<Str: Into<NvimString>>(
     unsafe { nvim_replace_termcodes(str.into(), from_part, do_lt, special) }
 }
 
-// select_popupmenu_item
+/// Binding to `nvim_select_popupmenu_item`.
+///
+/// Selects an item in the completion popup menu, or goes back to the
+/// original text if `item` is `None`. If `insert` is set the item is
+/// inserted in the buffer, and if `finish` is set the completion is ended.
+pub fn select_popupmenu_item(
+    item: Option<usize>,
+    insert: bool,
+    finish: bool,
+) -> Result<()> {
+    let item = item.map(Integer::try_from).transpose()?.unwrap_or(-1);
+    let mut err = NvimError::new();
+    unsafe {
+        nvim_select_popupmenu_item(
+            item,
+            insert,
+            finish,
+            Dictionary::new(),
+            &mut err,
+        )
+    };
+    err.into_err_or_else(|| ())
+}
 
 // set_current_buf
 
@@ -173,14 +463,110 @@ pub fn replace_termcodes<Str: Into<NvimString>>(
 
 // set_hl
 
+/// Binding to `nvim_set_hl_ns`.
+///
+/// Sets the highlight namespace used by the windows that don't have one of
+/// their own (see `Window::set_hl_ns`). Pass [`Namespace::GLOBAL`] to go
+/// back to the highlight groups defined with `:highlight`.
+pub fn set_hl_ns(ns: Namespace) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe { nvim_set_hl_ns(ns.into(), &mut err) };
+    err.into_err_or_else(|| ())
+}
+
+/// Binding to `nvim_set_hl_ns_fast`.
+///
+/// Like [`set_hl_ns`], but meant to be called from the callbacks of a
+/// decoration provider: the namespace is only used for the current redraw,
+/// and isn't validated.
+pub fn set_hl_ns_fast(ns: Namespace) -> Result<()> {
+    let mut err = NvimError::new();
+    unsafe { nvim_set_hl_ns_fast(ns.into(), &mut err) };
+    err.into_err_or_else(|| ())
+}
+
 // set_keymap
 
 // set_option
 
-// set_option_value
+/// Binding to `nvim_set_option_value`.
+///
+/// Sets the value of an option.
+pub fn set_option_value<Value>(
+    name: &str,
+    value: Value,
+    opts: &OptionValueOpts,
+) -> Result<()>
+where
+    Value: ToObject,
+{
+    let mut err = NvimError::new();
+    unsafe {
+        nvim_set_option_value(
+            name.into(),
+            value.to_obj()?,
+            &keyset("option", opts),
+            &mut err,
+        )
+    };
+    err.into_err_or_else(|| ())
+}
 
 // set_var
 
-// set_vvar
+/// Binding to `nvim_set_vvar`.
+///
+/// Sets a `v:` variable, if it's not readonly.
+pub fn set_vvar<Value>(name: &str, value: Value) -> Result<()>
+where
+    Value: ToObject,
+{
+    let mut err = NvimError::new();
+    unsafe { nvim_set_vvar(name.into(), value.to_obj()?, &mut err) };
+    err.into_err_or_else(|| ())
+}
+
+/// Binding to `nvim__stats`.
+///
+/// Returns internal statistics about the editor, useful to debug and
+/// profile plugins. Unstable, see [`NvimStats`] for details.
+///
+/// [`NvimStats`]: crate::api::types::NvimStats
+#[cfg(feature = "diagnostics")]
+pub fn stats() -> Result<crate::api::types::NvimStats> {
+    FromObject::from_obj(unsafe { nvim__stats() }.into())
+}
 
-// strwidth
+/// Binding to `nvim_strwidth`.
+///
+/// Returns the number of display cells `text` occupies. Tabs count as one
+/// cell.
+pub fn strwidth(text: &str) -> Result<usize> {
+    let mut err = NvimError::new();
+    let width = unsafe { nvim_strwidth(text.into(), &mut err) };
+    err.into_err_or_flatten(|| width.try_into().map_err(Into::into))
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+
+    #[nvim_oxi::test]
+    fn exec_lua_keeps_nil_arguments() -> Result<()> {
+        let args = [Object::nil(), 1.into(), Object::nil(), "x".into()];
+        let (count, types): (usize, Vec<String>) = exec_lua(
+            "local n = select('#', ...)
+             local types = {}
+             for i = 1, n do types[i] = type((select(i, ...))) end
+             return { n, types }",
+            args,
+        )?;
+        assert_eq!(count, 4);
+        assert_eq!(types, ["nil", "number", "nil", "string"]);
+
+        let none: Option<u32> = exec_lua("return ...", [Object::nil()])?;
+        assert_eq!(none, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use super::{feedkeys, paste, replace_termcodes};
+use crate::api::types::PastePhase;
+use crate::api::Window;
+use crate::Result;
+
+/// Executes `keys` in Normal mode, like `:normal!`. Key notation such as
+/// `<CR>` or `<C-w>` is replaced with the corresponding termcodes and
+/// mappings are not applied.
+pub fn normal(keys: &str) {
+    // `x` executes the keys right away instead of just adding them to the
+    // typeahead buffer, which is what makes `run_normal_in_win` work.
+    feedkeys(replace_termcodes(keys, true, true, true), "nx", false)
+}
+
+/// Inserts `text` at the cursor position as if it had been pasted, i.e.
+/// without triggering mappings or abbreviations. Works in any mode.
+pub fn insert_text(text: &str) -> Result<()> {
+    paste(text, true, PastePhase::Single).map(|_| ())
+}
+
+/// Executes `keys` in Normal mode with `win` as the temporary current
+/// window. See [`normal`] for details.
+pub fn run_normal_in_win(win: Window, keys: &str) -> Result<()> {
+    let keys = keys.to_owned();
+    win.call(move |()| {
+        normal(&keys);
+        Ok(())
+    })
+}
@@ -0,0 +1,27 @@
+use crate::api::types::{Changelist, Jumplist, MarkInfos};
+use crate::api::{call_function, Buffer, Window};
+use crate::Result;
+
+/// Returns the global marks, i.e. the uppercase and numbered ones, or the
+/// local marks of `buf` if it's set. See `:h getmarklist()` for details.
+pub fn list_marks(buf: Option<Buffer>) -> Result<Vec<MarkInfos>> {
+    match buf {
+        Some(buf) => call_function("getmarklist", [buf.0]),
+        None => call_function("getmarklist", [] as [i32; 0]),
+    }
+}
+
+impl Window {
+    /// Returns the window's jumplist. See `:h getjumplist()` for details.
+    pub fn get_jumplist(&self) -> Result<Jumplist> {
+        call_function("getjumplist", [self.0])
+    }
+}
+
+impl Buffer {
+    /// Returns the buffer's changelist. See `:h getchangelist()` for
+    /// details.
+    pub fn get_changelist(&self) -> Result<Changelist> {
+        call_function("getchangelist", [self.0])
+    }
+}
@@ -1,5 +1,33 @@
+mod blend;
+mod call_raw;
+mod clipboard;
 mod ffi;
+mod file_changed;
+#[allow(clippy::module_inception)]
 mod global;
+mod input;
+mod marks;
+mod notify;
+mod option_ops;
 pub mod opts;
+mod pum;
+mod redraw;
+mod register;
+mod tags;
+mod text_object;
 
+pub(crate) use blend::require_termguicolors;
+pub use blend::{get_pum_blend, set_pum_blend};
+pub use call_raw::*;
+pub use clipboard::*;
+pub use file_changed::*;
 pub use global::*;
+pub use input::*;
+pub use marks::*;
+pub use notify::*;
+pub use option_ops::*;
+pub use pum::*;
+pub use redraw::*;
+pub use register::*;
+pub use tags::*;
+pub use text_object::*;
@@ -0,0 +1,73 @@
+use std::cell::Cell;
+
+use nvim_types::{
+    dictionary::Dictionary,
+    error::Error as NvimError,
+    object::Object,
+};
+
+use super::ffi::*;
+use super::opts::NotifyOpts;
+use crate::api::{self, types::LogLevel};
+use crate::Result;
+
+thread_local! {
+    static MIN_LEVEL: Cell<LogLevel> = const { Cell::new(LogLevel::Trace) };
+}
+
+/// Sets the minimum level of the notifications sent with [`notify`] and
+/// [`notify_once`], below which they're silently dropped. Every level is
+/// sent by default.
+pub fn set_notify_level(level: LogLevel) {
+    MIN_LEVEL.with(|min| min.set(level));
+}
+
+/// Returns the minimum level set with [`set_notify_level`].
+pub fn notify_level() -> LogLevel {
+    MIN_LEVEL.with(Cell::get)
+}
+
+#[inline]
+fn is_filtered(level: LogLevel) -> bool {
+    level < notify_level() || level == LogLevel::Off
+}
+
+/// Binding to `nvim_notify`.
+///
+/// Displays a notification through `vim.notify`, returning whatever it
+/// returns, e.g. the notification record created by a notifier plugin.
+/// Returns nil without displaying anything if `level` is below the minimum
+/// set with [`set_notify_level`].
+pub fn notify(
+    msg: &str,
+    level: LogLevel,
+    opts: &NotifyOpts,
+) -> Result<Object> {
+    if is_filtered(level) {
+        return Ok(Object::nil());
+    }
+
+    let mut err = NvimError::new();
+    let out = unsafe {
+        nvim_notify(msg.into(), level.into(), opts.into(), &mut err)
+    };
+    err.into_err_or_else(|| out)
+}
+
+/// Displays a notification through `vim.notify_once`, which ignores
+/// messages that have already been displayed. Returns whether the
+/// notification was displayed.
+pub fn notify_once(
+    msg: &str,
+    level: LogLevel,
+    opts: &NotifyOpts,
+) -> Result<bool> {
+    if is_filtered(level) {
+        return Ok(false);
+    }
+
+    api::exec_lua(
+        "return vim.notify_once(...)",
+        [Object::from(msg), level.into(), Dictionary::from(opts).into()],
+    )
+}
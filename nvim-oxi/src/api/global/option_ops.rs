@@ -0,0 +1,146 @@
+use std::ops::{Bound, RangeBounds};
+
+use nvim_types::Integer;
+
+use super::opts::OptionValueOpts;
+use super::{get_option_info, get_option_value, set_option_value};
+use crate::api::types::OptionType;
+use crate::{Error, Result};
+
+/// Flips the value of a boolean option, returning the new value. `opts`
+/// selects the scope, window or buffer, like in `api::set_option_value`.
+pub fn toggle_option(name: &str, opts: &OptionValueOpts) -> Result<bool> {
+    expect_type(name, OptionType::Boolean)?;
+    let value = !get_option_value::<bool>(name, opts)?;
+    set_option_value(name, value, opts)?;
+    Ok(value)
+}
+
+/// Adds `delta` to the value of a number option, clamping the result to
+/// `clamp`, and returns the new value. Pass `..` to leave it unbounded.
+///
+/// Neovim still validates the new value, so e.g. decrementing `'scrolloff'`
+/// below zero fails unless it's clamped.
+pub fn increment_option<R>(
+    name: &str,
+    delta: Integer,
+    clamp: R,
+    opts: &OptionValueOpts,
+) -> Result<Integer>
+where
+    R: RangeBounds<Integer>,
+{
+    expect_type(name, OptionType::Number)?;
+    let current = get_option_value::<Integer>(name, opts)?;
+    let value = clamp_to(current.saturating_add(delta), &clamp);
+    set_option_value(name, value, opts)?;
+    Ok(value)
+}
+
+/// Adds `delta` to the size of every font in `'guifont'`, e.g. to zoom in
+/// and out in a GUI. Fonts without a size are left as they are, and sizes
+/// never go below 1.
+pub fn increment_guifont_size(delta: f64) -> Result<()> {
+    let opts = OptionValueOpts::default();
+    let guifont = get_option_value::<String>("guifont", &opts)?;
+    set_option_value("guifont", resize_guifont(&guifont, delta), &opts)
+}
+
+fn expect_type(name: &str, expected: OptionType) -> Result<()> {
+    let found = get_option_info(name)?.ty;
+
+    if found != expected {
+        return Err(Error::OptionTypeError {
+            name: name.to_owned(),
+            expected,
+            found,
+        });
+    }
+
+    Ok(())
+}
+
+fn clamp_to<R: RangeBounds<Integer>>(value: Integer, range: &R) -> Integer {
+    let value = match range.start_bound() {
+        Bound::Included(&min) => value.max(min),
+        Bound::Excluded(&min) => value.max(min.saturating_add(1)),
+        Bound::Unbounded => value,
+    };
+
+    match range.end_bound() {
+        Bound::Included(&max) => value.min(max),
+        Bound::Excluded(&max) => value.min(max.saturating_sub(1)),
+        Bound::Unbounded => value,
+    }
+}
+
+/// Applies `delta` to the `h{size}` attribute of each font in a `'guifont'`
+/// value, e.g. `Fira Code:h12,Noto Emoji:h11.5`.
+fn resize_guifont(guifont: &str, delta: f64) -> String {
+    split_fonts(guifont)
+        .into_iter()
+        .map(|font| {
+            font.split(':')
+                .enumerate()
+                .map(|(idx, attr)| {
+                    let size = attr
+                        .strip_prefix('h')
+                        .filter(|_| idx > 0)
+                        .and_then(|size| size.parse::<f64>().ok());
+
+                    match size {
+                        Some(size) => format!("h{}", (size + delta).max(1.)),
+                        None => attr.to_owned(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(":")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Splits a `'guifont'` value on the commas that aren't escaped with a
+/// backslash.
+fn split_fonts(guifont: &str) -> Vec<&str> {
+    let mut fonts = Vec::new();
+    let mut start = 0;
+    let mut is_escaped = false;
+
+    for (idx, ch) in guifont.char_indices() {
+        match ch {
+            '\\' => is_escaped = !is_escaped,
+            ',' if !is_escaped => {
+                fonts.push(&guifont[start..idx]);
+                start = idx + 1;
+            },
+            _ => is_escaped = false,
+        }
+    }
+
+    fonts.push(&guifont[start..]);
+    fonts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp() {
+        assert_eq!(clamp_to(-3, &(0..)), 0);
+        assert_eq!(clamp_to(12, &(0..10)), 9);
+        assert_eq!(clamp_to(12, &(0..=10)), 10);
+        assert_eq!(clamp_to(5, &..), 5);
+    }
+
+    #[test]
+    fn resize() {
+        assert_eq!(
+            resize_guifont("Fira Code:h12:b,Noto\\, Emoji:h11.5", 1.),
+            "Fira Code:h13:b,Noto\\, Emoji:h12.5"
+        );
+        assert_eq!(resize_guifont("hack:h2", -4.), "hack:h1");
+        assert_eq!(resize_guifont("Menlo", 1.), "Menlo");
+    }
+}
@@ -0,0 +1,57 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+use crate::api::Window;
+
+/// Options passed to `api::eval_statusline`.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct EvalStatuslineOpts {
+    /// Window to use as the context for the statusline.
+    #[builder(setter(into, strip_option))]
+    winid: Option<Window>,
+
+    /// Maximum width of the statusline.
+    #[builder(setter(strip_option))]
+    maxwidth: Option<u32>,
+
+    /// Character used to fill the blank spaces.
+    #[builder(setter(strip_option))]
+    fillchar: Option<char>,
+
+    /// Whether to return the highlight information.
+    highlights: bool,
+
+    /// Evaluate the tabline instead of the statusline.
+    use_tabline: bool,
+
+    /// Evaluate the winbar instead of the statusline. Requires Neovim 0.8.
+    use_winbar: bool,
+}
+
+impl EvalStatuslineOpts {
+    #[inline(always)]
+    pub fn builder() -> EvalStatuslineOptsBuilder {
+        EvalStatuslineOptsBuilder::default()
+    }
+}
+
+impl From<EvalStatuslineOpts> for Dictionary {
+    fn from(opts: EvalStatuslineOpts) -> Self {
+        Self::from_iter([
+            ("winid", Object::from(opts.winid.map(|win| win.0))),
+            ("maxwidth", opts.maxwidth.into()),
+            ("fillchar", opts.fillchar.into()),
+            ("highlights", opts.highlights.into()),
+            ("use_tabline", opts.use_tabline.into()),
+            // Only sent when set, since older versions reject the key.
+            ("use_winbar", opts.use_winbar.then_some(true).into()),
+        ])
+    }
+}
+
+impl From<&EvalStatuslineOpts> for Dictionary {
+    fn from(opts: &EvalStatuslineOpts) -> Self {
+        opts.clone().into()
+    }
+}
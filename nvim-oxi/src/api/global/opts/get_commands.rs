@@ -20,7 +20,7 @@ impl From<GetCommandsOpts> for Dictionary {
     }
 }
 
-impl<'a> From<&'a GetCommandsOpts> for Dictionary {
+impl From<&GetCommandsOpts> for Dictionary {
     fn from(opts: &GetCommandsOpts) -> Self {
         opts.clone().into()
     }
@@ -0,0 +1,70 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+/// Options passed to `api::get_context`.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct GetContextOpts {
+    #[builder(setter(custom))]
+    types: Option<Vec<ContextType>>,
+}
+
+impl GetContextOpts {
+    #[inline(always)]
+    pub fn builder() -> GetContextOptsBuilder {
+        GetContextOptsBuilder::default()
+    }
+}
+
+impl GetContextOptsBuilder {
+    /// The kinds of state to include in the context. Every kind is included
+    /// if not set.
+    pub fn types<Types>(&mut self, types: Types) -> &mut Self
+    where
+        Types: IntoIterator<Item = ContextType>,
+    {
+        self.types = Some(Some(types.into_iter().collect()));
+        self
+    }
+}
+
+/// A kind of state saved in an `EditorContext`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ContextType {
+    Registers,
+    Jumplist,
+    BufferList,
+    GlobalVars,
+    ScriptFunctions,
+    GlobalFunctions,
+}
+
+impl From<ContextType> for Object {
+    fn from(ty: ContextType) -> Self {
+        use ContextType::*;
+        match ty {
+            Registers => "regs",
+            Jumplist => "jumps",
+            BufferList => "bufs",
+            GlobalVars => "gvars",
+            ScriptFunctions => "sfuncs",
+            GlobalFunctions => "funcs",
+        }
+        .into()
+    }
+}
+
+impl From<GetContextOpts> for Dictionary {
+    fn from(opts: GetContextOpts) -> Self {
+        Self::from_iter([(
+            "types",
+            opts.types.map(|types| types.into_iter().collect::<Object>()),
+        )])
+    }
+}
+
+impl From<&GetContextOpts> for Dictionary {
+    fn from(opts: &GetContextOpts) -> Self {
+        opts.clone().into()
+    }
+}
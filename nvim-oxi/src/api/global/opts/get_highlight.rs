@@ -0,0 +1,37 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+/// Options passed to `api::get_hl`.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct GetHighlightOpts {
+    /// Only get the highlight group with this id.
+    #[builder(setter(strip_option))]
+    id: Option<u32>,
+
+    /// Only get the highlight group with this name.
+    #[builder(setter(into, strip_option))]
+    name: Option<String>,
+
+    /// Whether to return the group a linked group links to instead of
+    /// resolving the link. Defaults to `true`.
+    #[builder(setter(strip_option))]
+    link: Option<bool>,
+}
+
+impl GetHighlightOpts {
+    #[inline(always)]
+    pub fn builder() -> GetHighlightOptsBuilder {
+        GetHighlightOptsBuilder::default()
+    }
+}
+
+impl From<&GetHighlightOpts> for Dictionary {
+    fn from(opts: &GetHighlightOpts) -> Self {
+        Self::from_iter([
+            ("id", Object::from(opts.id)),
+            ("name", opts.name.clone().into()),
+            ("link", opts.link.into()),
+        ])
+    }
+}
@@ -1,7 +1,21 @@
+mod eval_statusline;
 mod get_commands;
+mod get_context;
+#[cfg(feature = "neovim-0-9")]
+mod get_highlight;
+mod notify;
+mod open_term;
+mod option_value;
 mod set_keymap;
 mod user_command;
 
+pub use eval_statusline::*;
 pub use get_commands::*;
+pub use get_context::*;
+#[cfg(feature = "neovim-0-9")]
+pub use get_highlight::*;
+pub use notify::*;
+pub use open_term::*;
+pub use option_value::*;
 pub use set_keymap::*;
 pub use user_command::*;
@@ -0,0 +1,38 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+/// Options passed to `api::notify` and `api::notify_once`. They're forwarded
+/// to `vim.notify`, so which ones are used depends on the notifier plugin
+/// installed, if any.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct NotifyOpts {
+    /// Title of the notification, usually the name of the plugin sending it.
+    #[builder(setter(into, strip_option))]
+    title: Option<String>,
+
+    /// Icon displayed next to the title.
+    #[builder(setter(into, strip_option))]
+    icon: Option<String>,
+
+    /// How long the notification is displayed for, in milliseconds.
+    #[builder(setter(strip_option))]
+    timeout: Option<u32>,
+}
+
+impl NotifyOpts {
+    #[inline(always)]
+    pub fn builder() -> NotifyOptsBuilder {
+        NotifyOptsBuilder::default()
+    }
+}
+
+impl From<&NotifyOpts> for Dictionary {
+    fn from(opts: &NotifyOpts) -> Self {
+        Self::from_iter([
+            ("title", Object::from(opts.title.clone())),
+            ("icon", opts.icon.clone().into()),
+            ("timeout", opts.timeout.into()),
+        ])
+    }
+}
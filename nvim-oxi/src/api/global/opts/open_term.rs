@@ -0,0 +1,56 @@
+use derive_builder::Builder;
+use nvim_types::{
+    dictionary::Dictionary,
+    object::Object,
+    string::String as NvimString,
+};
+
+use crate::api::Buffer;
+use crate::lua::LuaFnMut;
+
+/// Arguments passed to the function registered to `on_input`. The input
+/// data is kept as raw bytes, since terminal input isn't necessarily valid
+/// UTF-8.
+pub type TermInputArgs = (
+    String,     // the string literal "input"
+    u32,        // channel id of the terminal
+    Buffer,     // terminal buffer
+    NvimString, // input data
+);
+
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct OpenTermOpts {
+    #[builder(setter(custom))]
+    on_input: Option<LuaFnMut<TermInputArgs, ()>>,
+}
+
+impl OpenTermOpts {
+    #[inline(always)]
+    pub fn builder() -> OpenTermOptsBuilder {
+        OpenTermOptsBuilder::default()
+    }
+}
+
+impl OpenTermOptsBuilder {
+    /// Callback invoked on every keystroke typed into the terminal buffer.
+    pub fn on_input<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnMut(TermInputArgs) -> crate::Result<()> + 'static,
+    {
+        self.on_input = Some(Some(fun.into()));
+        self
+    }
+}
+
+impl From<OpenTermOpts> for Dictionary {
+    fn from(opts: OpenTermOpts) -> Self {
+        Self::from_iter([("on_input", Object::from(opts.on_input))])
+    }
+}
+
+impl From<&OpenTermOpts> for Dictionary {
+    fn from(opts: &OpenTermOpts) -> Self {
+        opts.clone().into()
+    }
+}
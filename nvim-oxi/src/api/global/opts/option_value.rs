@@ -0,0 +1,70 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+use crate::api::{Buffer, Window};
+
+/// Options passed to `api::get_option_value` and `api::set_option_value`.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct OptionValueOpts {
+    #[builder(setter(strip_option))]
+    scope: Option<OptionScope>,
+
+    /// Window to get/set window-local options for.
+    #[builder(setter(into, strip_option))]
+    pub(crate) win: Option<Window>,
+
+    /// Buffer to get/set buffer-local options for.
+    #[builder(setter(into, strip_option))]
+    pub(crate) buf: Option<Buffer>,
+
+    /// Get the value an option would have in a new buffer with this
+    /// filetype, without creating one. Only valid when getting options, and
+    /// can't be combined with any other field.
+    #[cfg(feature = "neovim-nightly")]
+    #[builder(setter(into, strip_option))]
+    filetype: Option<String>,
+}
+
+impl OptionValueOpts {
+    #[inline(always)]
+    pub fn builder() -> OptionValueOptsBuilder {
+        OptionValueOptsBuilder::default()
+    }
+}
+
+/// Whether to act on the global or the local value of an option, like
+/// `:setglobal` and `:setlocal`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum OptionScope {
+    Global,
+    Local,
+}
+
+impl From<OptionScope> for Object {
+    fn from(scope: OptionScope) -> Self {
+        match scope {
+            OptionScope::Global => "global",
+            OptionScope::Local => "local",
+        }
+        .into()
+    }
+}
+
+impl From<OptionValueOpts> for Dictionary {
+    fn from(opts: OptionValueOpts) -> Self {
+        Self::from_iter([
+            ("scope", Object::from(opts.scope)),
+            ("win", opts.win.map(|win| win.0).into()),
+            ("buf", opts.buf.map(|buf| buf.0).into()),
+            #[cfg(feature = "neovim-nightly")]
+            ("filetype", opts.filetype.into()),
+        ])
+    }
+}
+
+impl From<&OptionValueOpts> for Dictionary {
+    fn from(opts: &OptionValueOpts) -> Self {
+        opts.clone().into()
+    }
+}
@@ -56,7 +56,7 @@ impl From<SetKeymapOpts> for Dictionary {
     }
 }
 
-impl<'a> From<&'a SetKeymapOpts> for Dictionary {
+impl From<&SetKeymapOpts> for Dictionary {
     fn from(opts: &SetKeymapOpts) -> Self {
         opts.clone().into()
     }
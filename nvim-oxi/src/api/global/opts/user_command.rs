@@ -6,8 +6,14 @@ use nvim_types::{
     Integer,
 };
 
-use crate::api::types::{CommandAddr, CommandNArgs, CommandRange};
-use crate::lua::LuaFn;
+use crate::api::types::{
+    CommandAddr,
+    CommandNArgs,
+    CommandPreview,
+    CommandPreviewArgs,
+    CommandRange,
+};
+use crate::lua::{LuaFn, LuaFnMut};
 use crate::object::ToObject;
 
 #[derive(Clone, Debug, Default, Builder)]
@@ -37,6 +43,11 @@ pub struct CreateCommandOpts {
     #[builder(setter(custom))]
     nargs: Option<Object>,
 
+    /// Called to preview the effects of the command while it's being typed,
+    /// if `'inccommand'` is set. See `:h :command-preview` for details.
+    #[builder(setter(custom))]
+    preview: Option<LuaFnMut<CommandPreviewArgs, CommandPreview>>,
+
     #[builder(setter(custom))]
     range: Option<Object>,
 
@@ -66,12 +77,30 @@ impl CreateCommandOptsBuilder {
 
     object_setter!(range, CommandRange);
 
-    object_setter!(complete, CommandComplete);
+    pub fn complete(&mut self, complete: CommandComplete) -> &mut Self {
+        let complete = match complete {
+            CommandComplete::Custom(fun)
+            | CommandComplete::CustomList(fun) => fun.into(),
+            other => other.to_obj().unwrap(),
+        };
+        self.complete = Some(Some(complete));
+        self
+    }
+
+    pub fn preview<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnMut(CommandPreviewArgs) -> crate::Result<CommandPreview>
+            + 'static,
+    {
+        self.preview = Some(Some(fun.into()));
+        self
+    }
 }
 
 /// See `:h command-complete` for details.
 #[non_exhaustive]
 #[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CommandComplete {
     Arglist,
     Augroup,
@@ -109,9 +138,17 @@ pub enum CommandComplete {
     User,
     Var,
 
+    /// Same as [`CustomList`](CommandComplete::CustomList), since Neovim
+    /// only accepts Lua functions returning a list of candidates.
     #[serde(skip)]
-    /// See `:h command-completion-custom` for details.
     Custom(LuaFn<(String, String, usize), Vec<String>>),
+
+    /// Completion candidates computed by a function called with the
+    /// argument being completed, the whole command line and the cursor
+    /// position in it. Neovim filters the returned candidates. See
+    /// `:h command-completion-customlist` for details.
+    #[serde(skip)]
+    CustomList(LuaFn<(String, String, usize), Vec<String>>),
 }
 
 impl From<CreateCommandOpts> for Dictionary {
@@ -127,13 +164,38 @@ impl From<CreateCommandOpts> for Dictionary {
             ("bang", opts.bang.into()),
             ("bar", opts.bar.into()),
             ("keepscript", opts.keepscript.into()),
+            ("preview", opts.preview.into()),
             ("register", opts.register.into()),
         ])
     }
 }
 
-impl<'a> From<&'a CreateCommandOpts> for Dictionary {
+impl From<&CreateCommandOpts> for Dictionary {
     fn from(opts: &CreateCommandOpts) -> Self {
         opts.clone().into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete(complete: CommandComplete) -> Object {
+        let opts = CreateCommandOpts::builder().complete(complete).build();
+        opts.unwrap().complete.unwrap()
+    }
+
+    #[test]
+    fn builtin_completions_are_snake_case() {
+        let name = |obj: Object| String::try_from(obj).unwrap();
+        assert_eq!(name(complete(CommandComplete::Arglist)), "arglist");
+        assert_eq!(
+            name(complete(CommandComplete::FileInPath)),
+            "file_in_path"
+        );
+        assert_eq!(
+            name(complete(CommandComplete::TagListfiles)),
+            "tag_listfiles"
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use nvim_types::object::Object;
+
+use crate::api::call_function;
+use crate::api::types::{PumInfo, PumPosition};
+use crate::Result;
+
+/// Returns the state of the popup menu used for Insert mode completion.
+/// See `:h complete_info()` for details.
+pub fn get_pum_info() -> Result<PumInfo> {
+    call_function("complete_info", [] as [Object; 0])
+}
+
+/// Returns the position and size of the popup menu, or `None` if it's not
+/// visible. See `:h pum_getpos()` for details.
+pub fn get_pum_position() -> Result<Option<PumPosition>> {
+    if call_function::<_, i64>("pumvisible", [] as [Object; 0])? == 0 {
+        return Ok(None);
+    }
+    call_function("pum_getpos", [] as [Object; 0]).map(Some)
+}
@@ -0,0 +1,10 @@
+use crate::api::exec2;
+use crate::api::vimscript::opts::ExecOpts;
+use crate::Result;
+
+/// Forces Neovim to process pending redraws, so that the UI (and the
+/// screen grid seen by attached clients) reflects the latest changes. See
+/// `:h :redraw` for details.
+pub fn flush_ui() -> Result<()> {
+    exec2("redraw", &ExecOpts::default()).map(|_| ())
+}
@@ -0,0 +1,46 @@
+use nvim_types::object::Object;
+
+use crate::api::call_function;
+use crate::api::types::{RegisterContents, RegisterType};
+use crate::{Error, Result};
+
+/// Returns the contents of the register `name`, or `None` if the register
+/// is empty or doesn't exist. See `:h getreg()` for details.
+pub fn get_register(name: char) -> Result<Option<RegisterContents>> {
+    let regtype = call_function::<_, String>("getregtype", [name])?;
+
+    if regtype.is_empty() {
+        return Ok(None);
+    }
+
+    let lines = call_function(
+        "getreg",
+        [Object::from(name), Object::from(1), Object::from(1)],
+    )?;
+
+    Ok(Some(RegisterContents { lines, regtype: regtype.parse()? }))
+}
+
+/// Sets the contents of the register `name`. See `:h setreg()` for
+/// details.
+pub fn set_register<Lines, Line>(
+    name: char,
+    lines: Lines,
+    regtype: RegisterType,
+) -> Result<()>
+where
+    Lines: IntoIterator<Item = Line>,
+    Line: Into<String>,
+{
+    let lines = lines.into_iter().map(Into::into).collect::<Object>();
+
+    let failed = call_function::<_, i64>(
+        "setreg",
+        [Object::from(name), lines, Object::from(regtype.to_string())],
+    )?;
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(Error::SetRegisterError(name)),
+    }
+}
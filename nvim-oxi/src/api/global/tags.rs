@@ -0,0 +1,9 @@
+use crate::api::call_function;
+use crate::api::types::TagInfo;
+use crate::Result;
+
+/// Returns the tags matching the regular expression `pattern`. See
+/// `:h taglist()` for details.
+pub fn taglist(pattern: &str) -> Result<Vec<TagInfo>> {
+    call_function("taglist", [pattern])
+}
@@ -0,0 +1,61 @@
+local kind = ...
+
+local row, col = unpack(vim.api.nvim_win_get_cursor(0))
+row = row - 1
+
+-- Returns the match of `pattern` in `line` which contains the cursor.
+-- Offsets are in bytes, so multibyte characters are handled by Vim's regex
+-- engine.
+local function match_around(line, pattern)
+  local start = 0
+  while true do
+    local m = vim.fn.matchstrpos(line, pattern, start)
+    local first, last = m[2], m[3]
+    if first == -1 or first > col then
+      return nil
+    elseif col < last then
+      return { row, first, row, last, m[1] }
+    elseif last == start then
+      -- Avoid looping forever on empty matches.
+      start = start + 1
+    else
+      start = last
+    end
+  end
+end
+
+local line = vim.api.nvim_get_current_line()
+
+if kind == 'word' then
+  return match_around(line, [[\k\+]])
+elseif kind == 'WORD' then
+  return match_around(line, [[\S\+]])
+elseif kind == 'url' then
+  return match_around(line, [[\v<[a-zA-Z][a-zA-Z0-9+.-]*://[^[:space:]<>"']+]])
+elseif kind == 'line' then
+  return { row, 0, row, #line, line }
+end
+
+-- Paragraph.
+local function is_blank(lnum)
+  local l = vim.api.nvim_buf_get_lines(0, lnum, lnum + 1, true)[1]
+  return l:match('^%s*$') ~= nil
+end
+
+if is_blank(row) then
+  return nil
+end
+
+local first, last = row, row
+local line_count = vim.api.nvim_buf_line_count(0)
+
+while first > 0 and not is_blank(first - 1) do
+  first = first - 1
+end
+
+while last < line_count - 1 and not is_blank(last + 1) do
+  last = last + 1
+end
+
+local lines = vim.api.nvim_buf_get_lines(0, first, last + 1, true)
+return { first, 0, last, #lines[#lines], table.concat(lines, '\n') }
@@ -0,0 +1,25 @@
+use nvim_types::object::Object;
+
+use crate::api::exec_lua;
+use crate::api::types::{TextObjectKind, TextRange};
+use crate::Result;
+
+/// Returns the text object of the given kind under the cursor in the
+/// current window together with its text, or `None` if the cursor isn't on
+/// one, e.g. when it's on whitespace and `kind` is
+/// [`Word`](TextObjectKind::Word).
+///
+/// Unlike `expand("<cword>")`, which also returns the keyword after the
+/// cursor, only text objects containing the cursor are returned.
+pub fn text_object_under_cursor(
+    kind: TextObjectKind,
+) -> Result<Option<(TextRange, String)>> {
+    let object = exec_lua::<_, Option<(usize, usize, usize, usize, String)>>(
+        include_str!("./text_object.lua"),
+        [Object::from(kind.as_str())],
+    )?;
+
+    Ok(object.map(|(start_row, start_col, end_row, end_col, text)| {
+        (TextRange { start_row, start_col, end_row, end_col }, text)
+    }))
+}
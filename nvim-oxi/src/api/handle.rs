@@ -0,0 +1,56 @@
+//! Parsing of the `{id}`, `:{id}` and `{Type}({id})` string representations
+//! of buffer, window and tabpage handles.
+
+use libc::c_int;
+
+use crate::{Error, Result};
+
+/// Parses a handle from `s`, where `name` is the prefix used by the
+/// `Display` implementation of the handle's type (e.g. `"Buffer"`).
+pub(crate) fn parse(s: &str, name: &str) -> Result<c_int> {
+    let trimmed = s.trim();
+
+    let id = trimmed
+        .strip_prefix(':')
+        .or_else(|| {
+            trimmed.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+        })
+        .unwrap_or(trimmed);
+
+    match id.parse::<c_int>() {
+        Ok(handle) if handle >= 0 => Ok(handle),
+        _ => Err(Error::HandleParseError(s.to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bare_id() {
+        assert_eq!(3, parse("3", "Buffer").unwrap());
+        assert_eq!(1000, parse(" 1000 ", "Window").unwrap());
+    }
+
+    #[test]
+    fn parse_colon_id() {
+        assert_eq!(3, parse(":3", "Buffer").unwrap());
+        assert_eq!(0, parse(":0", "TabPage").unwrap());
+    }
+
+    #[test]
+    fn parse_display() {
+        assert_eq!(3, parse("Buffer(3)", "Buffer").unwrap());
+        assert_eq!(1001, parse("Window(1001)", "Window").unwrap());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse("", "Buffer").is_err());
+        assert!(parse("-1", "Buffer").is_err());
+        assert!(parse("Window(3)", "Buffer").is_err());
+        assert!(parse("Buffer(3", "Buffer").is_err());
+        assert!(parse("foo", "Buffer").is_err());
+    }
+}
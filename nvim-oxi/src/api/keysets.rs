@@ -0,0 +1,70 @@
+//! The options of many API functions are declared as keysets in Neovim's C
+//! API (`Dict(name) *opts`), whose fields change between releases. When the
+//! crate is built with `NVIM_OXI_KEYSETS` pointing to the keysets header of
+//! a Neovim build (see `build.rs`), debug builds check that the
+//! dictionaries passed for them only use fields of that build, turning a
+//! mismatch into a clear panic instead of a corrupted or ignored option.
+
+use nvim_types::dictionary::Dictionary;
+
+/// The keysets of the Neovim build the crate was checked against.
+#[cfg(all(nvim_oxi_keysets, debug_assertions))]
+const KEYSETS: &[(&str, &[&str])] =
+    include!(concat!(env!("OUT_DIR"), "/keysets.rs"));
+
+/// Converts `opts` into the dictionary passed for the keyset `name`,
+/// checking its keys in debug builds.
+#[inline]
+pub(crate) fn keyset<O: Into<Dictionary>>(name: &str, opts: O) -> Dictionary {
+    let dict = opts.into();
+
+    #[cfg(all(nvim_oxi_keysets, debug_assertions))]
+    {
+        let Some((_, fields)) = KEYSETS.iter().find(|(n, _)| *n == name)
+        else {
+            panic!("keyset `{name}` isn't defined by the Neovim headers");
+        };
+
+        if let Some(key) = unknown_keys(fields, &dict).next() {
+            panic!(
+                "`{key}` isn't a field of the keyset `{name}` in the Neovim \
+                 headers, whose fields are {fields:?}"
+            );
+        }
+    }
+
+    #[cfg(not(all(nvim_oxi_keysets, debug_assertions)))]
+    let _ = name;
+
+    dict
+}
+
+/// Returns the keys of `dict` which aren't in `fields`.
+#[cfg_attr(not(any(test, nvim_oxi_keysets)), allow(dead_code))]
+fn unknown_keys<'a>(
+    fields: &'a [&str],
+    dict: &'a Dictionary,
+) -> impl Iterator<Item = String> + 'a {
+    dict.keys()
+        .map(|key| key.to_string_lossy().into_owned())
+        .filter(move |key| !fields.contains(&key.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_keys_are_found() {
+        let dict =
+            Dictionary::from_iter([("types", "regs"), ("bogus", "value")]);
+        let unknown = unknown_keys(&["types"], &dict).collect::<Vec<_>>();
+        assert_eq!(unknown, ["bogus"]);
+    }
+
+    #[test]
+    fn nil_fields_are_skipped() {
+        let dict = Dictionary::from_iter([("types", None::<bool>)]);
+        assert_eq!(unknown_keys(&[], &dict).count(), 0);
+    }
+}
@@ -2,6 +2,8 @@ pub mod autocmd;
 pub mod buffer;
 pub mod extmark;
 pub mod global;
+mod handle;
+mod keysets;
 pub mod tabpage;
 pub mod types;
 pub mod ui;
@@ -12,10 +14,17 @@ pub mod window;
 pub use autocmd::*;
 pub use buffer::*;
 pub use extmark::*;
+// The `opts` modules of the other APIs are glob-imported too, so this makes
+// `api::opts` unambiguous.
+pub use global::opts;
 pub use global::*;
+pub(crate) use keysets::keyset;
 pub use tabpage::*;
 pub use types::*;
+#[allow(unused_imports)]
 pub use ui::*;
 pub use vimscript::*;
 pub use win_config::*;
 pub use window::*;
+
+pub use crate::r#fn::{stdpath, StdPath};
@@ -0,0 +1,6 @@
+use nvim_types::TabHandle;
+
+extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/tabpage.c#L143
+    pub(super) fn nvim_tabpage_is_valid(tabpage: TabHandle) -> bool;
+}
@@ -0,0 +1,5 @@
+mod ffi;
+#[allow(clippy::module_inception)]
+mod tabpage;
+
+pub use tabpage::*;
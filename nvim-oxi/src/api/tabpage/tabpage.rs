@@ -0,0 +1,46 @@
+use std::fmt;
+use std::str::FromStr;
+
+use nvim_types::TabHandle;
+
+use super::ffi::*;
+use crate::Result;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TabPage(pub(crate) TabHandle);
+
+impl fmt::Display for TabPage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TabPage({})", self.0)
+    }
+}
+
+impl<H: Into<TabHandle>> From<H> for TabPage {
+    fn from(handle: H) -> Self {
+        TabPage(handle.into())
+    }
+}
+
+impl FromStr for TabPage {
+    type Err = crate::Error;
+
+    /// Parses a tabpage from either `{id}`, `:{id}` or `TabPage({id})`.
+    fn from_str(s: &str) -> Result<Self> {
+        crate::api::handle::parse(s, "TabPage").map(Self)
+    }
+}
+
+impl TabPage {
+    /// Shorthand for `nvim_oxi::api::get_current_tabpage`.
+    #[inline(always)]
+    pub fn current() -> Self {
+        crate::api::get_current_tabpage()
+    }
+
+    /// Binding to `nvim_tabpage_is_valid`.
+    ///
+    /// Checks if a tabpage is valid.
+    pub fn is_valid(&self) -> bool {
+        unsafe { nvim_tabpage_is_valid(self.0) }
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// The metadata of the Neovim API, as returned by `api::get_api_info`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct ApiMetadata {
+    pub version: ApiVersion,
+
+    /// Every function of the API, including the deprecated ones.
+    pub functions: Vec<ApiFunction>,
+
+    /// The events sent to remote UIs.
+    #[serde(default)]
+    pub ui_events: Vec<UiEvent>,
+
+    /// The options remote UIs can set when attaching, e.g. `ext_popupmenu`.
+    #[serde(default)]
+    pub ui_options: Vec<String>,
+
+    /// The types of the handles exposed by the API, i.e. `Buffer`, `Window`
+    /// and `Tabpage`, indexed by name.
+    #[serde(default)]
+    pub types: HashMap<String, ApiType>,
+
+    /// The types of the errors raised by the API, indexed by name.
+    #[serde(default)]
+    pub error_types: HashMap<String, ApiErrorType>,
+}
+
+impl ApiMetadata {
+    /// Returns the function called `name`, if the running Neovim has it.
+    pub fn function(&self, name: &str) -> Option<&ApiFunction> {
+        self.functions.iter().find(|fun| fun.name == name)
+    }
+
+    /// Whether the running Neovim has the function `name` and it's not
+    /// deprecated.
+    pub fn has_function(&self, name: &str) -> bool {
+        self.function(name).is_some_and(|fun| fun.deprecated_since.is_none())
+    }
+}
+
+/// The version of Neovim and of its API.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+
+    /// The level of the API, incremented every time functions are added.
+    pub api_level: u32,
+
+    /// The oldest API level the current one is backwards compatible with.
+    pub api_compatible: u32,
+
+    /// Whether the API may still change before the release.
+    pub api_prerelease: bool,
+
+    /// The build of the Neovim binary, e.g. a commit hash. Missing on older
+    /// versions.
+    #[serde(default)]
+    pub build: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ApiFunction {
+    pub name: String,
+
+    /// The API level the function was added in.
+    pub since: u32,
+
+    /// The API level the function was deprecated in, if any.
+    #[serde(default)]
+    pub deprecated_since: Option<u32>,
+
+    /// The type and the name of each parameter.
+    pub parameters: Vec<(String, String)>,
+
+    pub return_type: String,
+
+    /// Whether the function can be called as a method of its first
+    /// parameter by remote clients.
+    pub method: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct UiEvent {
+    pub name: String,
+
+    /// The API level the event was added in.
+    pub since: u32,
+
+    /// The type and the name of each parameter.
+    pub parameters: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ApiType {
+    /// The msgpack extension type id of the handle.
+    pub id: u32,
+
+    /// The prefix of the functions acting on the handle, e.g. `nvim_buf_`.
+    pub prefix: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ApiErrorType {
+    pub id: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::object::Object;
+
+    use super::*;
+    use crate::object::FromObject;
+
+    fn function(name: &str, deprecated_since: Option<i64>) -> Object {
+        Object::from_iter([
+            ("name", Object::from(name)),
+            ("since", 1.into()),
+            ("deprecated_since", deprecated_since.into()),
+            (
+                "parameters",
+                Object::from_iter([Object::from_iter(["Buffer", "buffer"])]),
+            ),
+            ("return_type", "void".into()),
+            ("method", true.into()),
+        ])
+    }
+
+    #[test]
+    fn feature_detection() {
+        let version = Object::from_iter([
+            ("major", Object::from(0)),
+            ("minor", 9.into()),
+            ("patch", 1.into()),
+            ("api_level", 11.into()),
+            ("api_compatible", 0.into()),
+            ("api_prerelease", false.into()),
+        ]);
+
+        let obj = Object::from_iter([
+            ("version", version),
+            (
+                "functions",
+                Object::from_iter([
+                    function("nvim_buf_attach", None),
+                    function("nvim_buf_get_number", Some(2)),
+                ]),
+            ),
+        ]);
+
+        let metadata = ApiMetadata::from_obj(obj).unwrap();
+        assert_eq!(metadata.version.api_level, 11);
+        assert!(metadata.has_function("nvim_buf_attach"));
+        assert!(!metadata.has_function("nvim_buf_get_number"));
+        assert!(!metadata.has_function("nvim_foo"));
+
+        let attach = metadata.function("nvim_buf_attach").unwrap();
+        assert_eq!(attach.parameters, [("Buffer".into(), "buffer".into())]);
+    }
+}
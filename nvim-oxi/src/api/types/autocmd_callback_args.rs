@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+use crate::api::Buffer;
+use crate::lua::LuaPoppable;
+
+/// The argument passed to the callback of an autocommand.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, LuaPoppable)]
+pub struct AutocmdCallbackArgs {
+    /// The id of the autocommand.
+    pub id: u32,
+
+    /// The name of the event that triggered the autocommand.
+    pub event: String,
+
+    /// The id of the autocommand's group, if any.
+    pub group: Option<u32>,
+
+    /// The expanded value of `<amatch>`.
+    pub r#match: String,
+
+    /// The expanded value of `<abuf>`.
+    #[serde(rename = "buf")]
+    pub buffer: Buffer,
+
+    /// The expanded value of `<afile>`.
+    pub file: String,
+}
@@ -0,0 +1,257 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object, Integer};
+use serde::{de, Deserialize};
+
+use super::command_modifiers::count_or_none;
+use super::{CmdMagic, CommandModifiers, FilterInfos, SplitModifier};
+
+/// A command to be executed by `api::cmd`, or parsed by `api::parse_cmd`.
+/// See `:h nvim_cmd()` for details.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Builder, Deserialize)]
+#[builder(default)]
+#[serde(default)]
+pub struct CmdInfos {
+    /// The name of the command.
+    #[builder(setter(into, strip_option))]
+    pub cmd: Option<String>,
+
+    #[builder(setter(strip_option))]
+    #[serde(deserialize_with = "range_or_none")]
+    pub range: Option<CmdRange>,
+
+    #[builder(setter(strip_option))]
+    #[serde(deserialize_with = "count_or_none")]
+    pub count: Option<u32>,
+
+    #[builder(setter(strip_option))]
+    #[serde(deserialize_with = "reg_or_none")]
+    pub reg: Option<char>,
+
+    pub bang: bool,
+
+    #[builder(setter(custom))]
+    pub args: Vec<String>,
+
+    #[builder(setter(strip_option))]
+    pub magic: Option<CmdMagic>,
+
+    #[builder(setter(strip_option))]
+    pub mods: Option<CommandModifiers>,
+}
+
+impl CmdInfos {
+    #[inline(always)]
+    pub fn builder() -> CmdInfosBuilder {
+        CmdInfosBuilder::default()
+    }
+}
+
+/// The range given to a command, with 1-indexed line numbers (or whatever
+/// unit the command's `addr` specifies).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CmdRange {
+    Single(usize),
+    Double(usize, usize),
+}
+
+/// Neovim returns an empty range when the command wasn't given one.
+fn range_or_none<'de, D>(deserializer: D) -> Result<Option<CmdRange>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    match Vec::<usize>::deserialize(deserializer)?[..] {
+        [] => Ok(None),
+        [a] => Ok(Some(CmdRange::Single(a))),
+        [a, b] => Ok(Some(CmdRange::Double(a, b))),
+        ref other => Err(de::Error::invalid_length(other.len(), &"0, 1 or 2")),
+    }
+}
+
+/// Neovim returns an empty string when the command wasn't given a register.
+pub(super) fn reg_or_none<'de, D>(
+    deserializer: D,
+) -> Result<Option<char>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    Ok(String::deserialize(deserializer)?.chars().next())
+}
+
+impl From<CmdRange> for Object {
+    fn from(range: CmdRange) -> Self {
+        let int = |n: usize| n as Integer;
+        match range {
+            CmdRange::Single(a) => Object::from_iter([int(a)]),
+            CmdRange::Double(a, b) => Object::from_iter([int(a), int(b)]),
+        }
+    }
+}
+
+macro_rules! mods_setter {
+    ($name:ident, Option<$type:ty>) => {
+        pub fn $name(&mut self, $name: $type) -> &mut Self {
+            self.mods_mut().$name = Some($name);
+            self
+        }
+    };
+
+    ($name:ident, $type:ty) => {
+        pub fn $name(&mut self, $name: $type) -> &mut Self {
+            self.mods_mut().$name = $name;
+            self
+        }
+    };
+}
+
+macro_rules! magic_setter {
+    ($name:ident, $field:ident) => {
+        pub fn $name(&mut self, $field: bool) -> &mut Self {
+            self.magic
+                .get_or_insert_with(Default::default)
+                .get_or_insert_with(Default::default)
+                .$field = $field;
+            self
+        }
+    };
+}
+
+impl CmdInfosBuilder {
+    magic_setter!(magic_file, file);
+
+    magic_setter!(magic_bar, bar);
+
+    mods_setter!(browse, bool);
+
+    mods_setter!(confirm, bool);
+
+    mods_setter!(emsg_silent, bool);
+
+    mods_setter!(hide, bool);
+
+    #[cfg(feature = "neovim-0-9")]
+    mods_setter!(horizontal, bool);
+
+    mods_setter!(keepalt, bool);
+
+    mods_setter!(keepjumps, bool);
+
+    mods_setter!(keepmarks, bool);
+
+    mods_setter!(keeppatterns, bool);
+
+    mods_setter!(lockmarks, bool);
+
+    mods_setter!(noautocmd, bool);
+
+    mods_setter!(noswapfile, bool);
+
+    mods_setter!(sandbox, bool);
+
+    mods_setter!(silent, bool);
+
+    mods_setter!(split, Option<SplitModifier>);
+
+    mods_setter!(tab, Option<u32>);
+
+    mods_setter!(unsilent, bool);
+
+    mods_setter!(verbose, Option<u32>);
+
+    mods_setter!(vertical, bool);
+
+    pub fn args<Args, Arg>(&mut self, args: Args) -> &mut Self
+    where
+        Args: IntoIterator<Item = Arg>,
+        Arg: Into<String>,
+    {
+        self.args = Some(args.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the `:filter` modifier. If `force` is true only the lines *not*
+    /// matching `pattern` are kept.
+    pub fn filter<P: Into<String>>(
+        &mut self,
+        pattern: P,
+        force: bool,
+    ) -> &mut Self {
+        let pattern = pattern.into();
+        self.mods_mut().filter = Some(FilterInfos { pattern, force });
+        self
+    }
+
+    fn mods_mut(&mut self) -> &mut CommandModifiers {
+        self.mods
+            .get_or_insert_with(Default::default)
+            .get_or_insert_with(Default::default)
+    }
+}
+
+impl From<CmdInfos> for Dictionary {
+    fn from(infos: CmdInfos) -> Self {
+        Self::from_iter([
+            ("cmd", Object::from(infos.cmd)),
+            ("range", infos.range.into()),
+            ("count", infos.count.into()),
+            ("reg", infos.reg.into()),
+            ("bang", infos.bang.into()),
+            ("args", Object::from_iter(infos.args)),
+            ("magic", infos.magic.into()),
+            ("mods", infos.mods.into()),
+        ])
+    }
+}
+
+impl From<&CmdInfos> for Dictionary {
+    fn from(infos: &CmdInfos) -> Self {
+        infos.clone().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::FromObject;
+
+    #[test]
+    fn modifier_and_magic_setters() {
+        let infos = CmdInfos::builder()
+            .cmd("echo")
+            .magic_bar(true)
+            .silent(true)
+            .verbose(2)
+            .split(SplitModifier::TopLeft)
+            .filter("foo", false)
+            .build()
+            .unwrap();
+
+        assert_eq!(infos.magic, Some(CmdMagic { file: false, bar: true }));
+
+        let mods = infos.mods.clone().unwrap();
+        assert!(mods.silent);
+        assert_eq!(mods.verbose, Some(2));
+        assert_eq!(mods.tab, None);
+        assert_eq!(mods.split, Some(SplitModifier::TopLeft));
+        assert_eq!(
+            mods.filter,
+            Some(FilterInfos { pattern: "foo".into(), force: false })
+        );
+
+        let dict = Dictionary::from(&infos);
+        let magic = CmdMagic::from_obj(dict["magic"].clone()).unwrap();
+        assert_eq!(Some(magic), infos.magic);
+        let mods = CommandModifiers::from_obj(dict["mods"].clone()).unwrap();
+        assert_eq!(Some(mods), infos.mods);
+    }
+
+    #[test]
+    fn no_modifiers_or_magic() {
+        let infos = CmdInfos::builder().cmd("echo").build().unwrap();
+        assert_eq!((infos.magic, infos.mods.clone()), (None, None));
+
+        // Nil values are left out of dictionaries.
+        let dict = Dictionary::from(&infos);
+        assert!(!dict.contains_key("magic"));
+        assert!(!dict.contains_key("mods"));
+    }
+}
@@ -0,0 +1,22 @@
+use nvim_types::{dictionary::Dictionary, object::Object};
+use serde::Deserialize;
+
+/// Which characters in a command's arguments are treated specially. See
+/// `:h cmdline-special` and `:h :bar` for details.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+#[serde(default)]
+pub struct CmdMagic {
+    /// Whether to expand special characters like `%` and `#` in the
+    /// arguments.
+    pub file: bool,
+
+    /// Whether `|` separates the command from the next one.
+    pub bar: bool,
+}
+
+impl From<CmdMagic> for Object {
+    fn from(magic: CmdMagic) -> Self {
+        Dictionary::from_iter([("file", magic.file), ("bar", magic.bar)])
+            .into()
+    }
+}
@@ -0,0 +1,6 @@
+/// The result of `api::cmd`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct CmdOutput {
+    /// The output of the command, if `CmdOpts::output` was set.
+    pub output: Option<String>,
+}
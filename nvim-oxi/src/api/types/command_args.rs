@@ -0,0 +1,175 @@
+use nvim_types::object::Object;
+use serde::{ser, Deserialize, Serialize};
+
+use super::cmd_infos::reg_or_none;
+use super::command_modifiers::count_or_none;
+use super::{CmdRange, CommandModifiers, CommandNArgs};
+use crate::lua::LuaPoppable;
+
+/// The argument passed to the callback and to the preview callback of a
+/// user command. See `:h nvim_create_user_command()` for details.
+///
+/// It's serialized back into the table Neovim passes, so that it can be
+/// given to the callback of an existing command (see
+/// [`CommandInfos::callback`](super::CommandInfos::callback)).
+#[derive(
+    Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, LuaPoppable,
+)]
+pub struct CommandArgs {
+    /// The name of the command.
+    pub name: String,
+
+    /// The arguments passed to the command, if any.
+    pub args: String,
+
+    /// The arguments split by unescaped whitespace, or the whole `args` as
+    /// the only item if the command takes at most one argument.
+    #[serde(default)]
+    pub fargs: Vec<String>,
+
+    /// The number of arguments accepted by the command.
+    pub nargs: CommandNArgs,
+
+    /// Whether the command was executed with a `!` modifier.
+    pub bang: bool,
+
+    /// The starting line of the command's range.
+    pub line1: usize,
+
+    /// The final line of the command's range.
+    pub line2: usize,
+
+    /// The number of items in the command's range, from 0 to 2.
+    pub range: u8,
+
+    /// The count given to the command, if any.
+    #[serde(
+        default,
+        deserialize_with = "count_or_none",
+        serialize_with = "count_or_minus_one"
+    )]
+    pub count: Option<u32>,
+
+    /// The register given to the command, if any.
+    #[serde(
+        default,
+        deserialize_with = "reg_or_none",
+        serialize_with = "reg_or_empty"
+    )]
+    pub reg: Option<char>,
+
+    /// The command modifiers, as a string, e.g. `"silent vertical"`.
+    #[serde(default)]
+    pub mods: String,
+
+    /// The command modifiers, parsed.
+    #[serde(default, serialize_with = "smods_as_object")]
+    pub smods: CommandModifiers,
+}
+
+impl CommandArgs {
+    /// The range given to the command, if any, in the form expected by
+    /// `api::cmd`.
+    pub fn cmd_range(&self) -> Option<CmdRange> {
+        match self.range {
+            0 => None,
+            1 => Some(CmdRange::Single(self.line2)),
+            _ => Some(CmdRange::Double(self.line1, self.line2)),
+        }
+    }
+}
+
+fn count_or_minus_one<S>(
+    count: &Option<u32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    serializer.serialize_i64(count.map_or(-1, i64::from))
+}
+
+fn reg_or_empty<S>(
+    reg: &Option<char>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    match reg {
+        Some(reg) => serializer.collect_str(reg),
+        None => serializer.serialize_str(""),
+    }
+}
+
+fn smods_as_object<S>(
+    smods: &CommandModifiers,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    Object::from(smods.clone()).serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::object::Object;
+
+    use super::*;
+    use crate::object::{FromObject, ToObject};
+
+    #[test]
+    fn callback_args() {
+        let smods = Object::from_iter([
+            ("split", Object::from("")),
+            ("tab", (-1).into()),
+            ("verbose", (-1).into()),
+            ("keepjumps", true.into()),
+        ]);
+
+        let args = Object::from_iter([
+            ("name", Object::from("Norm")),
+            ("args", "A;".into()),
+            ("fargs", Object::from_iter(["A;"])),
+            ("nargs", "+".into()),
+            ("bang", false.into()),
+            ("line1", 1.into()),
+            ("line2", 10.into()),
+            ("range", 2.into()),
+            ("count", (-1).into()),
+            ("reg", "".into()),
+            ("mods", "keepjumps".into()),
+            ("smods", smods),
+        ]);
+
+        let args = CommandArgs::from_obj(args).unwrap();
+        assert_eq!(args.nargs, CommandNArgs::OneOrMore);
+        assert_eq!((args.line1, args.line2, args.range), (1, 10, 2));
+        assert_eq!((args.count, args.reg), (None, None));
+        assert!(args.smods.keepjumps);
+        assert_eq!(args.smods.tab, None);
+        assert_eq!(args.cmd_range(), Some(CmdRange::Double(1, 10)));
+
+        let obj = args.clone().to_obj().unwrap();
+        assert_eq!(CommandArgs::from_obj(obj).unwrap(), args);
+    }
+
+    #[test]
+    fn callback_args_without_optional_fields() {
+        let args = Object::from_iter([
+            ("name", Object::from("Foo")),
+            ("args", "".into()),
+            ("nargs", "0".into()),
+            ("bang", true.into()),
+            ("line1", 3.into()),
+            ("line2", 3.into()),
+            ("range", 0.into()),
+        ]);
+
+        let args = CommandArgs::from_obj(args).unwrap();
+        assert!(args.fargs.is_empty() && args.mods.is_empty());
+        assert_eq!(args.smods, Default::default());
+        assert_eq!(args.cmd_range(), None);
+    }
+}
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use super::{CommandAddr, CommandNArgs, CommandRange};
+use super::{CommandAddr, CommandArgs, CommandNArgs, CommandRange};
 use crate::lua::LuaFn;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
@@ -8,7 +8,9 @@ pub struct CommandInfos {
     pub addr: Option<CommandAddr>,
     pub bang: bool,
     pub bar: bool,
-    pub callback: Option<LuaFn<(), ()>>,
+    /// The Lua function the command runs, if it was created with one. It
+    /// can be called with [`LuaFn::call1`].
+    pub callback: Option<LuaFn<CommandArgs, ()>>,
     pub complete: Option<String>,
     pub complete_arg: Option<String>,
     pub count: Option<String>,
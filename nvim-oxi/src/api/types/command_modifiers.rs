@@ -0,0 +1,206 @@
+use nvim_types::{dictionary::Dictionary, object::Object, Integer};
+use serde::{de, Deserialize};
+
+/// Modifiers applied to a command. See `:h command-modifiers` for details.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+#[serde(default)]
+pub struct CommandModifiers {
+    pub browse: bool,
+    pub confirm: bool,
+    pub emsg_silent: bool,
+
+    #[serde(deserialize_with = "filter_or_none")]
+    pub filter: Option<FilterInfos>,
+
+    pub hide: bool,
+
+    #[cfg(feature = "neovim-0-9")]
+    pub horizontal: bool,
+
+    pub keepalt: bool,
+    pub keepjumps: bool,
+    pub keepmarks: bool,
+    pub keeppatterns: bool,
+    pub lockmarks: bool,
+    pub noautocmd: bool,
+    pub noswapfile: bool,
+    pub sandbox: bool,
+    pub silent: bool,
+
+    #[serde(deserialize_with = "split_or_none")]
+    pub split: Option<SplitModifier>,
+
+    /// The count given to `:tab`, if any.
+    #[serde(deserialize_with = "count_or_none")]
+    pub tab: Option<u32>,
+
+    pub unsilent: bool,
+
+    /// The count given to `:verbose`, if any.
+    #[serde(deserialize_with = "count_or_none")]
+    pub verbose: Option<u32>,
+
+    pub vertical: bool,
+}
+
+/// The pattern given to `:filter`. See `:h :filter` for details.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct FilterInfos {
+    pub pattern: String,
+
+    /// Whether the filter is inverted, i.e. `:filter!`.
+    pub force: bool,
+}
+
+/// Where to open the windows created by a command. See `:h :aboveleft`,
+/// `:h :belowright`, `:h :topleft` and `:h :botright` for details.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitModifier {
+    AboveLeft,
+    BelowRight,
+    TopLeft,
+    BotRight,
+}
+
+impl SplitModifier {
+    #[inline]
+    const fn as_str(&self) -> &'static str {
+        use SplitModifier::*;
+        match self {
+            AboveLeft => "aboveleft",
+            BelowRight => "belowright",
+            TopLeft => "topleft",
+            BotRight => "botright",
+        }
+    }
+}
+
+/// Converts an optional count to the `-1` Neovim uses when it's omitted.
+#[inline]
+fn count_or_minus_one(count: Option<u32>) -> Integer {
+    count.map(Integer::from).unwrap_or(-1)
+}
+
+/// The inverse of [`count_or_minus_one`].
+pub(super) fn count_or_none<'de, D>(
+    deserializer: D,
+) -> Result<Option<u32>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    Integer::deserialize(deserializer).map(|count| count.try_into().ok())
+}
+
+/// Neovim returns an empty pattern when there's no `:filter`.
+fn filter_or_none<'de, D>(
+    deserializer: D,
+) -> Result<Option<FilterInfos>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let filter = Option::<FilterInfos>::deserialize(deserializer)?;
+    Ok(filter.filter(|filter| !filter.pattern.is_empty()))
+}
+
+/// Neovim returns an empty string when there's no split modifier.
+fn split_or_none<'de, D>(
+    deserializer: D,
+) -> Result<Option<SplitModifier>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    match String::deserialize(deserializer)?.as_str() {
+        "" => Ok(None),
+        split => SplitModifier::deserialize(
+            de::IntoDeserializer::<D::Error>::into_deserializer(split),
+        )
+        .map(Some),
+    }
+}
+
+impl From<FilterInfos> for Object {
+    fn from(filter: FilterInfos) -> Self {
+        Dictionary::from_iter([
+            ("pattern", Object::from(filter.pattern)),
+            ("force", filter.force.into()),
+        ])
+        .into()
+    }
+}
+
+impl From<CommandModifiers> for Object {
+    fn from(mods: CommandModifiers) -> Self {
+        Dictionary::from_iter([
+            ("browse", Object::from(mods.browse)),
+            ("confirm", mods.confirm.into()),
+            ("emsg_silent", mods.emsg_silent.into()),
+            ("filter", mods.filter.into()),
+            ("hide", mods.hide.into()),
+            #[cfg(feature = "neovim-0-9")]
+            ("horizontal", mods.horizontal.into()),
+            ("keepalt", mods.keepalt.into()),
+            ("keepjumps", mods.keepjumps.into()),
+            ("keepmarks", mods.keepmarks.into()),
+            ("keeppatterns", mods.keeppatterns.into()),
+            ("lockmarks", mods.lockmarks.into()),
+            ("noautocmd", mods.noautocmd.into()),
+            ("noswapfile", mods.noswapfile.into()),
+            ("sandbox", mods.sandbox.into()),
+            ("silent", mods.silent.into()),
+            (
+                "split",
+                mods.split.map(|split| split.as_str()).unwrap_or("").into(),
+            ),
+            ("tab", count_or_minus_one(mods.tab).into()),
+            ("unsilent", mods.unsilent.into()),
+            ("verbose", count_or_minus_one(mods.verbose).into()),
+            ("vertical", mods.vertical.into()),
+        ])
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::FromObject;
+
+    #[test]
+    fn round_trip() {
+        let mods = CommandModifiers {
+            filter: Some(FilterInfos { pattern: "foo".into(), force: true }),
+            split: Some(SplitModifier::BotRight),
+            tab: Some(2),
+            silent: true,
+            ..Default::default()
+        };
+        let obj = Object::from(mods.clone());
+        assert_eq!(CommandModifiers::from_obj(obj).unwrap(), mods);
+
+        let obj = Object::from(CommandModifiers::default());
+        assert_eq!(
+            CommandModifiers::from_obj(obj).unwrap(),
+            CommandModifiers::default()
+        );
+    }
+
+    #[test]
+    fn omitted_modifiers() {
+        let filter = Object::from_iter([
+            ("pattern", Object::from("")),
+            ("force", false.into()),
+        ]);
+
+        let obj = Object::from_iter([
+            ("filter", filter),
+            ("split", "".into()),
+            ("tab", (-1).into()),
+            ("verbose", (-1).into()),
+        ]);
+
+        let mods = CommandModifiers::from_obj(obj).unwrap();
+        assert_eq!(mods, CommandModifiers::default());
+    }
+}
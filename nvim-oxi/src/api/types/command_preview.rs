@@ -0,0 +1,85 @@
+use libc::c_int;
+use serde::ser;
+
+use super::{CommandArgs, Namespace};
+use crate::api::Buffer;
+use crate::lua::{lua_State, LuaPoppable};
+use crate::Result;
+
+/// The arguments passed to the preview callback of a user command. See
+/// `:h :command-preview` for details.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CommandPreviewArgs {
+    /// The same arguments the command's callback receives.
+    pub args: CommandArgs,
+
+    /// The namespace to use for the highlights of the preview. They're
+    /// cleared by Neovim once the preview ends.
+    pub namespace: Namespace,
+
+    /// The buffer to show the preview in, if `'inccommand'` is set to
+    /// `split`.
+    pub buffer: Option<Buffer>,
+}
+
+impl LuaPoppable for CommandPreviewArgs {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        let (args, namespace, buffer) =
+            <(CommandArgs, Namespace, Option<Buffer>)>::pop(lstate)?;
+
+        Ok(Self { args, namespace, buffer })
+    }
+
+    unsafe fn pop_values(lstate: *mut lua_State, n: c_int) -> Result<Self> {
+        let (args, namespace, buffer) =
+            <(CommandArgs, Namespace, Option<Buffer>)>::pop_values(lstate, n)?;
+
+        Ok(Self { args, namespace, buffer })
+    }
+}
+
+/// What to do after the preview callback of a user command returns.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum CommandPreview {
+    /// Don't show a preview.
+    #[default]
+    NoPreview,
+
+    /// Show the changes made by the preview callback in the current
+    /// window, but not in the preview window.
+    Preview,
+
+    /// Also open the preview window, displaying the contents of
+    /// [`CommandPreviewArgs::buffer`], if `'inccommand'` is set to
+    /// `split`.
+    PreviewInWindow,
+}
+
+impl ser::Serialize for CommandPreview {
+    fn serialize<S: ser::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(match self {
+            Self::NoPreview => 0,
+            Self::Preview => 1,
+            Self::PreviewInWindow => 2,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::ToObject;
+
+    #[test]
+    fn preview_return_value() {
+        let int = |preview: CommandPreview| {
+            i64::try_from(preview.to_obj().unwrap()).unwrap()
+        };
+        assert_eq!(int(CommandPreview::NoPreview), 0);
+        assert_eq!(int(CommandPreview::Preview), 1);
+        assert_eq!(int(CommandPreview::PreviewInWindow), 2);
+    }
+}
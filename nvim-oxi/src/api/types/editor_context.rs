@@ -0,0 +1,85 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use nvim_types::{
+    array::Array,
+    dictionary::Dictionary,
+    object::Object,
+    string::String as NvimString,
+};
+
+use super::{shada, RegisterType};
+use crate::Result;
+
+/// The editor state returned by `api::get_context`, which can be restored
+/// with `api::load_context`.
+#[derive(Clone, Debug, Default)]
+pub struct EditorContext(pub(crate) Dictionary);
+
+/// A register saved in an [`EditorContext`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RegisterSnapshot {
+    pub name: char,
+
+    /// The text stored in the register, one element per line. Registers
+    /// can hold arbitrary bytes, so the lines aren't necessarily valid
+    /// UTF-8.
+    pub contents: Vec<NvimString>,
+
+    pub regtype: RegisterType,
+
+    /// Whether the unnamed register points to this register.
+    pub is_unnamed: bool,
+}
+
+impl EditorContext {
+    /// Returns the registers saved in the context, or an empty vector if
+    /// registers weren't requested when getting the context.
+    pub fn registers(&self) -> Result<Vec<RegisterSnapshot>> {
+        let Some(regs) = self.get("regs") else { return Ok(Vec::new()) };
+
+        let lines = Array::try_from(regs)?
+            .into_iter()
+            .map(NvimString::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let bytes = shada::lines_to_bytes(lines.iter().map(|l| l.as_bytes()));
+        shada::decode_registers(&bytes)
+    }
+
+    /// Replaces the registers saved in the context. Loading the context
+    /// afterwards sets every register in `registers`, leaving the others
+    /// untouched.
+    pub fn set_registers<Registers>(&mut self, registers: Registers)
+    where
+        Registers: IntoIterator<Item = RegisterSnapshot>,
+    {
+        let registers = registers.into_iter().collect::<Vec<_>>();
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default();
+
+        let bytes = shada::encode_registers(&registers, timestamp);
+
+        let regs = shada::bytes_to_lines(&bytes)
+            .into_iter()
+            .map(NvimString::from_bytes)
+            .collect::<Array>();
+
+        self.set("regs", regs.into());
+    }
+
+    fn get(&self, key: &str) -> Option<Object> {
+        self.0.clone().into_iter().find_map(|(k, v)| (k == key).then_some(v))
+    }
+
+    fn set(&mut self, key: &str, value: Object) {
+        let dict = std::mem::take(&mut self.0);
+        self.0 = dict
+            .into_iter()
+            .filter(|(k, _)| k != key)
+            .chain([(key.into(), value)])
+            .collect();
+    }
+}
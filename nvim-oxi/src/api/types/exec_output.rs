@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// The result of `api::exec2`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct ExecOutput {
+    /// The output of the executed commands, if `ExecOpts::output` was set.
+    pub output: Option<String>,
+}
@@ -0,0 +1,274 @@
+use serde::Deserialize;
+
+use super::Mode;
+
+/// The current mode, as returned by `api::get_mode`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct GotMode {
+    pub mode: CurrentMode,
+
+    /// Whether Neovim is waiting for input, e.g. in the middle of a
+    /// mapping or of an operator.
+    pub blocking: bool,
+}
+
+impl GotMode {
+    /// Whether Neovim is waiting for input, in which case most API calls
+    /// would block until the user types something.
+    #[inline(always)]
+    pub fn is_blocking(&self) -> bool {
+        self.blocking
+    }
+}
+
+/// One of the modes returned by `mode(1)`, see `:h mode()`.
+///
+/// Unlike [`Mode`], which is the set of modes a mapping can be defined in,
+/// this tells apart every sub-mode Neovim can be in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub enum CurrentMode {
+    #[serde(rename = "n")]
+    Normal,
+
+    #[serde(rename = "no")]
+    OperatorPending,
+
+    /// Operator-pending, forced charwise with `o_v`.
+    #[serde(rename = "nov")]
+    OperatorPendingCharwise,
+
+    /// Operator-pending, forced linewise with `o_V`.
+    #[serde(rename = "noV")]
+    OperatorPendingLinewise,
+
+    /// Operator-pending, forced blockwise with `o_CTRL-V`.
+    #[serde(rename = "no\x16")]
+    OperatorPendingBlockwise,
+
+    /// Normal, entered with `i_CTRL-O` from Insert mode.
+    #[serde(rename = "niI")]
+    NormalFromInsert,
+
+    /// Normal, entered with `i_CTRL-O` from Replace mode.
+    #[serde(rename = "niR")]
+    NormalFromReplace,
+
+    /// Normal, entered with `i_CTRL-O` from Virtual Replace mode.
+    #[serde(rename = "niV")]
+    NormalFromVirtualReplace,
+
+    /// Normal, in a terminal buffer.
+    #[serde(rename = "nt")]
+    NormalInTerminal,
+
+    /// Normal, entered with `t_CTRL-\_CTRL-O` from Terminal mode.
+    #[serde(rename = "ntT")]
+    NormalFromTerminal,
+
+    #[serde(rename = "v")]
+    Visual,
+
+    /// Visual, entered with `CTRL-O` from Select mode.
+    #[serde(rename = "vs")]
+    VisualFromSelect,
+
+    #[serde(rename = "V")]
+    VisualLine,
+
+    #[serde(rename = "Vs")]
+    VisualLineFromSelect,
+
+    #[serde(rename = "\x16")]
+    VisualBlock,
+
+    #[serde(rename = "\x16s")]
+    VisualBlockFromSelect,
+
+    #[serde(rename = "s")]
+    Select,
+
+    #[serde(rename = "S")]
+    SelectLine,
+
+    #[serde(rename = "\x13")]
+    SelectBlock,
+
+    #[serde(rename = "i")]
+    Insert,
+
+    /// Insert, with the completion menu open.
+    #[serde(rename = "ic")]
+    InsertCompletion,
+
+    /// Insert, after `i_CTRL-X`.
+    #[serde(rename = "ix")]
+    InsertCtrlX,
+
+    #[serde(rename = "R")]
+    Replace,
+
+    #[serde(rename = "Rc")]
+    ReplaceCompletion,
+
+    #[serde(rename = "Rx")]
+    ReplaceCtrlX,
+
+    #[serde(rename = "Rv")]
+    VirtualReplace,
+
+    #[serde(rename = "Rvc")]
+    VirtualReplaceCompletion,
+
+    #[serde(rename = "Rvx")]
+    VirtualReplaceCtrlX,
+
+    #[serde(rename = "c")]
+    CmdLine,
+
+    /// Command-line, in overstrike mode after `c_<Insert>`.
+    #[serde(rename = "cr")]
+    CmdLineOverstrike,
+
+    /// Vim Ex mode, entered with `gQ`.
+    #[serde(rename = "cv")]
+    Ex,
+
+    #[serde(rename = "cvr")]
+    ExOverstrike,
+
+    /// The hit-enter prompt.
+    #[serde(rename = "r")]
+    HitEnterPrompt,
+
+    /// The `-- more --` prompt.
+    #[serde(rename = "rm")]
+    MorePrompt,
+
+    /// A `:confirm` query.
+    #[serde(rename = "r?")]
+    ConfirmPrompt,
+
+    /// A shell or external command is executing.
+    #[serde(rename = "!")]
+    Shell,
+
+    #[serde(rename = "t")]
+    Terminal,
+
+    /// A mode added by a later Neovim version.
+    #[serde(other)]
+    Unknown,
+}
+
+impl CurrentMode {
+    /// Whether this is one of the Operator-pending modes.
+    pub fn is_op_pending(&self) -> bool {
+        use CurrentMode::*;
+        matches!(
+            self,
+            OperatorPending
+                | OperatorPendingCharwise
+                | OperatorPendingLinewise
+                | OperatorPendingBlockwise
+        )
+    }
+
+    /// Whether this is one of the Visual modes.
+    pub fn is_visual(&self) -> bool {
+        use CurrentMode::*;
+        matches!(
+            self,
+            Visual
+                | VisualFromSelect
+                | VisualLine
+                | VisualLineFromSelect
+                | VisualBlock
+                | VisualBlockFromSelect
+        )
+    }
+
+    /// Whether this is one of the Select modes.
+    pub fn is_select(&self) -> bool {
+        use CurrentMode::*;
+        matches!(self, Select | SelectLine | SelectBlock)
+    }
+
+    /// Whether this is one of the Insert or Replace modes, where typed
+    /// characters are inserted in the buffer.
+    pub fn is_insert(&self) -> bool {
+        use CurrentMode::*;
+        matches!(
+            self,
+            Insert
+                | InsertCompletion
+                | InsertCtrlX
+                | Replace
+                | ReplaceCompletion
+                | ReplaceCtrlX
+                | VirtualReplace
+                | VirtualReplaceCompletion
+                | VirtualReplaceCtrlX
+        )
+    }
+
+    /// Whether this is one of the Command-line or Ex modes.
+    pub fn is_cmd_line(&self) -> bool {
+        use CurrentMode::*;
+        matches!(self, CmdLine | CmdLineOverstrike | Ex | ExOverstrike)
+    }
+
+    /// Whether Neovim is showing a prompt.
+    pub fn is_prompt(&self) -> bool {
+        use CurrentMode::*;
+        matches!(self, HitEnterPrompt | MorePrompt | ConfirmPrompt)
+    }
+
+    /// Returns the mapping mode whose mappings apply in this mode, if any.
+    pub fn map_mode(&self) -> Option<Mode> {
+        use CurrentMode::*;
+        let mode = match self {
+            _ if self.is_op_pending() => Mode::OperatorPending,
+            _ if self.is_visual() => Mode::Visual,
+            _ if self.is_select() => Mode::Select,
+            _ if self.is_insert() => Mode::Insert,
+            _ if self.is_cmd_line() => Mode::CmdLine,
+            Normal
+            | NormalFromInsert
+            | NormalFromReplace
+            | NormalFromVirtualReplace
+            | NormalInTerminal
+            | NormalFromTerminal => Mode::Normal,
+            Terminal => Mode::Terminal,
+            _ => return None,
+        };
+        Some(mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::{dictionary::Dictionary, object::Object};
+
+    use super::*;
+    use crate::object::FromObject;
+
+    #[test]
+    fn got_mode() {
+        let dict = Dictionary::from_iter([
+            ("mode", Object::from("\x16s")),
+            ("blocking", Object::from(true)),
+        ]);
+        let got = GotMode::from_obj(dict.into()).unwrap();
+        assert_eq!(got.mode, CurrentMode::VisualBlockFromSelect);
+        assert!(got.is_blocking());
+        assert_eq!(got.mode.map_mode(), Some(Mode::Visual));
+    }
+
+    #[test]
+    fn unknown_mode() {
+        let mode = CurrentMode::from_obj("zz".into()).unwrap();
+        assert_eq!(mode, CurrentMode::Unknown);
+        assert_eq!(mode.map_mode(), None);
+        assert!(CurrentMode::from_obj("Rvx".into()).unwrap().is_insert());
+    }
+}
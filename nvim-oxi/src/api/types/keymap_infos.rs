@@ -1,43 +1,108 @@
+use nvim_types::string::String as NvimString;
 use serde::{de, Deserialize};
 
 use super::Mode;
+use crate::api;
 use crate::lua::LuaFn;
 
+/// A mapping, as returned by `Buffer::get_keymap`. See `:h maparg()` for
+/// details.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize)]
 pub struct KeymapInfos {
+    /// Whether the mapping is local to a buffer.
     #[serde(deserialize_with = "bool_from_int")]
-    buffer: bool,
+    pub buffer: bool,
 
-    callback: Option<LuaFn<(), ()>>,
+    /// The Lua function the mapping runs, if it was created with one. For
+    /// `expr` mappings it returns the keys to feed.
+    pub callback: Option<LuaFn<(), Option<String>>>,
 
     #[serde(deserialize_with = "bool_from_int")]
-    expr: bool,
+    pub expr: bool,
 
-    lhs: String,
+    /// A description of the mapping, if it was given one.
+    pub desc: Option<String>,
 
+    pub lhs: String,
+
+    /// The `lhs` with the key codes replaced by their raw bytes.
+    #[serde(default)]
+    pub lhsraw: Option<NvimString>,
+
+    /// Like `lhsraw`, but with an alternative form of the keys, if any.
+    #[serde(default)]
+    pub lhsrawalt: Option<NvimString>,
+
+    /// The line of the script the mapping was defined at, if known.
     #[serde(deserialize_with = "zero_is_none")]
-    lnum: Option<u32>,
+    pub lnum: Option<u32>,
 
-    mode: Mode,
+    /// The modes the mapping is defined in. Mappings defined in more than
+    /// one of them, e.g. with `:map` and then removed from Select mode with
+    /// `:sunmap`, have several.
+    #[serde(deserialize_with = "modes")]
+    pub mode: Vec<Mode>,
 
     #[serde(deserialize_with = "bool_from_int")]
-    noremap: bool,
+    pub noremap: bool,
 
     #[serde(deserialize_with = "bool_from_int")]
-    nowait: bool,
+    pub nowait: bool,
 
-    rhs: Option<String>,
+    /// Whether the keys returned by an `expr` mapping have their key codes
+    /// replaced.
+    #[serde(default, deserialize_with = "bool_from_int")]
+    pub replace_keycodes: bool,
+
+    pub rhs: Option<String>,
 
     #[serde(deserialize_with = "bool_from_int")]
-    script: bool,
+    pub script: bool,
 
-    sid: i32,
+    /// The id of the script the mapping was defined in.
+    pub sid: i32,
 
     #[serde(deserialize_with = "bool_from_int")]
-    silent: bool,
+    pub silent: bool,
+}
+
+impl KeymapInfos {
+    /// Triggers the mapping as if its `lhs` had been typed: calls its
+    /// callback, or feeds its `rhs`. The keys returned by an `expr` mapping
+    /// are fed too, remapped unless the mapping is `noremap`.
+    ///
+    /// Keys are fed with `api::feedkeys`, so they're processed once the
+    /// caller returns to the event loop. `<SID>` in the `rhs` isn't
+    /// expanded, since it depends on the script the mapping was defined in.
+    pub fn invoke(&self) -> crate::Result<()> {
+        let keys = match (&self.callback, &self.rhs) {
+            (Some(callback), _) if self.expr => match callback.call0()? {
+                Some(keys) => keys,
+                None => return Ok(()),
+            },
+            // Whatever other callbacks return is ignored, like Neovim does.
+            (Some(callback), _) => {
+                return crate::lua::call_ref::<(), _>(callback.0, |_| Ok(0));
+            },
+            (None, Some(rhs)) if self.expr => {
+                api::call_function::<_, String>("eval", [rhs.as_str()])?
+            },
+            (None, Some(rhs)) => rhs.clone(),
+            (None, None) => return Ok(()),
+        };
+
+        let keys = if !self.expr || self.replace_keycodes {
+            api::replace_termcodes(keys, true, true, true)
+        } else {
+            keys.into()
+        };
+        let mode = if self.noremap { "n" } else { "m" };
+        api::feedkeys(keys, mode, false);
+        Ok(())
+    }
 }
 
-fn bool_from_int<'de, D>(deserializer: D) -> Result<bool, D::Error>
+pub(super) fn bool_from_int<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: de::Deserializer<'de>,
 {
@@ -52,11 +117,36 @@ where
     }
 }
 
+/// Splits a mode like `"nox"` into its single modes. `" "` is the mode of
+/// `:map`.
+fn modes<'de, D>(deserializer: D) -> Result<Vec<Mode>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let modes = String::deserialize(deserializer)?;
+
+    if modes.trim().is_empty() {
+        return Ok(vec![Mode::NormalVisualOperator]);
+    }
+
+    modes
+        .chars()
+        .map(|ch| {
+            let mut buf = [0; 4];
+            Mode::deserialize(
+                de::IntoDeserializer::<D::Error>::into_deserializer(
+                    &*ch.encode_utf8(&mut buf),
+                ),
+            )
+        })
+        .collect()
+}
+
 fn zero_is_none<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
 where
     D: de::Deserializer<'de>,
 {
-    u32::deserialize(deserializer).map(|lnum| (lnum != 0).then(|| lnum))
+    u32::deserialize(deserializer).map(|lnum| (lnum != 0).then_some(lnum))
 }
 
 // fn empty_is_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
@@ -65,3 +155,80 @@ where
 // {
 //     String::deserialize(deserializer).map(|rhs| (!rhs.is_empty()).then(|| rhs))
 // }
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::object::Object;
+
+    use super::*;
+    use crate::object::FromObject;
+
+    #[test]
+    fn map_mode_and_raw_lhs() {
+        let map = Object::from_iter([
+            ("buffer", Object::from(0)),
+            ("expr", 1.into()),
+            ("lhs", "<F2>".into()),
+            // `<F2>` is `K_SPECIAL` followed by two bytes, not valid UTF-8.
+            ("lhsraw", NvimString::from_bytes(vec![0x80, b'k', b'2']).into()),
+            ("lnum", 0.into()),
+            ("mode", " ".into()),
+            ("noremap", 1.into()),
+            ("nowait", 0.into()),
+            ("replace_keycodes", 1.into()),
+            ("rhs", "v:true ? '<Esc>' : ''".into()),
+            ("script", 0.into()),
+            ("sid", (-8).into()),
+            ("silent", 0.into()),
+        ]);
+
+        let map = KeymapInfos::from_obj(map).unwrap();
+        assert_eq!(map.mode, [Mode::NormalVisualOperator]);
+        assert_eq!(map.lhsraw.unwrap().as_bytes(), b"\x80k2");
+        assert!(map.expr && map.replace_keycodes);
+        assert_eq!((map.lnum, map.desc, map.lhsrawalt), (None, None, None));
+    }
+
+    #[test]
+    fn combined_modes() {
+        let de = |mode: &str| {
+            modes(de::IntoDeserializer::<de::value::Error>::into_deserializer(
+                mode,
+            ))
+        };
+
+        assert_eq!(
+            de("nox").unwrap(),
+            [Mode::Normal, Mode::OperatorPending, Mode::Visual]
+        );
+        assert_eq!(de("!").unwrap(), [Mode::InsertCmdLine]);
+        assert!(de("nq").is_err());
+    }
+}
+
+#[cfg(feature = "test")]
+mod nvim_tests {
+    use super::*;
+    use crate::Result;
+
+    #[nvim_oxi::test]
+    fn callbacks_return_values_are_ignored() -> Result<()> {
+        api::exec_lua::<_, ()>(
+            "vim.keymap.set('n', '<F13>', function()
+                 vim.g.oxi_invoked = true
+                 return { 'not', 'keys' }
+             end)",
+            [(); 0],
+        )?;
+
+        let map = api::get_keymap(Mode::Normal)?
+            .find(|map| map.lhs == "<F13>")
+            .expect("the mapping was just set");
+        map.invoke()?;
+
+        let invoked: bool =
+            api::exec_lua("return vim.g.oxi_invoked", [(); 0])?;
+        assert!(invoked);
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+
+use nvim_types::string::String as NvimString;
+
+use crate::api;
+
+/// A key that isn't a printable character, named as in `:h key-notation`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Key {
+    BackSpace,
+    Delete,
+    Down,
+    End,
+    Enter,
+    Esc,
+    /// A function key, from `<F1>` to `<F37>`.
+    F(u8),
+    Home,
+    Insert,
+    Left,
+    /// The `<Leader>` key, i.e. the value of `mapleader`. Only replaced in
+    /// mappings.
+    Leader,
+    /// The `<LocalLeader>` key, i.e. the value of `maplocalleader`. Only
+    /// replaced in mappings.
+    LocalLeader,
+    PageDown,
+    PageUp,
+    Right,
+    Space,
+    Tab,
+    Up,
+}
+
+impl Key {
+    fn name(self) -> String {
+        use Key::*;
+        match self {
+            BackSpace => "BS".into(),
+            Delete => "Del".into(),
+            Down => "Down".into(),
+            End => "End".into(),
+            Enter => "CR".into(),
+            Esc => "Esc".into(),
+            F(n) => format!("F{n}"),
+            Home => "Home".into(),
+            Insert => "Insert".into(),
+            Left => "Left".into(),
+            Leader => "Leader".into(),
+            LocalLeader => "LocalLeader".into(),
+            PageDown => "PageDown".into(),
+            PageUp => "PageUp".into(),
+            Right => "Right".into(),
+            Space => "Space".into(),
+            Tab => "Tab".into(),
+            Up => "Up".into(),
+        }
+    }
+}
+
+/// A sequence of keys built symbolically and stored in key notation, e.g.
+/// `Keys::new().ctrl('w').char('v')` is `<C-w>v`.
+///
+/// Characters added with [`char`](Keys::char) or [`text`](Keys::text) are
+/// escaped, so text containing a `<` is never mistaken for key notation.
+/// The sequence is turned into termcodes with [`termcodes`](Keys::termcodes)
+/// before being fed, so there's no need to call
+/// [`api::replace_termcodes`] by hand.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Keys {
+    notation: String,
+}
+
+impl Keys {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a single character.
+    pub fn char(mut self, ch: char) -> Self {
+        self.push_char(ch);
+        self
+    }
+
+    /// Adds every character of `text`.
+    pub fn text(mut self, text: &str) -> Self {
+        text.chars().for_each(|ch| self.push_char(ch));
+        self
+    }
+
+    /// Adds a special key.
+    pub fn key(mut self, key: Key) -> Self {
+        self.notation.push('<');
+        self.notation.push_str(&key.name());
+        self.notation.push('>');
+        self
+    }
+
+    /// Adds a character typed while holding Ctrl, e.g. `<C-w>`.
+    pub fn ctrl(self, ch: char) -> Self {
+        self.modified("C", ch)
+    }
+
+    /// Adds a character typed while holding Alt, e.g. `<M-x>`.
+    pub fn alt(self, ch: char) -> Self {
+        self.modified("M", ch)
+    }
+
+    /// Adds a special key typed while holding Ctrl, e.g. `<C-Left>`.
+    pub fn ctrl_key(mut self, key: Key) -> Self {
+        self.notation.push_str("<C-");
+        self.notation.push_str(&key.name());
+        self.notation.push('>');
+        self
+    }
+
+    /// Adds a special key typed while holding Shift, e.g. `<S-Tab>`.
+    pub fn shift_key(mut self, key: Key) -> Self {
+        self.notation.push_str("<S-");
+        self.notation.push_str(&key.name());
+        self.notation.push('>');
+        self
+    }
+
+    /// Adds `<CR>`.
+    #[inline]
+    pub fn enter(self) -> Self {
+        self.key(Key::Enter)
+    }
+
+    /// Adds `<Esc>`.
+    #[inline]
+    pub fn esc(self) -> Self {
+        self.key(Key::Esc)
+    }
+
+    /// Adds keys already written in key notation, which isn't escaped.
+    pub fn notation(mut self, notation: &str) -> Self {
+        self.notation.push_str(notation);
+        self
+    }
+
+    /// Returns the sequence in key notation, e.g. to be used as the
+    /// right-hand side of a mapping or passed to [`api::input`].
+    #[inline]
+    pub fn as_notation(&self) -> &str {
+        &self.notation
+    }
+
+    /// Replaces the key notation with the corresponding termcodes, as
+    /// expected by [`api::feedkeys`] when `escape_ks` is false.
+    pub fn termcodes(&self) -> NvimString {
+        api::replace_termcodes(self.notation.as_str(), true, true, true)
+    }
+
+    /// Feeds the keys with [`api::feedkeys`], taking care of replacing the
+    /// termcodes. See `:h feedkeys()` for the `mode` flags.
+    pub fn feed(&self, mode: &str) {
+        api::feedkeys(self.termcodes(), mode, false)
+    }
+
+    /// Queues the keys as raw user input with [`api::input`], returning the
+    /// number of bytes actually written.
+    pub fn input(&self) -> crate::Result<usize> {
+        api::input(self.notation.as_str())
+    }
+
+    fn push_char(&mut self, ch: char) {
+        match escaped_name(ch) {
+            Some(name) => {
+                self.notation.push('<');
+                self.notation.push_str(name);
+                self.notation.push('>');
+            },
+            None => self.notation.push(ch),
+        }
+    }
+
+    fn modified(mut self, modifier: &str, ch: char) -> Self {
+        self.notation.push('<');
+        self.notation.push_str(modifier);
+        self.notation.push('-');
+        match escaped_name(ch) {
+            Some(name) => self.notation.push_str(name),
+            None => self.notation.push(ch),
+        }
+        self.notation.push('>');
+        self
+    }
+}
+
+/// Returns the name of the characters that can't be written as themselves
+/// in key notation, or would be ignored in mappings.
+fn escaped_name(ch: char) -> Option<&'static str> {
+    match ch {
+        '<' => Some("lt"),
+        '\\' => Some("Bslash"),
+        '|' => Some("Bar"),
+        ' ' => Some("Space"),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Keys {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.notation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notation() {
+        let keys = Keys::new().ctrl('w').char('v').text("a<b> c").enter();
+        assert_eq!(keys.as_notation(), "<C-w>va<lt>b><Space>c<CR>");
+
+        let keys = Keys::new().shift_key(Key::Tab).key(Key::F(12)).alt('|');
+        assert_eq!(keys.to_string(), "<S-Tab><F12><M-Bar>");
+    }
+}
@@ -0,0 +1,30 @@
+use nvim_types::{object::Object, Integer};
+
+/// Severity of a notification, mirroring `vim.log.levels`.
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+
+    /// Never displayed. Only useful as a minimum level, to silence every
+    /// notification.
+    Off,
+}
+
+impl From<LogLevel> for Integer {
+    fn from(level: LogLevel) -> Self {
+        level as Integer
+    }
+}
+
+impl From<LogLevel> for Object {
+    fn from(level: LogLevel) -> Self {
+        Integer::from(level).into()
+    }
+}
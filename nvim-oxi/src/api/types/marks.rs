@@ -0,0 +1,188 @@
+use serde::{de, Deserialize};
+
+use super::Position;
+use crate::api::Buffer;
+
+/// A mark, as returned by `api::list_marks`. See `:h getmarklist()` for
+/// details.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct MarkInfos {
+    /// The name of the mark, e.g. `a` or `A`.
+    #[serde(rename = "mark", deserialize_with = "mark_name")]
+    pub name: char,
+
+    pub pos: MarkPosition,
+
+    /// The file the mark is in, only set for global marks.
+    pub file: Option<String>,
+}
+
+/// The position of a [`MarkInfos`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(from = "(Buffer, usize, usize, usize)")]
+pub struct MarkPosition {
+    pub buffer: Buffer,
+
+    /// 1-indexed line number.
+    pub lnum: usize,
+
+    /// 1-indexed column number.
+    pub col: usize,
+
+    /// Offset for `'virtualedit'`.
+    pub off: usize,
+}
+
+impl From<(Buffer, usize, usize, usize)> for MarkPosition {
+    fn from((buffer, lnum, col, off): (Buffer, usize, usize, usize)) -> Self {
+        Self { buffer, lnum, col, off }
+    }
+}
+
+impl MarkPosition {
+    /// Returns the (1,0)-indexed position, as used by `Window::set_cursor`.
+    pub fn position(&self) -> Position {
+        Position::new(self.lnum, self.col.saturating_sub(1))
+    }
+}
+
+/// The jumplist of a window, as returned by `Window::get_jumplist`. See
+/// `:h getjumplist()` for details.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(from = "(Vec<JumpEntry>, usize)")]
+pub struct Jumplist {
+    /// The entries, from the oldest to the newest.
+    pub entries: Vec<JumpEntry>,
+
+    /// The 0-indexed position in the list, equal to the number of entries
+    /// if no jump has been undone with `CTRL-O`.
+    pub current: usize,
+}
+
+impl From<(Vec<JumpEntry>, usize)> for Jumplist {
+    fn from((entries, current): (Vec<JumpEntry>, usize)) -> Self {
+        Self { entries, current }
+    }
+}
+
+/// An entry of a [`Jumplist`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct JumpEntry {
+    #[serde(rename = "bufnr")]
+    pub buffer: Buffer,
+
+    /// The name of the file, if the buffer isn't loaded.
+    pub filename: Option<String>,
+
+    /// 1-indexed line number.
+    pub lnum: usize,
+
+    /// 0-indexed column number.
+    pub col: usize,
+
+    /// Offset for `'virtualedit'`.
+    pub coladd: usize,
+}
+
+impl JumpEntry {
+    /// Returns the (1,0)-indexed position, as used by `Window::set_cursor`.
+    pub fn position(&self) -> Position {
+        Position::new(self.lnum, self.col)
+    }
+}
+
+/// The changelist of a buffer, as returned by `Buffer::get_changelist`. See
+/// `:h getchangelist()` for details.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(from = "(Vec<ChangeEntry>, usize)")]
+pub struct Changelist {
+    /// The entries, from the oldest to the newest.
+    pub entries: Vec<ChangeEntry>,
+
+    /// The 0-indexed position in the list. Only meaningful for the current
+    /// buffer, it's the number of entries for the others.
+    pub current: usize,
+}
+
+impl From<(Vec<ChangeEntry>, usize)> for Changelist {
+    fn from((entries, current): (Vec<ChangeEntry>, usize)) -> Self {
+        Self { entries, current }
+    }
+}
+
+/// An entry of a [`Changelist`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ChangeEntry {
+    /// 1-indexed line number.
+    pub lnum: usize,
+
+    /// 0-indexed column number.
+    pub col: usize,
+
+    /// Offset for `'virtualedit'`.
+    pub coladd: usize,
+}
+
+impl ChangeEntry {
+    /// Returns the (1,0)-indexed position, as used by `Window::set_cursor`.
+    pub fn position(&self) -> Position {
+        Position::new(self.lnum, self.col)
+    }
+}
+
+/// Deserializes the name of a mark from the `'a` form `getmarklist()`
+/// returns it in.
+fn mark_name<'de, D>(deserializer: D) -> Result<char, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let mark = String::deserialize(deserializer)?;
+    let mut chars = mark.strip_prefix('\'').unwrap_or(&mark).chars();
+    match (chars.next(), chars.next()) {
+        (Some(name), None) => Ok(name),
+        _ => Err(de::Error::invalid_value(
+            de::Unexpected::Str(&mark),
+            &"a mark name like 'a",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::{array::Array, dictionary::Dictionary, object::Object};
+
+    use super::*;
+    use crate::object::FromObject;
+
+    #[test]
+    fn mark_infos() {
+        let pos = Array::from_iter([1, 12, 5, 0]);
+        let mark = Dictionary::from_iter([
+            ("mark", Object::from("'A")),
+            ("pos", pos.into()),
+            ("file", "~/notes.md".into()),
+        ]);
+        let mark = MarkInfos::from_obj(mark.into()).unwrap();
+        assert_eq!(mark.name, 'A');
+        assert_eq!(mark.pos.position(), Position::new(12, 4));
+        assert_eq!(mark.file.as_deref(), Some("~/notes.md"));
+    }
+
+    #[test]
+    fn jumplist() {
+        let entry = Dictionary::from_iter([
+            ("bufnr", 3),
+            ("lnum", 40),
+            ("col", 2),
+            ("coladd", 0),
+        ]);
+        let list = Array::from_iter([
+            Object::from(Array::from_iter([entry])),
+            Object::from(1),
+        ]);
+        let list = Jumplist::from_obj(list.into()).unwrap();
+        assert_eq!(list.current, 1);
+        assert_eq!(list.entries[0].filename, None);
+        assert_eq!(list.entries[0].position(), Position::new(40, 2));
+    }
+}
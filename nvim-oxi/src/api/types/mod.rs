@@ -1,13 +1,94 @@
+mod api_metadata;
+mod autocmd_callback_args;
+mod cmd_infos;
+mod cmd_magic;
+mod cmd_output;
 mod command_addr;
+mod command_args;
 mod command_infos;
+mod command_modifiers;
 mod command_nargs;
+mod command_preview;
 mod command_range;
+mod editor_context;
+mod exec_output;
+mod got_mode;
 mod keymap_infos;
+mod keys;
+mod log_level;
+mod marks;
 mod mode;
+mod namespace;
+mod option_infos;
+mod parsed_cmd;
+mod paste_phase;
+mod position;
+mod pum_info;
+mod register;
+mod shada;
+#[cfg(feature = "diagnostics")]
+mod stats;
+mod statusline_infos;
+mod tags;
+#[cfg(feature = "neovim-nightly")]
+mod text_height;
+mod text_object;
+mod view;
 
+pub use api_metadata::{
+    ApiErrorType,
+    ApiFunction,
+    ApiMetadata,
+    ApiType,
+    ApiVersion,
+    UiEvent,
+};
+pub use autocmd_callback_args::AutocmdCallbackArgs;
+pub use cmd_infos::{CmdInfos, CmdInfosBuilder, CmdRange};
+pub use cmd_magic::CmdMagic;
+pub use cmd_output::CmdOutput;
 pub use command_addr::CommandAddr;
+pub use command_args::CommandArgs;
 pub use command_infos::CommandInfos;
+pub use command_modifiers::{CommandModifiers, FilterInfos, SplitModifier};
 pub use command_nargs::CommandNArgs;
+pub use command_preview::{CommandPreview, CommandPreviewArgs};
 pub use command_range::CommandRange;
+pub use editor_context::{EditorContext, RegisterSnapshot};
+pub use exec_output::ExecOutput;
+pub use got_mode::{CurrentMode, GotMode};
 pub use keymap_infos::KeymapInfos;
+pub use keys::{Key, Keys};
+pub use log_level::LogLevel;
+pub use marks::{
+    ChangeEntry,
+    Changelist,
+    JumpEntry,
+    Jumplist,
+    MarkInfos,
+    MarkPosition,
+};
 pub use mode::Mode;
+pub use namespace::Namespace;
+pub use option_infos::{OptionInfos, OptionLocality, OptionType};
+pub use parsed_cmd::ParsedCmd;
+pub use paste_phase::PastePhase;
+pub use position::Position;
+#[cfg(feature = "neovim-nightly")]
+pub use pum_info::CompleteSetInfos;
+pub use pum_info::{PumInfo, PumItem, PumPosition};
+pub use register::{RegisterContents, RegisterType};
+#[cfg(feature = "diagnostics")]
+pub use stats::{BufferStats, NvimStats};
+pub use statusline_infos::{StatuslineHighlightInfos, StatuslineInfos};
+pub use tags::{
+    TagInfo,
+    TagStack,
+    TagStackAction,
+    TagStackFrom,
+    TagStackItem,
+};
+#[cfg(feature = "neovim-nightly")]
+pub use text_height::TextHeight;
+pub use text_object::{TextObjectKind, TextRange};
+pub use view::View;
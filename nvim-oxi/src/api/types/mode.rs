@@ -1,7 +1,8 @@
 use nvim_types::string::String as NvimString;
 use serde::{Deserialize, Serialize};
 
-/// TODO: docs
+/// The mode of a mapping, see `:h map-modes`. The modes Neovim can actually
+/// be in, as returned by `api::get_mode`, are [`CurrentMode`](super::CurrentMode)s.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Mode {
     #[serde(rename = "c")]
@@ -16,8 +17,10 @@ pub enum Mode {
     #[serde(rename = "l")]
     Langmap,
 
-    /// SEGFAULT: passing this to `Buffer::get_keymap` causes a segfault?
-    #[serde(rename = "")]
+    /// The modes of `:map`. Mappings defined in these modes have a `" "`
+    /// mode in their `KeymapInfos`.
+    #[doc(alias = "Any")]
+    #[serde(rename = "", alias = " ")]
     NormalVisualOperator,
 
     #[serde(rename = "n")]
@@ -99,3 +102,33 @@ impl From<Mode> for NvimString {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::FromObject;
+
+    #[test]
+    fn modes_round_trip() {
+        use Mode::*;
+        for (mode, name) in [
+            (CmdLine, "c"),
+            (Insert, "i"),
+            (InsertCmdLine, "!"),
+            (Langmap, "l"),
+            (NormalVisualOperator, ""),
+            (Normal, "n"),
+            (OperatorPending, "o"),
+            (Select, "s"),
+            (Terminal, "t"),
+            (Visual, "x"),
+            (VisualSelect, "v"),
+        ] {
+            assert_eq!(NvimString::from(mode), name);
+            assert_eq!(Mode::from_obj(name.into()).unwrap(), mode);
+        }
+
+        let map_mode = Mode::from_obj(" ".into()).unwrap();
+        assert_eq!(map_mode, NormalVisualOperator);
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+
+use nvim_types::{object::Object, Integer};
+use serde::{Deserialize, Serialize};
+
+/// A namespace grouping extmarks, highlights, decoration providers and
+/// diagnostics. See `:h namespace` for details.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(transparent)]
+pub struct Namespace(pub(crate) u32);
+
+impl Namespace {
+    /// The namespace of the highlight groups defined with `:highlight`, used
+    /// by windows that don't have a highlight namespace of their own.
+    pub const GLOBAL: Self = Self(0);
+
+    /// The id of the namespace.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Namespace({})", self.0)
+    }
+}
+
+impl From<u32> for Namespace {
+    fn from(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl From<Namespace> for Object {
+    fn from(ns: Namespace) -> Self {
+        Integer::from(ns.0).into()
+    }
+}
+
+impl From<Namespace> for Integer {
+    fn from(ns: Namespace) -> Self {
+        ns.0.into()
+    }
+}
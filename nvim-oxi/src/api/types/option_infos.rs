@@ -0,0 +1,51 @@
+use serde::Deserialize;
+
+/// The result of `api::get_option_info`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct OptionInfos {
+    pub name: String,
+
+    /// The short name of the option, or an empty string if it has none.
+    pub shortname: String,
+
+    #[serde(rename = "type")]
+    pub ty: OptionType,
+
+    pub scope: OptionLocality,
+
+    /// Whether the option is global with a local value, like `'undolevels'`.
+    pub global_local: bool,
+
+    /// Whether the option has been set since Neovim started.
+    pub was_set: bool,
+
+    /// Whether the option is a comma-separated list.
+    pub commalist: bool,
+
+    /// Whether the option is a list of single character flags.
+    pub flaglist: bool,
+
+    pub last_set_sid: i64,
+
+    pub last_set_linenr: i64,
+
+    pub last_set_chan: i64,
+}
+
+/// The type of an option's value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionType {
+    Boolean,
+    Number,
+    String,
+}
+
+/// Where an option's local value lives.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionLocality {
+    Global,
+    Win,
+    Buf,
+}
@@ -0,0 +1,149 @@
+use serde::{de, Deserialize};
+
+use super::{CmdInfos, CommandAddr, CommandNArgs};
+
+/// The result of `api::parse_cmd`. The parsed command can be executed as is
+/// by passing [`infos`](ParsedCmd::infos) to `api::cmd`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct ParsedCmd {
+    #[serde(flatten)]
+    pub infos: CmdInfos,
+
+    /// The type of address the command's range refers to, or `None` if the
+    /// command doesn't accept a range.
+    #[serde(default, deserialize_with = "short_addr")]
+    pub addr: Option<CommandAddr>,
+
+    /// The number of arguments accepted by the command.
+    #[serde(default)]
+    pub nargs: Option<CommandNArgs>,
+
+    /// The command following a `|`, if any, left unparsed.
+    #[serde(default, deserialize_with = "nextcmd_or_none")]
+    pub nextcmd: Option<String>,
+}
+
+/// `nvim_parse_cmd` uses the short names of `:h command-addr`, `"none"` for
+/// commands that don't take a range and `"?"` for unknown address types.
+fn short_addr<'de, D>(deserializer: D) -> Result<Option<CommandAddr>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    use CommandAddr::*;
+    Ok(Some(match String::deserialize(deserializer)?.as_str() {
+        "line" | "lines" => Lines,
+        "arg" | "arguments" => Arguments,
+        "buf" | "buffers" => Buffers,
+        "load" | "loaded_buffers" => LoadedBuffers,
+        "win" | "windows" => Windows,
+        "tab" | "tabs" => Tabs,
+        "qf" | "quickfix" => Quickfix,
+        "other" => Other,
+        _ => return Ok(None),
+    }))
+}
+
+fn nextcmd_or_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let nextcmd = Option::<String>::deserialize(deserializer)?;
+    Ok(nextcmd.filter(|cmd| !cmd.is_empty()))
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+    use crate::api::types::{CmdRange, FilterInfos, SplitModifier};
+    use crate::api::vimscript::opts::{CmdOpts, ExecOpts, ParseCmdOpts};
+    use crate::{api, Result};
+
+    fn parse(src: &str) -> Result<ParsedCmd> {
+        api::parse_cmd(src, &ParseCmdOpts::default())
+    }
+
+    #[nvim_oxi::test]
+    fn parse_cmd_fields() -> Result<()> {
+        let subst = parse("silent! 1,3s/a/b/g")?;
+        assert_eq!(subst.infos.cmd.as_deref(), Some("substitute"));
+        assert_eq!(subst.infos.range, Some(CmdRange::Double(1, 3)));
+        assert_eq!(subst.addr, Some(CommandAddr::Lines));
+        assert_eq!(subst.nargs, Some(CommandNArgs::Any));
+        let mods = subst.infos.mods.unwrap();
+        assert!(mods.silent && mods.emsg_silent);
+        assert_eq!((mods.filter, mods.split), (None, None));
+
+        let ls = parse("filter! /foo/ ls")?;
+        assert_eq!(ls.addr, None);
+        assert_eq!(
+            ls.infos.mods.unwrap().filter,
+            Some(FilterInfos { pattern: "foo".into(), force: true })
+        );
+
+        let verbose = parse("verbose 2 set ts?")?.infos.mods.unwrap();
+        assert_eq!(verbose.verbose, Some(2));
+
+        let split = parse("botright vsplit")?.infos.mods.unwrap();
+        assert_eq!(split.split, Some(SplitModifier::BotRight));
+
+        let yank = parse("2yank a 2")?;
+        assert_eq!((yank.infos.reg, yank.infos.count), (Some('a'), Some(2)));
+
+        let bdelete = parse("$bdelete! | echo")?;
+        assert!(bdelete.infos.bang);
+        assert_eq!(bdelete.addr, Some(CommandAddr::Buffers));
+        assert_eq!(bdelete.nextcmd.as_deref(), Some("echo"));
+
+        let magic = parse("edit %")?.infos.magic.unwrap();
+        assert!(magic.file && magic.bar);
+
+        Ok(())
+    }
+
+    /// Executing what `parse_cmd` returns with `cmd` must have the same
+    /// effects as executing the command line itself.
+    #[nvim_oxi::test]
+    fn parse_cmd_then_cmd_is_lossless() -> Result<()> {
+        let corpus = [
+            "silent! 1,3s/a/b/g",
+            "tab split",
+            "botright vsplit",
+            "verbose 2 set ts?",
+            "filter /loaded_/ let g:",
+            "2yank a 2",
+            "$bdelete!",
+        ];
+
+        for src in corpus {
+            assert_eq!(run(src, false)?, run(src, true)?, "{src:?}");
+        }
+
+        Ok(())
+    }
+
+    /// Runs `src` from a clean state, either as is or through `parse_cmd`
+    /// and `cmd`, and returns its output with a snapshot of the editor.
+    fn run(src: &str, parsed: bool) -> Result<(Option<String>, String)> {
+        api::exec2(
+            "silent! tabonly | silent! only | enew! | let @a = ''\ncall \
+             setline(1, ['a1', 'a2', 'a3', 'a4'])",
+            &ExecOpts::default(),
+        )?;
+
+        let output = if parsed {
+            let opts = CmdOpts::builder().output(true).build().unwrap();
+            api::cmd(&parse(src)?.infos, &opts)?.output
+        } else {
+            let opts = ExecOpts::builder().output(true).build().unwrap();
+            api::exec2(src, &opts)?.output
+        };
+
+        // Window and buffer numbers change between runs.
+        let state = api::eval(
+            "string([getline(1, '$'), winnr('$'), winnr(), tabpagenr('$'), \
+             getreg('a')])",
+        )?;
+
+        Ok((output, state))
+    }
+}
@@ -0,0 +1,29 @@
+use nvim_types::Integer;
+
+/// Phase of a (possibly streamed) paste, as expected by `api::paste`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PastePhase {
+    /// The whole paste is contained in a single call.
+    Single,
+
+    /// First chunk of a streamed paste.
+    Start,
+
+    /// Any chunk between the first and the last ones.
+    Continue,
+
+    /// Last chunk of a streamed paste.
+    End,
+}
+
+impl From<PastePhase> for Integer {
+    fn from(phase: PastePhase) -> Self {
+        use PastePhase::*;
+        match phase {
+            Single => -1,
+            Start => 1,
+            Continue => 2,
+            End => 3,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+/// A position in a buffer, as used by the cursor and mark functions. The
+/// `row` is 1-indexed and the `col` is a 0-indexed byte offset in the line,
+/// which `:h api-indexing` calls (1,0)-indexing.
+///
+/// Functions taking 0-indexed lines, like `Buffer::get_lines`, can be fed
+/// with [`to_zero_indexed`](Position::to_zero_indexed).
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(from = "(usize, usize)", into = "(usize, usize)")]
+pub struct Position {
+    /// The line, starting from 1.
+    pub row: usize,
+
+    /// The byte offset in the line, starting from 0.
+    pub col: usize,
+}
+
+impl Position {
+    /// Creates a (1,0)-indexed position.
+    #[inline]
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Creates a position from a 0-indexed row and column.
+    #[inline]
+    pub const fn from_zero_indexed(row: usize, col: usize) -> Self {
+        Self { row: row + 1, col }
+    }
+
+    /// Returns the 0-indexed row and column, or `None` for the row 0 used
+    /// by Neovim for marks that aren't set.
+    #[inline]
+    pub fn to_zero_indexed(self) -> Option<(usize, usize)> {
+        self.row.checked_sub(1).map(|row| (row, self.col))
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    #[inline]
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Position> for (usize, usize) {
+    #[inline]
+    fn from(pos: Position) -> Self {
+        (pos.row, pos.col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::{array::Array, object::Object};
+
+    use super::*;
+    use crate::object::{FromObject, ToObject};
+
+    #[test]
+    fn from_array() {
+        let arr = Array::from_iter([Object::from(3), Object::from(7)]);
+        let pos = Position::from_obj(arr.into()).unwrap();
+        assert_eq!(pos, Position::new(3, 7));
+        assert_eq!(pos.to_zero_indexed(), Some((2, 7)));
+        assert_eq!(Position::from_zero_indexed(2, 7), pos);
+
+        let obj = pos.to_obj().unwrap();
+        assert_eq!(Position::from_obj(obj).unwrap(), pos);
+    }
+
+    #[test]
+    fn unset_mark() {
+        assert_eq!(Position::new(0, 0).to_zero_indexed(), None);
+    }
+}
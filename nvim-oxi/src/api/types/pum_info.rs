@@ -0,0 +1,69 @@
+use serde::{de, Deserialize};
+
+use super::keymap_infos::bool_from_int;
+
+/// The state of the popup menu, as returned by `api::get_pum_info`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct PumInfo {
+    /// The current completion mode, e.g. `keyword` or `omni`, or an empty
+    /// string if not completing. See `:h complete_info_mode`.
+    pub mode: String,
+
+    #[serde(deserialize_with = "bool_from_int")]
+    pub pum_visible: bool,
+
+    /// The items in the popup menu.
+    pub items: Vec<PumItem>,
+
+    /// The index of the selected item, if any.
+    #[serde(deserialize_with = "negative_is_none")]
+    pub selected: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+#[serde(default)]
+pub struct PumItem {
+    pub word: String,
+    pub abbr: String,
+    pub kind: String,
+    pub menu: String,
+    pub info: String,
+}
+
+/// The position of the popup menu, as returned by
+/// `api::get_pum_position`.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub struct PumPosition {
+    pub height: f64,
+    pub width: f64,
+
+    /// Screen row of the menu, 0-indexed.
+    pub row: f64,
+
+    /// Screen column of the menu, 0-indexed.
+    pub col: f64,
+
+    /// The total number of items.
+    pub size: usize,
+
+    /// Whether a scrollbar is visible.
+    pub scrollbar: bool,
+}
+
+/// The result of `api::complete_set`.
+#[cfg(feature = "neovim-nightly")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct CompleteSetInfos {
+    /// The window displaying the info, if the popup is visible.
+    pub winid: Option<crate::api::Window>,
+
+    /// The buffer holding the info, if the popup is visible.
+    pub bufnr: Option<crate::api::Buffer>,
+}
+
+fn negative_is_none<'de, D>(deserializer: D) -> Result<Option<usize>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    i64::deserialize(deserializer).map(|idx| idx.try_into().ok())
+}
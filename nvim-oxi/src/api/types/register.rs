@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::Error;
+
+/// The contents of a register, as returned by `api::get_register`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RegisterContents {
+    /// The text stored in the register, one element per line.
+    pub lines: Vec<String>,
+
+    pub regtype: RegisterType,
+}
+
+/// How the text in a register is put. See `:h getregtype()` for details.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum RegisterType {
+    Charwise,
+    Linewise,
+
+    /// If `width` is `None` it's set to the length of the longest line.
+    Blockwise {
+        width: Option<usize>,
+    },
+}
+
+impl fmt::Display for RegisterType {
+    /// Formats the type as expected by the `options` of `setreg()`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use RegisterType::*;
+        match self {
+            Charwise => f.write_str("c"),
+            Linewise => f.write_str("l"),
+            Blockwise { width: None } => f.write_str("b"),
+            Blockwise { width: Some(width) } => write!(f, "b{width}"),
+        }
+    }
+}
+
+impl std::str::FromStr for RegisterType {
+    type Err = Error;
+
+    /// Parses the output of `getregtype()`, or a type in the format
+    /// accepted by `setreg()`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad_regtype =
+            || Error::DeserializeError(format!("invalid regtype \"{s}\""));
+
+        match s {
+            "v" | "c" => Ok(Self::Charwise),
+            "V" | "l" => Ok(Self::Linewise),
+            _ => {
+                let width = s
+                    .strip_prefix('\x16')
+                    .or_else(|| s.strip_prefix('b'))
+                    .ok_or_else(bad_regtype)?;
+                let width = (!width.is_empty())
+                    .then(|| width.parse())
+                    .transpose()
+                    .map_err(|_| bad_regtype())?;
+                Ok(Self::Blockwise { width })
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regtype_roundtrip() {
+        assert_eq!(
+            "v".parse::<RegisterType>().ok(),
+            Some(RegisterType::Charwise)
+        );
+        assert_eq!(
+            "V".parse::<RegisterType>().ok(),
+            Some(RegisterType::Linewise)
+        );
+        assert_eq!(
+            "\x1612".parse::<RegisterType>().ok(),
+            Some(RegisterType::Blockwise { width: Some(12) })
+        );
+        assert_eq!(
+            "b".parse::<RegisterType>().ok(),
+            Some(RegisterType::Blockwise { width: None })
+        );
+        assert!("".parse::<RegisterType>().is_err());
+        assert!("\x16a".parse::<RegisterType>().is_err());
+
+        assert_eq!(
+            RegisterType::Blockwise { width: Some(3) }.to_string(),
+            "b3"
+        );
+        assert_eq!(RegisterType::Blockwise { width: None }.to_string(), "b");
+    }
+}
@@ -0,0 +1,446 @@
+//! A minimal reader and writer for the register entries of the ShaDa
+//! format (see `:h shada-format`), which is how `nvim_get_context` and
+//! `nvim_load_context` exchange registers.
+
+use nvim_types::string::String as NvimString;
+
+use super::{RegisterSnapshot, RegisterType};
+use crate::{Error, Result};
+
+/// The ShaDa entry type of registers.
+const REGISTER_ENTRY: u64 = 5;
+
+/// A MessagePack value.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Bytes(Vec<u8>),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Ext,
+}
+
+impl Value {
+    fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(int) => Some(*int),
+            _ => None,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Value> {
+        let Self::Map(pairs) = self else { return None };
+        pairs.iter().find_map(|(k, v)| match k {
+            Self::Bytes(k) if k == key.as_bytes() => Some(v),
+            _ => None,
+        })
+    }
+}
+
+fn bad_shada(msg: &str) -> Error {
+    Error::DeserializeError(format!("invalid ShaDa data: {msg}"))
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(bad_shada("unexpected end of data"));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn uint(&mut self, len: usize) -> Result<u64> {
+        Ok(self.take(len)?.iter().fold(0, |n, &byte| n << 8 | byte as u64))
+    }
+
+    fn int(&mut self, len: usize) -> Result<i64> {
+        let n = self.uint(len)?;
+        let shift = 64 - 8 * len as u32;
+        Ok(((n << shift) as i64) >> shift)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<Value> {
+        Ok(Value::Bytes(self.take(len)?.to_vec()))
+    }
+
+    fn array(&mut self, len: usize) -> Result<Value> {
+        (0..len).map(|_| self.value()).collect::<Result<_>>().map(Value::Array)
+    }
+
+    fn map(&mut self, len: usize) -> Result<Value> {
+        (0..len)
+            .map(|_| Ok((self.value()?, self.value()?)))
+            .collect::<Result<_>>()
+            .map(Value::Map)
+    }
+
+    fn ext(&mut self, len: usize) -> Result<Value> {
+        self.take(len + 1).map(|_| Value::Ext)
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let marker = self.take(1)?[0];
+
+        match marker {
+            0x00..=0x7f => Ok(Value::Int(marker as i64)),
+            0x80..=0x8f => self.map((marker & 0x0f) as usize),
+            0x90..=0x9f => self.array((marker & 0x0f) as usize),
+            0xa0..=0xbf => self.bytes((marker & 0x1f) as usize),
+            0xc0 => Ok(Value::Nil),
+            0xc2 => Ok(Value::Bool(false)),
+            0xc3 => Ok(Value::Bool(true)),
+            0xc4 | 0xd9 => {
+                let len = self.uint(1)? as usize;
+                self.bytes(len)
+            },
+            0xc5 | 0xda => {
+                let len = self.uint(2)? as usize;
+                self.bytes(len)
+            },
+            0xc6 | 0xdb => {
+                let len = self.uint(4)? as usize;
+                self.bytes(len)
+            },
+            0xc7 => {
+                let len = self.uint(1)? as usize;
+                self.ext(len)
+            },
+            0xc8 => {
+                let len = self.uint(2)? as usize;
+                self.ext(len)
+            },
+            0xc9 => {
+                let len = self.uint(4)? as usize;
+                self.ext(len)
+            },
+            0xca => {
+                let bits = self.uint(4)? as u32;
+                Ok(Value::Float(f32::from_bits(bits) as f64))
+            },
+            0xcb => Ok(Value::Float(f64::from_bits(self.uint(8)?))),
+            0xcc => Ok(Value::Int(self.uint(1)? as i64)),
+            0xcd => Ok(Value::Int(self.uint(2)? as i64)),
+            0xce => Ok(Value::Int(self.uint(4)? as i64)),
+            0xcf => Ok(Value::Int(self.uint(8)? as i64)),
+            0xd0 => Ok(Value::Int(self.int(1)?)),
+            0xd1 => Ok(Value::Int(self.int(2)?)),
+            0xd2 => Ok(Value::Int(self.int(4)?)),
+            0xd3 => Ok(Value::Int(self.int(8)?)),
+            0xd4 => self.ext(1),
+            0xd5 => self.ext(2),
+            0xd6 => self.ext(4),
+            0xd7 => self.ext(8),
+            0xd8 => self.ext(16),
+            0xdc => {
+                let len = self.uint(2)? as usize;
+                self.array(len)
+            },
+            0xdd => {
+                let len = self.uint(4)? as usize;
+                self.array(len)
+            },
+            0xde => {
+                let len = self.uint(2)? as usize;
+                self.map(len)
+            },
+            0xdf => {
+                let len = self.uint(4)? as usize;
+                self.map(len)
+            },
+            0xe0..=0xff => Ok(Value::Int(marker as i8 as i64)),
+            0xc1 => Err(bad_shada("reserved MessagePack marker")),
+        }
+    }
+}
+
+fn write_uint(buf: &mut Vec<u8>, n: u64) {
+    match n {
+        0..=0x7f => buf.push(n as u8),
+        0x80..=0xff => buf.extend([0xcc, n as u8]),
+        0x100..=0xffff => {
+            buf.push(0xcd);
+            buf.extend((n as u16).to_be_bytes());
+        },
+        0x1_0000..=0xffff_ffff => {
+            buf.push(0xce);
+            buf.extend((n as u32).to_be_bytes());
+        },
+        _ => {
+            buf.push(0xcf);
+            buf.extend(n.to_be_bytes());
+        },
+    }
+}
+
+fn write_len(buf: &mut Vec<u8>, len: usize, fix: u8, markers: [u8; 2]) {
+    match len {
+        0..=15 => buf.push(fix | len as u8),
+        16..=0xffff => {
+            buf.push(markers[0]);
+            buf.extend((len as u16).to_be_bytes());
+        },
+        _ => {
+            buf.push(markers[1]);
+            buf.extend((len as u32).to_be_bytes());
+        },
+    }
+}
+
+fn write_str(buf: &mut Vec<u8>, str: &str) {
+    // Keys are always short.
+    buf.push(0xa0 | str.len() as u8);
+    buf.extend(str.as_bytes());
+}
+
+fn write_bin(buf: &mut Vec<u8>, bytes: &[u8]) {
+    match bytes.len() {
+        0..=0xff => buf.extend([0xc4, bytes.len() as u8]),
+        0x100..=0xffff => {
+            buf.push(0xc5);
+            buf.extend((bytes.len() as u16).to_be_bytes());
+        },
+        _ => {
+            buf.push(0xc6);
+            buf.extend((bytes.len() as u32).to_be_bytes());
+        },
+    }
+    buf.extend(bytes);
+}
+
+/// Parses the register entries contained in ShaDa data, ignoring any other
+/// entry.
+pub(super) fn decode_registers(bytes: &[u8]) -> Result<Vec<RegisterSnapshot>> {
+    let mut decoder = Decoder { bytes };
+    let mut registers = Vec::new();
+
+    while !decoder.is_empty() {
+        let header = [decoder.value()?, decoder.value()?, decoder.value()?];
+
+        let [Value::Int(ty), Value::Int(_timestamp), Value::Int(len)] = header
+        else {
+            return Err(bad_shada("bad entry header"));
+        };
+
+        let len = usize::try_from(len)
+            .map_err(|_| bad_shada("negative entry length"))?;
+        let mut data = Decoder { bytes: decoder.take(len)? };
+
+        if ty as u64 == REGISTER_ENTRY {
+            registers.push(decode_register(&data.value()?)?);
+        }
+    }
+
+    Ok(registers)
+}
+
+fn decode_register(data: &Value) -> Result<RegisterSnapshot> {
+    let name = data
+        .get("n")
+        .and_then(Value::as_int)
+        .and_then(|n| u32::try_from(n).ok())
+        .and_then(char::from_u32)
+        .ok_or_else(|| bad_shada("register without a name"))?;
+
+    let contents = match data.get("rc") {
+        Some(Value::Array(lines)) => lines
+            .iter()
+            .map(|line| match line {
+                Value::Bytes(bytes) => {
+                    Ok(NvimString::from_bytes(bytes.clone()))
+                },
+                _ => Err(bad_shada("register line is not a string")),
+            })
+            .collect::<Result<_>>()?,
+        _ => return Err(bad_shada("register without contents")),
+    };
+
+    let regtype = match data.get("rt").and_then(Value::as_int).unwrap_or(0) {
+        0 => RegisterType::Charwise,
+        1 => RegisterType::Linewise,
+        2 => {
+            let width = data.get("rw").and_then(Value::as_int).unwrap_or(0);
+            let width = usize::try_from(width)
+                .ok()
+                .and_then(|width| width.checked_add(1))
+                .ok_or_else(|| bad_shada("invalid block width"))?;
+            RegisterType::Blockwise { width: Some(width) }
+        },
+        _ => return Err(bad_shada("unknown register type")),
+    };
+
+    let is_unnamed = matches!(data.get("ru"), Some(Value::Bool(true)));
+
+    Ok(RegisterSnapshot { name, contents, regtype, is_unnamed })
+}
+
+/// Encodes registers as ShaDa register entries.
+pub(super) fn encode_registers(
+    registers: &[RegisterSnapshot],
+    timestamp: u64,
+) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    for register in registers {
+        let data = encode_register(register);
+        write_uint(&mut buf, REGISTER_ENTRY);
+        write_uint(&mut buf, timestamp);
+        write_uint(&mut buf, data.len() as u64);
+        buf.extend(data);
+    }
+
+    buf
+}
+
+fn encode_register(register: &RegisterSnapshot) -> Vec<u8> {
+    let (regtype, width) = match register.regtype {
+        RegisterType::Charwise => (0, None),
+        RegisterType::Linewise => (1, None),
+        RegisterType::Blockwise { width } => {
+            let width = width.unwrap_or_else(|| {
+                let lens = register
+                    .contents
+                    .iter()
+                    .map(|line| line.to_string_lossy().chars().count());
+                lens.max().unwrap_or_default()
+            });
+            (2, Some(width.saturating_sub(1)))
+        },
+    };
+
+    let mut buf = Vec::new();
+    let len = 3 + width.is_some() as usize + register.is_unnamed as usize;
+    write_len(&mut buf, len, 0x80, [0xde, 0xdf]);
+
+    write_str(&mut buf, "n");
+    write_uint(&mut buf, register.name as u64);
+
+    write_str(&mut buf, "rt");
+    write_uint(&mut buf, regtype);
+
+    if let Some(width) = width {
+        write_str(&mut buf, "rw");
+        write_uint(&mut buf, width as u64);
+    }
+
+    if register.is_unnamed {
+        write_str(&mut buf, "ru");
+        buf.push(0xc3);
+    }
+
+    write_str(&mut buf, "rc");
+    write_len(&mut buf, register.contents.len(), 0x90, [0xdc, 0xdd]);
+    for line in &register.contents {
+        write_bin(&mut buf, line.as_bytes());
+    }
+
+    buf
+}
+
+/// Joins the lines of a list in `readfile()`'s binary format back into
+/// the original bytes.
+pub(super) fn lines_to_bytes<'a, Lines>(lines: Lines) -> Vec<u8>
+where
+    Lines: IntoIterator<Item = &'a [u8]>,
+{
+    let mut bytes = Vec::new();
+    for (idx, line) in lines.into_iter().enumerate() {
+        if idx > 0 {
+            bytes.push(b'\n');
+        }
+        bytes.extend(line.iter().map(|&b| if b == b'\n' { 0 } else { b }));
+    }
+    bytes
+}
+
+/// Splits bytes into lines in `readfile()`'s binary format, where newlines
+/// separate the lines and NUL bytes are represented by newlines.
+pub(super) fn bytes_to_lines(bytes: &[u8]) -> Vec<Vec<u8>> {
+    bytes
+        .split(|&b| b == b'\n')
+        .map(|line| line.iter().map(|&b| if b == 0 { b'\n' } else { b }))
+        .map(Iterator::collect)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_roundtrip() {
+        let registers = vec![
+            RegisterSnapshot {
+                name: 'a',
+                contents: vec!["foo".into(), "bar".into()],
+                regtype: RegisterType::Linewise,
+                is_unnamed: true,
+            },
+            RegisterSnapshot {
+                name: '"',
+                contents: vec![
+                    "x".repeat(300).into(),
+                    "é\n".into(),
+                    NvimString::from_bytes(b"\xff\x00".to_vec()),
+                ],
+                regtype: RegisterType::Blockwise { width: Some(300) },
+                is_unnamed: false,
+            },
+        ];
+
+        let bytes = encode_registers(&registers, 1_650_000_000);
+        let lines = bytes_to_lines(&bytes);
+        let bytes = lines_to_bytes(lines.iter().map(Vec::as_slice));
+
+        assert_eq!(decode_registers(&bytes).unwrap(), registers);
+    }
+
+    #[test]
+    fn invalid_block_widths() {
+        let mut bytes = encode_registers(
+            &[RegisterSnapshot {
+                name: 'a',
+                contents: vec!["foo".into()],
+                regtype: RegisterType::Blockwise { width: Some(3) },
+                is_unnamed: false,
+            }],
+            0,
+        );
+
+        // Replaces the width, `2` in the `rw` field, with -1.
+        let idx = bytes.windows(3).position(|w| w == [0xa2, b'r', b'w']);
+        bytes[idx.unwrap() + 3] = 0xff;
+        assert!(decode_registers(&bytes).is_err());
+    }
+
+    #[test]
+    fn skip_other_entries() {
+        // A header entry (type 1) with an empty map.
+        let mut bytes = vec![0x01, 0x00, 0x01, 0x80];
+        bytes.extend(encode_registers(
+            &[RegisterSnapshot {
+                name: '0',
+                contents: vec!["baz".into()],
+                regtype: RegisterType::Charwise,
+                is_unnamed: false,
+            }],
+            0,
+        ));
+
+        let registers = decode_registers(&bytes).unwrap();
+        assert_eq!(registers.len(), 1);
+        assert_eq!(registers[0].name, '0');
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+/// Internal statistics about a buffer, returned by `Buffer::stats`.
+///
+/// **Unstable**: this mirrors the private `nvim__buf_stats` function, whose
+/// output can change in any Neovim release. Fields missing in the running
+/// version are `None`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+#[serde(default)]
+pub struct BufferStats {
+    /// How many times the cached line has been flushed to the memline.
+    pub flush_count: Option<u64>,
+
+    /// The line currently cached by the memline.
+    pub current_lnum: Option<u64>,
+
+    /// Whether the cached line has changes that haven't been flushed yet.
+    pub line_dirty: Option<bool>,
+
+    /// Bytes deleted since the last `on_bytes` notification. Should always
+    /// be zero when called from the API.
+    pub dirty_bytes: Option<u64>,
+
+    /// Like `dirty_bytes`, but counted in a different way.
+    pub dirty_bytes2: Option<u64>,
+
+    /// Number of virtual lines placed by extmarks.
+    pub virt_blocks: Option<u64>,
+
+    /// Size of the extmark changes saved in the current undo header.
+    pub uhp_extmark_size: Option<u64>,
+}
+
+/// Internal statistics about the editor, returned by `api::stats`.
+///
+/// **Unstable**: this mirrors the private `nvim__stats` function, whose
+/// output can change in any Neovim release. Fields missing in the running
+/// version are `None`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+#[serde(default)]
+pub struct NvimStats {
+    /// How many times `fsync()` has been called.
+    pub fsync: Option<u64>,
+
+    /// How many times the screen has been redrawn.
+    pub redraw: Option<u64>,
+
+    /// Number of log messages skipped because of the log level.
+    pub log_skip: Option<u64>,
+
+    /// Number of references held into the Lua registry, including the ones
+    /// held by this crate's `LuaFn`s.
+    pub lua_refcount: Option<u64>,
+
+    /// Number of allocations made by arena allocators.
+    pub arena_alloc_count: Option<u64>,
+}
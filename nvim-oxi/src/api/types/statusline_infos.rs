@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+/// The result of `api::eval_statusline`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct StatuslineInfos {
+    /// The evaluated statusline.
+    pub str: String,
+
+    /// Display width of the statusline.
+    pub width: usize,
+
+    /// Where each highlight group starts, if `EvalStatuslineOpts::highlights`
+    /// was set.
+    pub highlights: Option<Vec<StatuslineHighlightInfos>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct StatuslineHighlightInfos {
+    /// Byte index (0-based) of the first character that uses the highlight.
+    pub start: usize,
+
+    /// Name of the highlight group.
+    pub group: String,
+}
@@ -0,0 +1,107 @@
+use nvim_types::{dictionary::Dictionary, object::Object};
+use serde::Deserialize;
+
+use crate::api::Buffer;
+
+/// A window's tag stack, as returned by `Window::get_tagstack`. See
+/// `:h gettagstack()` for details.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct TagStack {
+    /// The number of entries in the stack.
+    pub length: usize,
+
+    /// The 1-indexed position of the current entry.
+    pub curidx: usize,
+
+    pub items: Vec<TagStackItem>,
+}
+
+/// An entry of a tag stack.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct TagStackItem {
+    /// The name of the tag.
+    pub tagname: String,
+
+    /// The position the tag was jumped from.
+    pub from: TagStackFrom,
+
+    /// The current matching tag number, used when the name matches
+    /// multiple tags.
+    pub matchnr: u32,
+
+    /// The buffer of the current matching tag.
+    pub bufnr: Buffer,
+}
+
+/// The cursor position saved in a [`TagStackItem`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct TagStackFrom {
+    pub buffer: Buffer,
+
+    /// 1-indexed line number.
+    pub lnum: usize,
+
+    /// 1-indexed column number.
+    pub col: usize,
+
+    /// Offset for `'virtualedit'`.
+    pub off: usize,
+}
+
+/// How `Window::set_tagstack` modifies the stack.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TagStackAction {
+    /// Replace the whole stack.
+    Replace,
+
+    /// Append the entries at the end of the stack.
+    Append,
+
+    /// Remove the entries after the current one before appending.
+    Truncate,
+}
+
+/// A tag found in the tags files, as returned by `api::taglist`. See
+/// `:h taglist()` for details.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct TagInfo {
+    /// The name of the tag.
+    pub name: String,
+
+    /// The file where the tag is defined, relative to the current directory
+    /// or absolute.
+    pub filename: String,
+
+    /// The Ex command used to locate the tag in the file.
+    pub cmd: String,
+
+    /// The kind of the tag, only present if the tags file was generated by
+    /// a ctags-like tool.
+    pub kind: Option<String>,
+}
+
+impl TagStackAction {
+    #[inline]
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Replace => "r",
+            Self::Append => "a",
+            Self::Truncate => "t",
+        }
+    }
+}
+
+impl From<TagStackItem> for Object {
+    fn from(item: TagStackItem) -> Self {
+        let TagStackFrom { buffer, lnum, col, off } = item.from;
+        let from = [buffer.0 as i64, lnum as i64, col as i64, off as i64];
+
+        Dictionary::from_iter([
+            ("tagname", Object::from(item.tagname)),
+            ("from", Object::from_iter(from)),
+            ("matchnr", item.matchnr.into()),
+            ("bufnr", item.bufnr.0.into()),
+        ])
+        .into()
+    }
+}
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+/// The height of a range of text in a window, as returned by
+/// `Window::text_height`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Deserialize)]
+pub struct TextHeight {
+    /// The number of screen lines the range takes, including filler lines.
+    pub all: usize,
+
+    /// The number of diff filler and virtual lines among them.
+    pub fill: usize,
+}
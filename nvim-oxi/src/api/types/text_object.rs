@@ -0,0 +1,43 @@
+/// The kinds of text objects returned by `api::text_object_under_cursor`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TextObjectKind {
+    /// A sequence of `'iskeyword'` characters, like `iw`.
+    Word,
+
+    /// A sequence of non-blank characters, like `iW`.
+    BigWord,
+
+    /// The whole line, without the trailing newline.
+    Line,
+
+    /// The block of non-blank lines around the cursor, like `ip`.
+    Paragraph,
+
+    /// A URL starting with a scheme like `https://`.
+    Url,
+}
+
+impl TextObjectKind {
+    #[inline]
+    pub(crate) const fn as_str(&self) -> &'static str {
+        use TextObjectKind::*;
+        match self {
+            Word => "word",
+            BigWord => "WORD",
+            Line => "line",
+            Paragraph => "paragraph",
+            Url => "url",
+        }
+    }
+}
+
+/// The position of a text object in the buffer. Rows are 0-indexed, columns
+/// are 0-indexed byte offsets and the end is exclusive, as expected by
+/// `Buffer::get_text` and `Buffer::set_text`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TextRange {
+    pub start_row: usize,
+    pub start_col: usize,
+    pub end_row: usize,
+    pub end_col: usize,
+}
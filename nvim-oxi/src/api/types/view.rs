@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// The view of a window, as returned by `Window::save_view`. See
+/// `:h winsaveview()` for details.
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub struct View {
+    /// The 1-indexed cursor line.
+    pub lnum: usize,
+
+    /// The 0-indexed byte offset of the cursor in the line.
+    pub col: usize,
+
+    /// The offset of the cursor in screen cells with `'virtualedit'`.
+    pub coladd: usize,
+
+    /// The column the cursor moves to when moving vertically. Very large
+    /// when it sticks to the end of the line after `$`.
+    pub curswant: usize,
+
+    /// The first line displayed in the window.
+    pub topline: usize,
+
+    /// The number of filler lines above `topline`, in diff mode.
+    pub topfill: usize,
+
+    /// The first column displayed in the window, when `'wrap'` is off.
+    pub leftcol: usize,
+
+    /// The number of screen columns skipped in the first line, when it's
+    /// too long to be displayed and `'wrap'` is on.
+    pub skipcol: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::{dictionary::Dictionary, object::Object};
+
+    use super::*;
+    use crate::object::{FromObject, ToObject};
+
+    #[test]
+    fn round_trip() {
+        let dict = Dictionary::from_iter([
+            ("lnum", 12),
+            ("col", 4),
+            ("coladd", 0),
+            ("curswant", 2147483647),
+            ("topline", 3),
+            ("topfill", 0),
+            ("leftcol", 0),
+            ("skipcol", 0),
+        ]);
+        let view = View::from_obj(Object::from(dict)).unwrap();
+        assert_eq!(view.lnum, 12);
+        assert_eq!(view.curswant, 2147483647);
+        assert_eq!(View::from_obj(view.to_obj().unwrap()).unwrap(), view);
+    }
+}
@@ -0,0 +1,51 @@
+use nvim_types::{
+    array::Array,
+    dictionary::Dictionary,
+    error::Error,
+    object::Object,
+    string::String,
+};
+
+extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vimscript.c#L315
+    pub(super) fn nvim_call_function(
+        func: String,
+        args: Array,
+        err: *mut Error,
+    ) -> Object;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/command.c#L305
+    pub(super) fn nvim_cmd(
+        channel_id: u64,
+        cmd: *const Dictionary,
+        opts: *const Dictionary,
+        err: *mut Error,
+    ) -> String;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vimscript.c#L247
+    pub(super) fn nvim_eval(expr: String, err: *mut Error) -> Object;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vimscript.c#L50
+    pub(super) fn nvim_exec(
+        channel_id: u64,
+        src: String,
+        output: bool,
+        err: *mut Error,
+    ) -> String;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/vimscript.c#L89
+    #[cfg(feature = "neovim-0-9")]
+    pub(super) fn nvim_exec2(
+        channel_id: u64,
+        src: String,
+        opts: *const Dictionary,
+        err: *mut Error,
+    ) -> Dictionary;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/command.c#L96
+    pub(super) fn nvim_parse_cmd(
+        str: String,
+        opts: Dictionary,
+        err: *mut Error,
+    ) -> Dictionary;
+}
@@ -0,0 +1,6 @@
+mod ffi;
+pub mod opts;
+#[allow(clippy::module_inception)]
+mod vimscript;
+
+pub use vimscript::*;
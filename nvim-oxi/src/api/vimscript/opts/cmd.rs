@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use nvim_types::dictionary::Dictionary;
+
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct CmdOpts {
+    /// Whether to capture and return the output of the command.
+    pub(crate) output: bool,
+}
+
+impl CmdOpts {
+    #[inline(always)]
+    pub fn builder() -> CmdOptsBuilder {
+        CmdOptsBuilder::default()
+    }
+}
+
+impl From<CmdOpts> for Dictionary {
+    fn from(opts: CmdOpts) -> Self {
+        Self::from_iter([("output", opts.output)])
+    }
+}
+
+impl From<&CmdOpts> for Dictionary {
+    fn from(opts: &CmdOpts) -> Self {
+        opts.clone().into()
+    }
+}
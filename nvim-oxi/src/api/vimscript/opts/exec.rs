@@ -0,0 +1,28 @@
+use derive_builder::Builder;
+use nvim_types::dictionary::Dictionary;
+
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct ExecOpts {
+    /// Whether to capture and return the output of the executed commands.
+    pub(crate) output: bool,
+}
+
+impl ExecOpts {
+    #[inline(always)]
+    pub fn builder() -> ExecOptsBuilder {
+        ExecOptsBuilder::default()
+    }
+}
+
+impl From<ExecOpts> for Dictionary {
+    fn from(opts: ExecOpts) -> Self {
+        Self::from_iter([("output", opts.output)])
+    }
+}
+
+impl From<&ExecOpts> for Dictionary {
+    fn from(opts: &ExecOpts) -> Self {
+        opts.clone().into()
+    }
+}
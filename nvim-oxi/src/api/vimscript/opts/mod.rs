@@ -0,0 +1,7 @@
+mod cmd;
+mod exec;
+mod parse_cmd;
+
+pub use cmd::*;
+pub use exec::*;
+pub use parse_cmd::*;
@@ -0,0 +1,21 @@
+use derive_builder::Builder;
+use nvim_types::dictionary::Dictionary;
+
+/// Options passed to `api::parse_cmd`. Neovim doesn't define any yet, the
+/// dictionary is reserved for future use.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct ParseCmdOpts {}
+
+impl ParseCmdOpts {
+    #[inline(always)]
+    pub fn builder() -> ParseCmdOptsBuilder {
+        ParseCmdOptsBuilder::default()
+    }
+}
+
+impl From<&ParseCmdOpts> for Dictionary {
+    fn from(_: &ParseCmdOpts) -> Self {
+        Dictionary::new()
+    }
+}
@@ -0,0 +1,296 @@
+use std::ops::Range;
+use std::path::Path;
+
+use nvim_types::{
+    array::Array,
+    dictionary::Dictionary,
+    error::Error as NvimError,
+    object::Object,
+    string::String as NvimString,
+};
+
+use super::ffi::*;
+use super::opts::*;
+use crate::api::keyset;
+use crate::api::types::{CmdInfos, CmdOutput, ExecOutput, ParsedCmd};
+use crate::lua::LUA_INTERNAL_CALL;
+use crate::object::FromObject;
+use crate::{Error, Result};
+
+/// Maximum number of bytes executed at once by [`exec_stream`].
+const EXEC_STREAM_BATCH_SIZE: usize = 1 << 18;
+
+/// Binding to `nvim_call_function`.
+///
+/// Calls a Vimscript function with the given arguments, deserializing its
+/// return value into `Ret`.
+pub fn call_function<Args, Ret>(func: &str, args: Args) -> Result<Ret>
+where
+    Args: IntoIterator,
+    Args::Item: Into<Object>,
+    Ret: FromObject,
+{
+    let args =
+        Array::from(args.into_iter().map(Into::into).collect::<Vec<_>>());
+    let mut err = NvimError::new();
+    let res = unsafe { nvim_call_function(func.into(), args, &mut err) };
+    err.into_err_or_flatten(|| Ret::from_obj(res))
+        .map_err(|err| err.in_function("nvim_call_function"))
+}
+
+/// Binding to `nvim_cmd`.
+///
+/// Executes an Ex command described by `infos`, without parsing a string.
+/// The output is captured and returned if `CmdOpts::output` is set.
+pub fn cmd(infos: &CmdInfos, opts: &CmdOpts) -> Result<CmdOutput> {
+    cmd_dict(infos.into(), opts)
+}
+
+/// Like [`cmd`], with the command already converted into a dictionary.
+fn cmd_dict(infos: Dictionary, opts: &CmdOpts) -> Result<CmdOutput> {
+    let mut err = NvimError::new();
+    let out = unsafe {
+        nvim_cmd(
+            LUA_INTERNAL_CALL,
+            &keyset("cmd", infos),
+            &keyset("cmd_opts", opts),
+            &mut err,
+        )
+    };
+    err.into_err_or_flatten(|| {
+        let output = opts.output.then(|| out.into_string()).transpose()?;
+        Ok(CmdOutput { output })
+    })
+    .map_err(|err: Error| err.in_function("nvim_cmd"))
+}
+
+/// Binding to `nvim_eval`.
+///
+/// Evaluates a Vimscript expression, deserializing its value into `Ret`.
+pub fn eval<Ret>(expr: &str) -> Result<Ret>
+where
+    Ret: FromObject,
+{
+    let mut err = NvimError::new();
+    let res = unsafe { nvim_eval(expr.into(), &mut err) };
+    err.into_err_or_flatten(|| Ret::from_obj(res))
+        .map_err(|err| err.in_function("nvim_eval"))
+}
+
+/// Binding to `nvim_exec`.
+///
+/// Executes a multiline block of Ex commands. If `output` is true the
+/// output is captured and returned.
+///
+/// `nvim_exec` is deprecated since Neovim 0.9, prefer [`exec2`] when
+/// targeting newer versions.
+pub fn exec(src: &str, output: bool) -> Result<Option<String>> {
+    let mut err = NvimError::new();
+    let out =
+        unsafe { nvim_exec(LUA_INTERNAL_CALL, src.into(), output, &mut err) };
+    err.into_err_or_flatten(|| {
+        output.then(|| out.into_string()).transpose().map_err(Into::into)
+    })
+    .map_err(|err: Error| err.in_function("nvim_exec"))
+}
+
+/// Binding to `nvim_exec2`.
+///
+/// Executes a multiline block of Ex commands. The output is captured and
+/// returned if `ExecOpts::output` is set.
+///
+/// When targeting Neovim 0.8, which doesn't have `nvim_exec2`, this calls
+/// `nvim_exec` instead.
+pub fn exec2(src: &str, opts: &ExecOpts) -> Result<ExecOutput> {
+    #[cfg(not(feature = "neovim-0-9"))]
+    return exec(src, opts.output).map(|output| ExecOutput { output });
+
+    #[cfg(feature = "neovim-0-9")]
+    {
+        let mut err = NvimError::new();
+        let out = unsafe {
+            nvim_exec2(
+                LUA_INTERNAL_CALL,
+                src.into(),
+                &keyset("exec_opts", opts),
+                &mut err,
+            )
+        };
+        err.into_err_or_flatten(|| ExecOutput::from_obj(out.into()))
+            .map_err(|err| err.in_function("nvim_exec2"))
+    }
+}
+
+/// Binding to `nvim_parse_cmd`.
+///
+/// Parses a command line without executing it. The `infos` of the returned
+/// [`ParsedCmd`] can be passed to [`cmd`] to execute it.
+pub fn parse_cmd(src: &str, opts: &ParseCmdOpts) -> Result<ParsedCmd> {
+    let mut err = NvimError::new();
+    let out = unsafe { nvim_parse_cmd(src.into(), opts.into(), &mut err) };
+    err.into_err_or_flatten(|| ParsedCmd::from_obj(out.into()))
+        .map_err(|err| err.in_function("nvim_parse_cmd"))
+}
+
+/// Executes a (potentially huge) Vimscript source produced one chunk at a
+/// time, feeding the chunks to Neovim in batches.
+///
+/// Chunks are never split, but consecutive chunks can end up in the same
+/// batch, so a block statement (`if`/`endif`, `function`/`endfunction`,
+/// etc.) must not span multiple chunks. Chunks are joined by newlines, so a
+/// chunk ending in one is followed by an empty line. If a batch fails the
+/// returned `ExecStreamError` contains the 1-indexed range of lines it
+/// spanned and, if it could be determined, the line that failed.
+pub fn exec_stream<Chunks>(chunks: Chunks) -> Result<()>
+where
+    Chunks: IntoIterator<Item = String>,
+{
+    let mut batch = String::new();
+    let mut batch_start = 1;
+    let mut next_line = 1;
+
+    for chunk in chunks {
+        if next_line > batch_start
+            && batch.len() + chunk.len() > EXEC_STREAM_BATCH_SIZE
+        {
+            exec_batch(&batch, batch_start..next_line)?;
+            batch.clear();
+            batch_start = next_line;
+        }
+
+        if next_line > batch_start {
+            batch.push('\n');
+        }
+
+        batch.push_str(&chunk);
+        next_line += chunk.split('\n').count();
+    }
+
+    if next_line > batch_start {
+        exec_batch(&batch, batch_start..next_line)?;
+    }
+
+    Ok(())
+}
+
+/// The global variable the error of a failed [`exec_stream`] batch is
+/// stored in.
+const EXEC_STREAM_ERROR_VAR: &str = "nvim_oxi_exec_stream_error";
+
+/// Executes a single batch of [`exec_stream`] spanning `lines`.
+///
+/// Errors abort the execution like inside a `:try` anyway, so the batch is
+/// wrapped in one to get the line that failed from `v:throwpoint`.
+fn exec_batch(batch: &str, lines: Range<usize>) -> Result<()> {
+    let src = format!(
+        "try\n{batch}\ncatch\nlet g:{EXEC_STREAM_ERROR_VAR} = [v:exception, \
+         v:throwpoint]\nendtry"
+    );
+
+    let failed = |line, source| Error::ExecStreamError {
+        start: lines.start,
+        end: lines.end - 1,
+        line,
+        source: Box::new(source),
+    };
+
+    exec2(&src, &ExecOpts::default()).map_err(|err| failed(None, err))?;
+
+    let caught = eval::<Option<(String, String)>>(&format!(
+        "exists('g:{0}') ? remove(g:, '{0}') : v:null",
+        EXEC_STREAM_ERROR_VAR
+    ))?;
+
+    let Some((exception, throwpoint)) = caught else { return Ok(()) };
+
+    // The throwpoint ends in e.g. `, line 3`, counting the `try`.
+    let line = throwpoint
+        .rsplit_once(", line ")
+        .and_then(|(_, line)| line.trim().parse::<usize>().ok())
+        .map(|line| lines.start + line - 2);
+
+    let source = crate::NvimError {
+        kind: crate::NvimErrorKind::Exception,
+        message: exception,
+        function: Some("nvim_exec2"),
+    };
+
+    Err(failed(line, source.into()))
+}
+
+/// Runs `:source` on a Vimscript or Lua file, e.g. a companion file a
+/// plugin ships in its runtime directory (see
+/// [`get_runtime_file`](crate::api::get_runtime_file)). Use
+/// [`exec_lua`](crate::api::exec_lua) instead to get values back from Lua.
+pub fn source<P: AsRef<Path>>(path: P) -> Result<()> {
+    // `%` and `#` in the path are taken literally.
+    let infos = CmdInfos::builder()
+        .cmd("source")
+        .magic_file(false)
+        .build()
+        .expect("all fields have defaults");
+
+    // Paths aren't necessarily valid UTF-8, so the path is passed as bytes
+    // instead of through `CmdInfos::args`.
+    let path = NvimString::from(path.as_ref().as_os_str().to_owned());
+    let mut infos = Dictionary::from(&infos);
+    infos.insert("args", Object::from_iter([path]));
+
+    cmd_dict(infos, &CmdOpts::default()).map(|_| ())
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+
+    #[nvim_oxi::test]
+    fn call_function_keeps_nil_arguments() -> Result<()> {
+        // `nil`s are passed as `v:null`, whose type is 7.
+        let list = Array::from(vec![Object::nil(), 1.into(), Object::nil()]);
+        let types: Vec<u32> =
+            call_function("map", [Object::from(list), "type(v:val)".into()])?;
+        assert_eq!(types, [7, 0, 7]);
+
+        let ty: u32 = call_function("type", [Object::nil()])?;
+        assert_eq!(ty, 7);
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn exec_stream_reports_failing_line() -> Result<()> {
+        let chunks = ["let g:a = 1\n", "let g:b = 2", "call Nope()"];
+        let err = exec_stream(chunks.map(String::from)).unwrap_err();
+
+        // The first chunk ends in a newline, so it spans lines 1 and 2.
+        let Error::ExecStreamError { start, end, line, .. } = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!((start, end, line), (1, 4, Some(4)));
+
+        let b: u32 = crate::api::get_var("b")?;
+        assert_eq!(b, 2);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[nvim_oxi::test]
+    fn source_non_utf8_path() -> Result<()> {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        use std::{env, fs};
+
+        let name = OsStr::from_bytes(b"nvim-oxi-source-\xff%.vim");
+        let path = env::temp_dir().join(name);
+        fs::write(&path, "let g:oxi_sourced = 1")?;
+
+        source(&path)?;
+        fs::remove_file(&path)?;
+
+        let sourced: u32 = crate::api::get_var("oxi_sourced")?;
+        assert_eq!(sourced, 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use nvim_types::{array::Array, object::Object};
+
+/// The border of a floating window, see the `border` field of
+/// `:h nvim_open_win()`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Border {
+    /// No border.
+    None,
+
+    /// A single line box.
+    Single,
+
+    /// A double line box.
+    Double,
+
+    /// Like [`Single`](Border::Single), but with rounded corners.
+    Rounded,
+
+    /// Padding by an extra cell around the window.
+    Solid,
+
+    /// A drop shadow effect, blending with the background.
+    Shadow,
+
+    /// The characters of the border, clockwise starting from the top-left
+    /// corner.
+    Custom(Box<[BorderChar; 8]>),
+}
+
+/// A character of a [`Border::Custom`], with the highlight group it's drawn
+/// with.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BorderChar {
+    /// The character, or `None` to leave out that side or corner of the
+    /// border.
+    pub char: Option<char>,
+
+    /// Defaults to `FloatBorder` if not set.
+    pub hl_group: Option<String>,
+}
+
+impl BorderChar {
+    #[inline]
+    pub fn new(char: char) -> Self {
+        Self { char: Some(char), hl_group: None }
+    }
+
+    /// A border character drawn with `hl_group`.
+    #[inline]
+    pub fn with_hl(char: char, hl_group: impl Into<String>) -> Self {
+        Self { char: Some(char), hl_group: Some(hl_group.into()) }
+    }
+}
+
+impl From<char> for BorderChar {
+    #[inline]
+    fn from(char: char) -> Self {
+        Self::new(char)
+    }
+}
+
+impl From<&BorderChar> for Object {
+    fn from(ch: &BorderChar) -> Self {
+        let char = ch.char.map(String::from).unwrap_or_default();
+        match &ch.hl_group {
+            Some(hl_group) => {
+                Array::from_iter([char, hl_group.clone()]).into()
+            },
+            None => char.into(),
+        }
+    }
+}
+
+impl From<&Border> for Object {
+    fn from(border: &Border) -> Self {
+        use Border::*;
+        match border {
+            None => "none".into(),
+            Single => "single".into(),
+            Double => "double".into(),
+            Rounded => "rounded".into(),
+            Solid => "solid".into(),
+            Shadow => "shadow".into(),
+            Custom(chars) => chars.iter().map(Object::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use nvim_types::{dictionary::Dictionary, error::Error, BufHandle, WinHandle};
+
+extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/win_config.c#L159
+    pub(super) fn nvim_open_win(
+        buf: BufHandle,
+        enter: bool,
+        config: *const Dictionary,
+        err: *mut Error,
+    ) -> WinHandle;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/win_config.c#L202
+    pub(super) fn nvim_win_set_config(
+        win: WinHandle,
+        config: *const Dictionary,
+        err: *mut Error,
+    );
+}
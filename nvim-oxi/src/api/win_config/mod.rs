@@ -0,0 +1,9 @@
+mod border;
+mod ffi;
+#[allow(clippy::module_inception)]
+mod win_config;
+mod window_config;
+
+pub use border::{Border, BorderChar};
+pub use win_config::*;
+pub use window_config::*;
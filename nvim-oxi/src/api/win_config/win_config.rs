@@ -0,0 +1,34 @@
+use nvim_types::error::Error as NvimError;
+
+use super::ffi::*;
+use super::WindowConfig;
+use crate::api::{keyset, Buffer, Window};
+use crate::Result;
+
+/// Binding to `nvim_open_win`.
+///
+/// Opens a new window displaying `buf`, either floating or split depending
+/// on `config`, and enters it if `enter` is set.
+pub fn open_win(
+    buf: &Buffer,
+    enter: bool,
+    config: &WindowConfig,
+) -> Result<Window> {
+    let config = keyset("float_config", config);
+    let mut err = NvimError::new();
+    let handle = unsafe { nvim_open_win(buf.0, enter, &config, &mut err) };
+    err.into_err_or_else(|| handle.into())
+}
+
+impl Window {
+    /// Binding to `nvim_win_set_config`.
+    ///
+    /// Changes the configuration of the window, e.g. to move or resize a
+    /// floating window. Fields that aren't set are left unchanged.
+    pub fn set_config(&mut self, config: &WindowConfig) -> Result<()> {
+        let config = keyset("float_config", config);
+        let mut err = NvimError::new();
+        unsafe { nvim_win_set_config(self.0, &config, &mut err) };
+        err.into_err_or_else(|| ())
+    }
+}
@@ -0,0 +1,353 @@
+use derive_builder::Builder;
+use nvim_types::{array::Array, dictionary::Dictionary, object::Object};
+
+use super::Border;
+use crate::api::{self, Window};
+use crate::Result;
+
+/// The configuration of a window, passed to `api::open_win` and
+/// `Window::set_config`. See `:h nvim_open_win()` for details.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct WindowConfig {
+    /// What the position of a floating window is relative to. Windows
+    /// without it are opened as splits.
+    #[builder(setter(strip_option))]
+    relative: Option<WindowRelativeTo>,
+
+    /// Which corner of the window is placed at `row`, `col`.
+    #[builder(setter(strip_option))]
+    anchor: Option<WindowAnchor>,
+
+    #[builder(setter(strip_option))]
+    width: Option<u32>,
+
+    #[builder(setter(strip_option))]
+    height: Option<u32>,
+
+    /// Row of the anchor, in screen cells. Can be fractional.
+    #[builder(setter(strip_option))]
+    row: Option<f64>,
+
+    /// Column of the anchor, in screen cells. Can be fractional.
+    #[builder(setter(strip_option))]
+    col: Option<f64>,
+
+    /// Places the window next to a (0,0)-indexed position in the buffer of
+    /// the window it's relative to. Only with
+    /// [`WindowRelativeTo::Window`].
+    #[builder(setter(strip_option))]
+    bufpos: Option<(usize, usize)>,
+
+    /// Whether the window can be entered by user actions like `<C-w>w`.
+    #[builder(setter(strip_option))]
+    focusable: Option<bool>,
+
+    /// Whether to display the window as an external top-level window. Only
+    /// supported by some UIs.
+    #[builder(setter(strip_option))]
+    external: Option<bool>,
+
+    /// Stacking order of floating windows, higher on top. Defaults to 50.
+    #[builder(setter(strip_option))]
+    zindex: Option<u32>,
+
+    #[builder(setter(strip_option))]
+    style: Option<WindowStyle>,
+
+    #[builder(setter(strip_option))]
+    border: Option<Border>,
+
+    /// Title shown in the border of the window. Only with a border.
+    #[cfg(feature = "neovim-0-9")]
+    #[builder(setter(into, strip_option))]
+    title: Option<WindowTitle>,
+
+    #[cfg(feature = "neovim-0-9")]
+    #[builder(setter(strip_option))]
+    title_pos: Option<WindowTitlePosition>,
+
+    /// Footer shown in the bottom border of the window. Only with a border.
+    #[cfg(feature = "neovim-nightly")]
+    #[builder(setter(into, strip_option))]
+    footer: Option<WindowTitle>,
+
+    #[cfg(feature = "neovim-nightly")]
+    #[builder(setter(strip_option))]
+    footer_pos: Option<WindowTitlePosition>,
+
+    /// Don't trigger any autocommand while opening the window. Only used
+    /// by `api::open_win`.
+    noautocmd: bool,
+}
+
+impl WindowConfig {
+    #[inline(always)]
+    pub fn builder() -> WindowConfigBuilder {
+        WindowConfigBuilder::default()
+    }
+
+    /// Returns a builder for a floating window placed `row` lines below
+    /// and `col` columns to the right of the cursor, e.g. `(1, 0)` for a
+    /// tooltip right under it. Negative offsets move it up or left.
+    pub fn relative_cursor(row: i32, col: i32) -> WindowConfigBuilder {
+        let mut builder = Self::builder();
+        builder
+            .relative(WindowRelativeTo::Cursor)
+            .row(row.into())
+            .col(col.into());
+        builder
+    }
+
+    /// Returns a builder for a floating window whose top-left corner is
+    /// at the mouse position.
+    pub fn relative_mouse() -> WindowConfigBuilder {
+        let mut builder = Self::builder();
+        builder.relative(WindowRelativeTo::Mouse).row(0.0).col(0.0);
+        builder
+    }
+}
+
+impl WindowConfigBuilder {
+    /// Sets the width and height of the window to fit `lines`, measuring
+    /// them in screen cells with `api::strwidth` so that multibyte and
+    /// wide characters are accounted for. The width is capped to
+    /// `max_width`, and lines longer than that count for as many rows as
+    /// they take when wrapped.
+    pub fn fit_to_text<Line: AsRef<str>>(
+        &mut self,
+        lines: &[Line],
+        max_width: u32,
+    ) -> Result<&mut Self> {
+        let widths = lines
+            .iter()
+            .map(|line| api::strwidth(line.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        let (width, height) = fit(&widths, max_width);
+        Ok(self.width(width).height(height))
+    }
+}
+
+/// Returns the width and height of a window displaying lines of the given
+/// widths, at most `max_width` columns wide. Both are at least 1, which is
+/// the minimum Neovim accepts.
+fn fit(widths: &[usize], max_width: u32) -> (u32, u32) {
+    let max_width = max_width.max(1) as usize;
+    let width = widths.iter().copied().max().unwrap_or(0).clamp(1, max_width);
+    let height = widths
+        .iter()
+        .map(|&line_width| line_width.div_ceil(width).max(1))
+        .sum::<usize>()
+        .max(1);
+    (width as u32, u32::try_from(height).unwrap_or(u32::MAX))
+}
+
+/// What the position of a floating window is relative to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WindowRelativeTo {
+    /// The global editor grid.
+    Editor,
+
+    /// A window, whose text area is used.
+    Window(Window),
+
+    /// The cursor position in the current window.
+    Cursor,
+
+    /// The mouse position.
+    Mouse,
+}
+
+/// Which corner of a floating window is placed at its position.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WindowAnchor {
+    NorthWest,
+    NorthEast,
+    SouthWest,
+    SouthEast,
+}
+
+impl From<WindowAnchor> for Object {
+    fn from(anchor: WindowAnchor) -> Self {
+        use WindowAnchor::*;
+        match anchor {
+            NorthWest => "NW",
+            NorthEast => "NE",
+            SouthWest => "SW",
+            SouthEast => "SE",
+        }
+        .into()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WindowStyle {
+    /// Disables the UI options like `'number'` and `'signcolumn'`, for
+    /// windows displaying temporary content.
+    Minimal,
+}
+
+impl From<WindowStyle> for Object {
+    fn from(style: WindowStyle) -> Self {
+        match style {
+            WindowStyle::Minimal => "minimal".into(),
+        }
+    }
+}
+
+/// The title or footer of a window, made of chunks of text each with an
+/// optional highlight group. Can be built from a string for a title drawn
+/// with `FloatTitle`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct WindowTitle(pub Vec<(String, Option<String>)>);
+
+impl From<&str> for WindowTitle {
+    fn from(text: &str) -> Self {
+        Self(vec![(text.to_owned(), None)])
+    }
+}
+
+impl From<String> for WindowTitle {
+    fn from(text: String) -> Self {
+        Self(vec![(text, None)])
+    }
+}
+
+impl From<Vec<(String, Option<String>)>> for WindowTitle {
+    fn from(chunks: Vec<(String, Option<String>)>) -> Self {
+        Self(chunks)
+    }
+}
+
+impl From<&WindowTitle> for Object {
+    fn from(title: &WindowTitle) -> Self {
+        title
+            .0
+            .iter()
+            .map(|(text, hl_group)| {
+                let mut chunk = Array::from_iter([text.clone()]);
+                if let Some(hl_group) = hl_group {
+                    chunk.push(hl_group.clone());
+                }
+                chunk
+            })
+            .collect()
+    }
+}
+
+/// Where the title or footer is placed in the border.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum WindowTitlePosition {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl From<WindowTitlePosition> for Object {
+    fn from(pos: WindowTitlePosition) -> Self {
+        use WindowTitlePosition::*;
+        match pos {
+            Left => "left",
+            Center => "center",
+            Right => "right",
+        }
+        .into()
+    }
+}
+
+impl From<&WindowConfig> for Dictionary {
+    fn from(config: &WindowConfig) -> Self {
+        let (relative, win) = match config.relative {
+            Some(WindowRelativeTo::Editor) => (Some("editor"), None),
+            Some(WindowRelativeTo::Window(win)) => (Some("win"), Some(win.0)),
+            Some(WindowRelativeTo::Cursor) => (Some("cursor"), None),
+            Some(WindowRelativeTo::Mouse) => (Some("mouse"), None),
+            None => (None, None),
+        };
+
+        let bufpos = config
+            .bufpos
+            .map(|(line, col)| Array::from_iter([line as i64, col as i64]));
+
+        Self::from_iter([
+            ("relative", Object::from(relative)),
+            ("win", win.into()),
+            ("anchor", config.anchor.into()),
+            ("width", config.width.into()),
+            ("height", config.height.into()),
+            ("row", config.row.into()),
+            ("col", config.col.into()),
+            ("bufpos", bufpos.into()),
+            ("focusable", config.focusable.into()),
+            ("external", config.external.into()),
+            ("zindex", config.zindex.into()),
+            ("style", config.style.into()),
+            ("border", config.border.as_ref().map(Object::from).into()),
+            #[cfg(feature = "neovim-0-9")]
+            ("title", config.title.as_ref().map(Object::from).into()),
+            #[cfg(feature = "neovim-0-9")]
+            ("title_pos", config.title_pos.into()),
+            #[cfg(feature = "neovim-nightly")]
+            ("footer", config.footer.as_ref().map(Object::from).into()),
+            #[cfg(feature = "neovim-nightly")]
+            ("footer_pos", config.footer_pos.into()),
+            // Only accepted by `nvim_open_win`, `nvim_win_set_config` fails
+            // if the key is present at all.
+            ("noautocmd", config.noautocmd.then_some(true).into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::win_config::BorderChar;
+    use crate::object::FromObject;
+
+    fn string(obj: &Object) -> String {
+        String::from_obj(obj.clone()).unwrap()
+    }
+
+    #[test]
+    fn float_config() {
+        let mut chars: [BorderChar; 8] = Default::default();
+        chars[1] = BorderChar::with_hl('─', "Comment");
+        chars[3] = '│'.into();
+
+        let config = WindowConfig::builder()
+            .relative(WindowRelativeTo::Editor)
+            .anchor(WindowAnchor::SouthEast)
+            .width(10)
+            .border(Border::Custom(Box::new(chars)))
+            .build()
+            .unwrap();
+
+        let dict = Dictionary::from(&config);
+        assert_eq!(string(&dict["relative"]), "editor");
+        assert_eq!(string(&dict["anchor"]), "SE");
+        assert!(!dict.contains_key("win"));
+        assert!(!dict.contains_key("noautocmd"));
+
+        let border = Array::from_obj(dict["border"].clone()).unwrap();
+        assert_eq!(border.len(), 8);
+        assert_eq!(string(&border.as_slice()[0]), "");
+        assert_eq!(
+            Vec::<String>::from_obj(border.as_slice()[1].clone()).unwrap(),
+            ["─", "Comment"]
+        );
+        assert_eq!(string(&border.as_slice()[3]), "│");
+    }
+
+    #[test]
+    fn fit_to_widths() {
+        assert_eq!(fit(&[3, 8, 0], 20), (8, 3));
+        assert_eq!(fit(&[25, 4], 10), (10, 4));
+        assert_eq!(fit(&[], 10), (1, 1));
+    }
+
+    #[test]
+    fn preset_border() {
+        assert_eq!(string(&Object::from(&Border::Rounded)), "rounded");
+    }
+}
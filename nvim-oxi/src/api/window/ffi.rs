@@ -0,0 +1,55 @@
+use nvim_types::{
+    array::Array,
+    error::Error,
+    object::Object,
+    BufHandle,
+    Integer,
+    LuaRef,
+    WinHandle,
+};
+
+extern "C" {
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c#L471
+    pub(super) fn nvim_win_call(
+        win: WinHandle,
+        fun: LuaRef,
+        err: *mut Error,
+    ) -> Object;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c#L30
+    pub(super) fn nvim_win_get_buf(
+        win: WinHandle,
+        err: *mut Error,
+    ) -> BufHandle;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c#L102
+    pub(super) fn nvim_win_get_cursor(
+        win: WinHandle,
+        err: *mut Error,
+    ) -> Array;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c#L394
+    pub(super) fn nvim_win_is_valid(win: WinHandle) -> bool;
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c#L118
+    pub(super) fn nvim_win_set_cursor(
+        win: WinHandle,
+        pos: Array,
+        err: *mut Error,
+    );
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c#L441
+    pub(super) fn nvim_win_set_hl_ns(
+        win: WinHandle,
+        ns_id: Integer,
+        err: *mut Error,
+    );
+
+    // https://github.com/neovim/neovim/blob/master/src/nvim/api/window.c#L542
+    #[cfg(feature = "neovim-nightly")]
+    pub(super) fn nvim_win_text_height(
+        win: WinHandle,
+        opts: *const nvim_types::dictionary::Dictionary,
+        err: *mut Error,
+    ) -> nvim_types::dictionary::Dictionary;
+}
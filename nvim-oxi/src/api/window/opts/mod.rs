@@ -0,0 +1,5 @@
+#[cfg(feature = "neovim-nightly")]
+mod text_height;
+
+#[cfg(feature = "neovim-nightly")]
+pub use text_height::*;
@@ -0,0 +1,47 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object, Integer};
+
+/// Options passed to `Window::text_height`. Without any, the height of the
+/// whole buffer is computed.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct TextHeightOpts {
+    /// The 0-indexed first line of the range. Defaults to the first line
+    /// of the buffer.
+    #[builder(setter(strip_option))]
+    start_row: Option<usize>,
+
+    /// The 0-indexed last line of the range, inclusive. Defaults to the
+    /// last line of the buffer.
+    #[builder(setter(strip_option))]
+    end_row: Option<usize>,
+
+    /// Starting virtual column of the first line, whose screen lines before
+    /// it aren't counted.
+    #[builder(setter(strip_option))]
+    start_vcol: Option<usize>,
+
+    /// Ending virtual column of the last line, whose screen lines after it
+    /// aren't counted.
+    #[builder(setter(strip_option))]
+    end_vcol: Option<usize>,
+}
+
+impl TextHeightOpts {
+    #[inline(always)]
+    pub fn builder() -> TextHeightOptsBuilder {
+        TextHeightOptsBuilder::default()
+    }
+}
+
+impl From<&TextHeightOpts> for Dictionary {
+    fn from(opts: &TextHeightOpts) -> Self {
+        let int = |n: Option<usize>| n.and_then(|n| Integer::try_from(n).ok());
+        Self::from_iter([
+            ("start_row", Object::from(int(opts.start_row))),
+            ("end_row", int(opts.end_row).into()),
+            ("start_vcol", int(opts.start_vcol).into()),
+            ("end_vcol", int(opts.end_vcol).into()),
+        ])
+    }
+}
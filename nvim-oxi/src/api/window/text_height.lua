@@ -0,0 +1,39 @@
+local win, start_row, end_row = ...
+
+if end_row <= start_row then
+  return 0
+end
+
+-- Added in Neovim 0.10, also accounts for folds, virtual lines and
+-- 'linebreak'.
+if vim.api.nvim_win_text_height then
+  local height = vim.api.nvim_win_text_height(win, {
+    start_row = start_row,
+    end_row = end_row - 1,
+  })
+  return height.all
+end
+
+local buf = vim.api.nvim_win_get_buf(win)
+local lines = vim.api.nvim_buf_get_lines(buf, start_row, end_row, true)
+
+if not vim.wo[win].wrap then
+  return #lines
+end
+
+local info = vim.fn.getwininfo(win)[1]
+local width = info.width - info.textoff
+
+if width <= 0 then
+  return #lines
+end
+
+-- `strdisplaywidth` uses the 'tabstop' of the current buffer.
+return vim.api.nvim_win_call(win, function()
+  local rows = 0
+  for _, line in ipairs(lines) do
+    local cells = vim.fn.strdisplaywidth(line)
+    rows = rows + math.max(1, math.ceil(cells / width))
+  end
+  return rows
+end)
@@ -0,0 +1,309 @@
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use nvim_types::{
+    array::Array,
+    dictionary::Dictionary,
+    error::Error as NvimError,
+    object::Object,
+    Integer,
+    WinHandle,
+};
+use serde::Deserialize;
+
+use super::ffi::*;
+use crate::api::types::{
+    Namespace,
+    Position,
+    TagStack,
+    TagStackAction,
+    TagStackItem,
+    View,
+};
+use crate::api::{
+    call_function,
+    eval,
+    exec_lua,
+    require_termguicolors,
+    Buffer,
+};
+use crate::lua;
+use crate::object::{FromObject, ToObject};
+use crate::options::WindowOptions;
+use crate::{Error, Result};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct Window(pub(crate) WinHandle);
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Window({})", self.0)
+    }
+}
+
+impl<H: Into<WinHandle>> From<H> for Window {
+    fn from(handle: H) -> Self {
+        Window(handle.into())
+    }
+}
+
+impl FromStr for Window {
+    type Err = crate::Error;
+
+    /// Parses a window from either `{id}`, `:{id}` or `Window({id})`.
+    fn from_str(s: &str) -> Result<Self> {
+        crate::api::handle::parse(s, "Window").map(Self)
+    }
+}
+
+impl Window {
+    /// Shorthand for `nvim_oxi::api::get_current_win`.
+    #[inline(always)]
+    pub fn current() -> Self {
+        crate::api::get_current_win()
+    }
+
+    /// Creates a window from a Vimscript window id. Unlike `Window::from`,
+    /// window id `0` is resolved to the current window. Fails if the id
+    /// doesn't fit in a [`WinHandle`].
+    pub fn from_number(winid: u32) -> Result<Self> {
+        match winid {
+            0 => Ok(Self::current()),
+            n => Ok(Self::from(WinHandle::try_from(n)?)),
+        }
+    }
+
+    /// Returns a handle to the window's local options, whose `get`/`set`
+    /// act on this window instead of the current one.
+    #[inline(always)]
+    pub fn options(&self) -> WindowOptions {
+        WindowOptions(*self)
+    }
+
+    /// Returns the transparency of the window, from 0 (opaque) to 100
+    /// (fully transparent).
+    pub fn get_blend(&self) -> Result<u8> {
+        self.options().get("winblend")
+    }
+
+    /// Sets the transparency of the window, from 0 (opaque) to 100 (fully
+    /// transparent). Values above 100 are clamped. Only floating windows
+    /// can be transparent.
+    ///
+    /// Fails if `'termguicolors'` is off, in which case the option would
+    /// have no effect.
+    pub fn set_blend(&self, blend: u8) -> Result<()> {
+        require_termguicolors("winblend")?;
+        self.options().set("winblend", blend.min(100))
+    }
+
+    /// Binding to `nvim_win_call`.
+    ///
+    /// Calls a closure with the window as the temporary current window,
+    /// returning its output. Window-local state like `winsaveview()`, `w:`
+    /// variables or `winline()` is then read from this window:
+    ///
+    /// ```ignore
+    /// let view = win.call(|()| api::call_function::<_, Dictionary>(
+    ///     "winsaveview",
+    ///     [] as [Object; 0],
+    /// ))?;
+    /// ```
+    pub fn call<F, R>(&self, fun: F) -> Result<R>
+    where
+        F: FnOnce(()) -> Result<R> + 'static,
+        R: 'static,
+    {
+        let (fun, output) = lua::fn_once_with_output(fun);
+        let mut err = NvimError::new();
+        unsafe { nvim_win_call(self.0, fun.0, &mut err) };
+        fun.unref();
+        err.into_err_or_flatten(|| output.take().expect("fun was called"))
+    }
+
+    /// Evaluates a Vimscript expression with the window as the temporary
+    /// current window, e.g. `winline()` or `&l:wrap`.
+    pub fn eval<Ret>(&self, expr: &str) -> Result<Ret>
+    where
+        Ret: FromObject + 'static,
+    {
+        let expr = expr.to_owned();
+        self.call(move |()| eval(&expr))
+    }
+
+    /// Saves the view of the window, i.e. its cursor position and scroll
+    /// state, to be restored with [`restore_view`](Window::restore_view)
+    /// after rewriting the contents of its buffer.
+    pub fn save_view(&self) -> Result<View> {
+        self.call(|()| call_function("winsaveview", [] as [Object; 0]))
+    }
+
+    /// Restores a view saved with [`save_view`](Window::save_view). Lines
+    /// and columns past the end of the buffer are clamped.
+    pub fn restore_view(&self, view: &View) -> Result<()> {
+        let view = view.to_obj()?;
+        self.call(move |()| {
+            call_function::<_, Object>("winrestview", [view]).map(|_| ())
+        })
+    }
+
+    /// Returns the window's tag stack. See `:h gettagstack()` for details.
+    pub fn get_tagstack(&self) -> Result<TagStack> {
+        call_function("gettagstack", [self.0])
+    }
+
+    /// Modifies the window's tag stack, setting the current index to the
+    /// end of the stack. See `:h settagstack()` for details.
+    pub fn set_tagstack<Items>(
+        &self,
+        items: Items,
+        action: TagStackAction,
+    ) -> Result<()>
+    where
+        Items: IntoIterator<Item = TagStackItem>,
+    {
+        let items = items.into_iter().collect::<Object>();
+        let dict = Dictionary::from_iter([("items", items)]);
+
+        let failed = call_function::<_, i64>(
+            "settagstack",
+            [Object::from(self.0), dict.into(), action.as_str().into()],
+        )?;
+
+        match failed {
+            0 => Ok(()),
+            _ => Err(Error::SetTagStackError(*self)),
+        }
+    }
+
+    /// Binding to `nvim_win_get_buf`.
+    ///
+    /// Gets the current buffer in the window.
+    pub fn get_buf(&self) -> Result<Buffer> {
+        let mut err = NvimError::new();
+        let handle = unsafe { nvim_win_get_buf(self.0, &mut err) };
+        err.into_err_or_else(|| handle.into())
+    }
+
+    /// Binding to `nvim_win_get_cursor`.
+    ///
+    /// Gets the (1,0)-indexed cursor position in the window.
+    pub fn get_cursor(&self) -> Result<Position> {
+        let mut err = NvimError::new();
+        let pos = unsafe { nvim_win_get_cursor(self.0, &mut err) };
+        err.into_err_or_flatten(|| Position::from_obj(pos.into()))
+    }
+
+    /// Returns the number of screen rows taken by the buffer line `line`
+    /// (0-indexed) when displayed in the window, i.e. more than one if it's
+    /// soft-wrapped.
+    pub fn wrapped_height_of_line(&self, line: usize) -> Result<usize> {
+        self.screen_rows_for_range(line..line + 1)
+    }
+
+    /// Returns the number of screen rows taken by the buffer lines in
+    /// `range` (0-indexed, end-exclusive) when displayed in the window.
+    ///
+    /// Uses `nvim_win_text_height` when available (Neovim 0.10+). On older
+    /// versions it's computed from the width of the window and `'wrap'`,
+    /// ignoring folds, virtual lines and `'linebreak'`.
+    pub fn screen_rows_for_range(&self, range: Range<usize>) -> Result<usize> {
+        exec_lua(
+            include_str!("./text_height.lua"),
+            [
+                Object::from(self.0),
+                Object::from(Integer::try_from(range.start)?),
+                Object::from(Integer::try_from(range.end)?),
+            ],
+        )
+    }
+
+    /// Binding to `nvim_win_is_valid`.
+    ///
+    /// Checks if a window is valid.
+    pub fn is_valid(&self) -> bool {
+        unsafe { nvim_win_is_valid(self.0) }
+    }
+
+    /// Binding to `nvim_win_set_cursor`.
+    ///
+    /// Sets the (1,0)-indexed cursor position in the window. Fails if the
+    /// row is past the end of the buffer, while a column past the end of
+    /// the line moves the cursor to the end of the line.
+    pub fn set_cursor(&mut self, pos: impl Into<Position>) -> Result<()> {
+        let pos = pos.into();
+        let pos = Array::from_iter([
+            Object::from(Integer::try_from(pos.row)?),
+            Object::from(Integer::try_from(pos.col)?),
+        ]);
+        let mut err = NvimError::new();
+        unsafe { nvim_win_set_cursor(self.0, pos, &mut err) };
+        err.into_err_or_else(|| ())
+    }
+
+    /// Like [`set_cursor`](Window::set_cursor), but clamps the row between
+    /// the first and the last line of the buffer and the column to the
+    /// length of the line instead of failing. Returns the position the
+    /// cursor was moved to.
+    pub fn set_cursor_clamped(
+        &mut self,
+        pos: impl Into<Position>,
+    ) -> Result<Position> {
+        let Position { row, col } = pos.into();
+        let buf = self.get_buf()?;
+        let row = row.clamp(1, buf.line_count()?.max(1));
+        let len = buf
+            .get_lines(row - 1, row, true)?
+            .next()
+            .map_or(0, |line| line.as_bytes().len());
+        let pos = Position::new(row, col.min(len.saturating_sub(1)));
+        self.set_cursor(pos)?;
+        Ok(pos)
+    }
+
+    /// Binding to `nvim_win_set_hl_ns`.
+    ///
+    /// Sets the highlight namespace of the window, whose highlight groups
+    /// take precedence over the ones of the global namespace.
+    pub fn set_hl_ns(&mut self, ns: Namespace) -> Result<()> {
+        let mut err = NvimError::new();
+        unsafe { nvim_win_set_hl_ns(self.0, ns.into(), &mut err) };
+        err.into_err_or_else(|| ())
+    }
+
+    /// Binding to `nvim_win_text_height`.
+    ///
+    /// Computes the number of screen lines a range of text takes in the
+    /// window, accounting for wrapping, folds, diff filler lines and
+    /// virtual lines. See also
+    /// [`screen_rows_for_range`](Window::screen_rows_for_range), which
+    /// falls back to an approximation on older versions of Neovim.
+    #[cfg(feature = "neovim-nightly")]
+    pub fn text_height(
+        &self,
+        opts: &super::opts::TextHeightOpts,
+    ) -> Result<crate::api::types::TextHeight> {
+        let mut err = NvimError::new();
+        let dict = unsafe {
+            nvim_win_text_height(
+                self.0,
+                &crate::api::keyset("win_text_height", opts),
+                &mut err,
+            )
+        };
+        err.into_err_or_flatten(|| FromObject::from_obj(dict.into()))
+    }
+}
+
+/// Binding to `nvim_win_call`.
+///
+/// Same as [`Window::call`].
+#[inline(always)]
+pub fn win_call<F, R>(win: Window, fun: F) -> Result<R>
+where
+    F: FnOnce(()) -> Result<R> + 'static,
+    R: 'static,
+{
+    win.call(fun)
+}
@@ -0,0 +1,181 @@
+//! Cooperative time budgeting for expensive callbacks.
+//!
+//! Decoration providers and other callbacks run synchronously on the main
+//! thread, so doing too much work in them makes the editor stutter. A
+//! [`FrameBudget`] lets them check how much time they've spent and defer
+//! the rest of their work to later iterations of the event loop.
+//!
+//! ```ignore
+//! use nvim_oxi::budget::FrameBudget;
+//!
+//! let budget = FrameBudget::new(4);
+//!
+//! for line in topline..botline {
+//!     if budget.exhausted() {
+//!         nvim_oxi::schedule(move |()| { /* finish the job */ Ok(()) });
+//!         break;
+//!     }
+//!     highlight_line(line)?;
+//! }
+//! ```
+
+use std::fmt;
+use std::iter::Peekable;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::Result;
+
+/// What happened during a frame, passed to the hook registered with
+/// [`FrameBudget::on_frame_end`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FrameReport {
+    /// The duration of the budget.
+    pub budget: Duration,
+
+    /// The time spent in the frame.
+    pub elapsed: Duration,
+
+    /// The number of items processed in the frame.
+    pub processed: usize,
+
+    /// Whether some items were left for a later frame.
+    pub deferred: bool,
+}
+
+type FrameEndHook = Rc<dyn Fn(&FrameReport)>;
+
+/// A time budget starting from when it's created or [`reset`].
+///
+/// [`reset`]: FrameBudget::reset
+#[derive(Clone)]
+pub struct FrameBudget {
+    budget: Duration,
+    start: Instant,
+    on_frame_end: Option<FrameEndHook>,
+}
+
+impl fmt::Debug for FrameBudget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FrameBudget")
+            .field("budget", &self.budget)
+            .field("start", &self.start)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FrameBudget {
+    /// Creates a budget of `ms` milliseconds starting now.
+    pub fn new(ms: u64) -> Self {
+        Self {
+            budget: Duration::from_millis(ms),
+            start: Instant::now(),
+            on_frame_end: None,
+        }
+    }
+
+    /// Registers a hook called at the end of every frame run by
+    /// [`process`](FrameBudget::process), e.g. to log how long the frames
+    /// take or how often work has to be deferred.
+    pub fn on_frame_end<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&FrameReport) + 'static,
+    {
+        self.on_frame_end = Some(Rc::new(hook));
+        self
+    }
+
+    /// Restarts the budget from now.
+    pub fn reset(&mut self) {
+        self.start = Instant::now();
+    }
+
+    /// The time spent since the budget started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// The time left before the budget is exhausted.
+    pub fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.elapsed())
+    }
+
+    /// Whether the budget has been used up.
+    pub fn exhausted(&self) -> bool {
+        self.elapsed() >= self.budget
+    }
+
+    /// Calls `fun` on each item until the budget is exhausted, then
+    /// schedules the remaining items to be processed in the next iteration
+    /// of the event loop with a fresh budget of the same duration, and so on
+    /// until there are none left.
+    ///
+    /// At least one item is processed in every frame. If `fun` fails in a
+    /// scheduled frame the remaining items are dropped and the error is
+    /// reported by Neovim.
+    pub fn process<I, F>(&self, items: I, fun: F) -> Result<()>
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        F: FnMut(I::Item) -> Result<()> + 'static,
+    {
+        run_frame(self.clone(), items.into_iter().peekable(), fun)
+    }
+
+    fn report(&self, processed: usize, deferred: bool) {
+        if let Some(hook) = &self.on_frame_end {
+            hook(&FrameReport {
+                budget: self.budget,
+                elapsed: self.elapsed(),
+                processed,
+                deferred,
+            });
+        }
+    }
+}
+
+fn run_frame<I, F>(
+    budget: FrameBudget,
+    mut items: Peekable<I>,
+    mut fun: F,
+) -> Result<()>
+where
+    I: Iterator + 'static,
+    F: FnMut(I::Item) -> Result<()> + 'static,
+{
+    let mut processed = 0;
+
+    while let Some(item) = items.next() {
+        fun(item)?;
+        processed += 1;
+
+        if budget.exhausted() && items.peek().is_some() {
+            budget.report(processed, true);
+
+            let mut budget = budget;
+            crate::schedule(move |()| {
+                budget.reset();
+                run_frame(budget, items, fun)
+            });
+
+            return Ok(());
+        }
+    }
+
+    budget.report(processed, false);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exhausted() {
+        assert!(FrameBudget::new(0).exhausted());
+
+        let budget = FrameBudget::new(60_000);
+        assert!(!budget.exhausted());
+        assert!(budget.remaining() > Duration::ZERO);
+    }
+}
@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+/// A completion candidate, see `:h complete-items`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize)]
+pub struct CompletionItem {
+    /// The text that will be inserted.
+    pub word: String,
+
+    /// Displayed in the menu instead of `word`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abbr: Option<String>,
+
+    /// A single letter describing the kind of completion, e.g. `f` for
+    /// functions or `v` for variables.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+
+    /// Extra text displayed in the menu after `word` or `abbr`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub menu: Option<String>,
+
+    /// More information about the item, displayed in the preview window.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub info: Option<String>,
+
+    /// Arbitrary data available in `v:completed_item` after the item has
+    /// been inserted, e.g. from a `CompleteDone` autocommand.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_data: Option<String>,
+}
+
+impl CompletionItem {
+    pub fn new<Word: Into<String>>(word: Word) -> Self {
+        Self { word: word.into(), ..Default::default() }
+    }
+}
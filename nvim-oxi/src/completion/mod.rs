@@ -0,0 +1,247 @@
+//! Completion sources written in Rust.
+//!
+//! A [`CompletionSource`] wraps a function returning the
+//! [`CompletionItem`]s matching the text before the cursor. It can be used
+//! as a buffer's `'omnifunc'` or `'completefunc'`, or it can open the
+//! popup menu directly with [`trigger`](CompletionSource::trigger).
+//!
+//! ```ignore
+//! use nvim_oxi::completion::{CompletionItem, CompletionSource};
+//!
+//! let source = CompletionSource::new(|base| {
+//!     Ok(["foo", "foobar"]
+//!         .into_iter()
+//!         .filter(|word| word.starts_with(base))
+//!         .map(CompletionItem::new)
+//!         .collect())
+//! });
+//!
+//! source.set_omnifunc(Buffer::current())?;
+//! ```
+
+mod item;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub use item::*;
+use serde::Serialize;
+
+use crate::api::opts::OptionValueOpts;
+use crate::api::{self, Buffer, Window};
+use crate::lua::{self, LuaFnMut};
+use crate::object::ToObject;
+use crate::Result;
+
+/// The Lua table storing the completion functions.
+const COMPLETE_FNS: &str = "_nvim_oxi_completion";
+
+/// Arguments passed to an `'omnifunc'` or a `'completefunc'`.
+pub(crate) type CompleteFnArgs = (i64, String);
+
+type FindStart = Box<dyn FnMut(&str, usize) -> Option<usize>>;
+
+type Complete = Box<dyn FnMut(&str) -> Result<Vec<CompletionItem>>>;
+
+struct Callbacks {
+    find_start: FindStart,
+    complete: Complete,
+}
+
+/// A completion source, see the [module-level](self) documentation. Its
+/// function is kept alive until the plugin is unloaded, even if the source
+/// is dropped.
+#[derive(Clone)]
+pub struct CompletionSource {
+    callbacks: Rc<RefCell<Callbacks>>,
+    func: String,
+}
+
+/// What an `'omnifunc'` returns, depending on the phase it's called in.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum CompleteFnOutput {
+    Start(i64),
+    Items(Vec<CompletionItem>),
+}
+
+impl CompletionSource {
+    /// Creates a source completing the keyword before the cursor, which is
+    /// passed to `complete`.
+    pub fn new<F>(complete: F) -> Self
+    where
+        F: FnMut(&str) -> Result<Vec<CompletionItem>> + 'static,
+    {
+        Self::with_find_start(keyword_start, complete)
+    }
+
+    /// Creates a source where `find_start` decides where the completed text
+    /// starts, given the current line and the byte index of the cursor in
+    /// it. Returning `None` cancels the completion.
+    pub fn with_find_start<S, F>(find_start: S, complete: F) -> Self
+    where
+        S: FnMut(&str, usize) -> Option<usize> + 'static,
+        F: FnMut(&str) -> Result<Vec<CompletionItem>> + 'static,
+    {
+        let callbacks = Rc::new(RefCell::new(Callbacks {
+            find_start: Box::new(find_start),
+            complete: Box::new(complete),
+        }));
+
+        let fun = {
+            let callbacks = Rc::clone(&callbacks);
+            LuaFnMut::<CompleteFnArgs, CompleteFnOutput>::from(
+                move |(findstart, base): CompleteFnArgs| {
+                    if findstart == 1 {
+                        let start = cursor_line()?.and_then(|line| {
+                            let start = call_find_start(&callbacks, &line)?;
+                            Some(line.offset + start)
+                        });
+                        // -3 cancels the completion without an error message.
+                        let start = start.map(|col| col as i64).unwrap_or(-3);
+                        Ok(CompleteFnOutput::Start(start))
+                    } else {
+                        let items = (callbacks.borrow_mut().complete)(&base)?;
+                        Ok(CompleteFnOutput::Items(items))
+                    }
+                },
+            )
+        };
+
+        let key = lua::add_global_fn(COMPLETE_FNS, fun.0);
+
+        Self { callbacks, func: format!("v:lua.{COMPLETE_FNS}.{key}") }
+    }
+
+    /// Sets the source as the `'omnifunc'` of `buf`, used by `i_CTRL-X_CTRL-O`.
+    pub fn set_omnifunc(&self, buf: Buffer) -> Result<()> {
+        self.set_for(buf, "omnifunc")
+    }
+
+    /// Sets the source as the `'completefunc'` of `buf`, used by
+    /// `i_CTRL-X_CTRL-U`.
+    pub fn set_completefunc(&self, buf: Buffer) -> Result<()> {
+        self.set_for(buf, "completefunc")
+    }
+
+    fn set_for(&self, buf: Buffer, option: &str) -> Result<()> {
+        let opts = OptionValueOpts::builder()
+            .buf(buf)
+            .build()
+            .expect("all fields have defaults");
+        api::set_option_value(option, self.func.as_str(), &opts)
+    }
+
+    /// Opens the popup menu with the source's items at the cursor, like
+    /// `complete()`. Only works in Insert mode, e.g. from a mapping using
+    /// `<Cmd>`.
+    pub fn trigger(&self) -> Result<()> {
+        let Some(line) = cursor_line()? else { return Ok(()) };
+        let Some(start) = call_find_start(&self.callbacks, &line) else {
+            return Ok(());
+        };
+
+        // The callbacks aren't borrowed while calling into Neovim, which
+        // could call the source again.
+        let base = &line.text[start..line.col];
+        let items = (self.callbacks.borrow_mut().complete)(base)?;
+
+        // `complete()` takes a 1-based column.
+        api::call_function::<_, i64>(
+            "complete",
+            [(line.offset + start + 1).to_obj()?, items.to_obj()?],
+        )
+        .map(|_| ())
+    }
+}
+
+/// The part of the current line the callbacks see. Lines can contain
+/// invalid UTF-8, so it's the valid stretch of the line around the cursor,
+/// starting at byte `offset` of the line.
+struct CursorLine {
+    text: String,
+
+    /// The byte index of the cursor in `text`.
+    col: usize,
+
+    offset: usize,
+}
+
+/// Returns `None` if the cursor is inside an invalid UTF-8 sequence.
+fn cursor_line() -> Result<Option<CursorLine>> {
+    let line = api::get_current_line()?;
+    let col = Window::current().get_cursor()?.col;
+
+    Ok(valid_around(line.as_bytes(), col).map(|(offset, text)| CursorLine {
+        text: text.to_owned(),
+        col: col - offset,
+        offset,
+    }))
+}
+
+/// Calls `find_start`, discarding starts that aren't before the cursor on
+/// a character boundary.
+fn call_find_start(
+    callbacks: &RefCell<Callbacks>,
+    line: &CursorLine,
+) -> Option<usize> {
+    let start = (callbacks.borrow_mut().find_start)(&line.text, line.col)?;
+    (start <= line.col && line.text.is_char_boundary(start)).then_some(start)
+}
+
+/// Returns the longest valid UTF-8 stretch of `bytes` containing the byte
+/// index `col`, together with the index it starts at.
+fn valid_around(bytes: &[u8], col: usize) -> Option<(usize, &str)> {
+    let mut offset = 0;
+
+    for chunk in bytes.utf8_chunks() {
+        if col < offset {
+            return None;
+        }
+        let valid = chunk.valid();
+        if col <= offset + valid.len() {
+            return Some((offset, valid));
+        }
+        offset += valid.len() + chunk.invalid().len();
+    }
+
+    (col == offset).then_some((offset, ""))
+}
+
+/// Returns the start of the word made of alphanumeric characters and
+/// underscores ending at `col`.
+fn keyword_start(line: &str, col: usize) -> Option<usize> {
+    let before = line.get(..col)?;
+    let len = before
+        .chars()
+        .rev()
+        .take_while(|ch| ch.is_alphanumeric() || *ch == '_')
+        .map(char::len_utf8)
+        .sum::<usize>();
+    Some(col - len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyword_start_before_cursor() {
+        assert_eq!(keyword_start("let foo_bar", 11), Some(4));
+        assert_eq!(keyword_start("let foo_bar", 7), Some(4));
+        assert_eq!(keyword_start("foo.", 4), Some(4));
+        assert_eq!(keyword_start("été", 5), Some(0));
+        assert_eq!(keyword_start("foo", 10), None);
+    }
+
+    #[test]
+    fn valid_text_around_cursor() {
+        assert_eq!(valid_around(b"let foo", 7), Some((0, "let foo")));
+        assert_eq!(valid_around(b"a\xffbc d", 4), Some((2, "bc d")));
+        assert_eq!(valid_around(b"ab\xff\xfe", 1), Some((0, "ab")));
+        assert_eq!(valid_around(b"a\xe2\x82b", 2), None);
+        assert_eq!(valid_around(b"a\xff", 2), Some((2, "")));
+        assert_eq!(valid_around(b"", 0), Some((0, "")));
+        assert_eq!(valid_around(b"abc", 4), None);
+    }
+}
@@ -0,0 +1,131 @@
+use nvim_types::{dictionary::Dictionary, object::Object};
+use serde::Deserialize;
+
+use crate::api::types::Namespace;
+use crate::api::Buffer;
+
+/// A diagnostic, see `:h diagnostic-structure`. Lines and columns are
+/// 0-indexed, with the end column being exclusive.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct Diagnostic {
+    pub lnum: usize,
+
+    pub col: usize,
+
+    /// Defaults to `lnum` when not set.
+    pub end_lnum: Option<usize>,
+
+    /// Defaults to `col` when not set.
+    pub end_col: Option<usize>,
+
+    #[serde(default)]
+    pub severity: DiagnosticSeverity,
+
+    pub message: String,
+
+    /// The name of the tool that produced the diagnostic, e.g. a linter.
+    pub source: Option<String>,
+
+    /// The buffer the diagnostic belongs to. Set by Neovim, ignored when
+    /// setting diagnostics.
+    pub bufnr: Option<Buffer>,
+
+    /// The namespace the diagnostic belongs to. Set by Neovim, ignored when
+    /// setting diagnostics.
+    pub namespace: Option<Namespace>,
+}
+
+impl Diagnostic {
+    /// Creates an error diagnostic spanning a single position.
+    pub fn new<Msg: Into<String>>(
+        lnum: usize,
+        col: usize,
+        message: Msg,
+    ) -> Self {
+        Self {
+            lnum,
+            col,
+            end_lnum: None,
+            end_col: None,
+            severity: DiagnosticSeverity::default(),
+            message: message.into(),
+            source: None,
+            bufnr: None,
+            namespace: None,
+        }
+    }
+
+    /// Sets where the diagnostic ends.
+    pub fn end(mut self, end_lnum: usize, end_col: usize) -> Self {
+        self.end_lnum = Some(end_lnum);
+        self.end_col = Some(end_col);
+        self
+    }
+
+    pub fn severity(mut self, severity: DiagnosticSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    pub fn source<Source: Into<String>>(mut self, source: Source) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+}
+
+impl From<Diagnostic> for Object {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Dictionary::from_iter([
+            ("lnum", Object::from(diagnostic.lnum as i64)),
+            ("col", (diagnostic.col as i64).into()),
+            ("end_lnum", diagnostic.end_lnum.map(|lnum| lnum as i64).into()),
+            ("end_col", diagnostic.end_col.map(|col| col as i64).into()),
+            ("severity", diagnostic.severity.into()),
+            ("message", diagnostic.message.into()),
+            ("source", diagnostic.source.into()),
+        ])
+        .into()
+    }
+}
+
+/// See `:h diagnostic-severity`.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Deserialize,
+)]
+#[serde(try_from = "u8")]
+pub enum DiagnosticSeverity {
+    #[default]
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Hint = 4,
+}
+
+impl TryFrom<u8> for DiagnosticSeverity {
+    type Error = String;
+
+    fn try_from(severity: u8) -> Result<Self, String> {
+        match severity {
+            1 => Ok(Self::Error),
+            2 => Ok(Self::Warn),
+            3 => Ok(Self::Info),
+            4 => Ok(Self::Hint),
+            other => Err(format!("invalid diagnostic severity {other}")),
+        }
+    }
+}
+
+impl From<DiagnosticSeverity> for Object {
+    fn from(severity: DiagnosticSeverity) -> Self {
+        (severity as i64).into()
+    }
+}
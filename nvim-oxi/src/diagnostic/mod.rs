@@ -0,0 +1,72 @@
+//! Bindings to the `vim.diagnostic` Lua module.
+//!
+//! Diagnostics are grouped by namespace, usually one per source, which can
+//! be created with `api::create_namespace`.
+
+#[allow(clippy::module_inception)]
+mod diagnostic;
+pub mod opts;
+
+pub use diagnostic::*;
+use nvim_types::{dictionary::Dictionary, object::Object};
+use opts::GetDiagnosticsOpts;
+
+use crate::api::types::Namespace;
+use crate::api::{self, Buffer};
+use crate::Result;
+
+/// Replaces the diagnostics of `namespace` in `buf`.
+pub fn set<Diagnostics>(
+    namespace: Namespace,
+    buf: Buffer,
+    diagnostics: Diagnostics,
+) -> Result<()>
+where
+    Diagnostics: IntoIterator<Item = Diagnostic>,
+{
+    let diagnostics = diagnostics.into_iter().collect::<Object>();
+    api::exec_lua(
+        "vim.diagnostic.set(...)",
+        [Object::from(namespace), buf.0.into(), diagnostics],
+    )
+}
+
+/// Returns the diagnostics of `buf`, or of every buffer if `buf` is `None`.
+pub fn get(
+    buf: Option<Buffer>,
+    opts: &GetDiagnosticsOpts,
+) -> Result<Vec<Diagnostic>> {
+    api::exec_lua(
+        "return vim.diagnostic.get(...)",
+        [Object::from(buf.map(|buf| buf.0)), Dictionary::from(opts).into()],
+    )
+}
+
+/// Displays the diagnostics of `namespace` in `buf`. `None` means every
+/// namespace or every buffer.
+pub fn show(namespace: Option<Namespace>, buf: Option<Buffer>) -> Result<()> {
+    call_with_ns_and_buf("show", namespace, buf)
+}
+
+/// Hides the diagnostics of `namespace` in `buf`, which can be displayed
+/// again with [`show`]. `None` means every namespace or every buffer.
+pub fn hide(namespace: Option<Namespace>, buf: Option<Buffer>) -> Result<()> {
+    call_with_ns_and_buf("hide", namespace, buf)
+}
+
+/// Removes the diagnostics of `namespace` in `buf`. `None` means every
+/// namespace or every buffer.
+pub fn reset(namespace: Option<Namespace>, buf: Option<Buffer>) -> Result<()> {
+    call_with_ns_and_buf("reset", namespace, buf)
+}
+
+fn call_with_ns_and_buf(
+    fun: &str,
+    namespace: Option<Namespace>,
+    buf: Option<Buffer>,
+) -> Result<()> {
+    api::exec_lua(
+        &format!("vim.diagnostic.{fun}(...)"),
+        [Object::from(namespace), buf.map(|buf| buf.0).into()],
+    )
+}
@@ -0,0 +1,45 @@
+use derive_builder::Builder;
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+use crate::api::types::Namespace;
+use crate::diagnostic::DiagnosticSeverity;
+
+/// Options passed to `diagnostic::get`.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct GetDiagnosticsOpts {
+    /// Only get the diagnostics from this namespace.
+    #[builder(setter(strip_option))]
+    namespace: Option<Namespace>,
+
+    /// Only get the diagnostics on this line (0-indexed).
+    #[builder(setter(strip_option))]
+    lnum: Option<usize>,
+
+    /// Only get the diagnostics with this severity.
+    #[builder(setter(strip_option))]
+    severity: Option<DiagnosticSeverity>,
+}
+
+impl GetDiagnosticsOpts {
+    #[inline(always)]
+    pub fn builder() -> GetDiagnosticsOptsBuilder {
+        GetDiagnosticsOptsBuilder::default()
+    }
+}
+
+impl From<GetDiagnosticsOpts> for Dictionary {
+    fn from(opts: GetDiagnosticsOpts) -> Self {
+        Self::from_iter([
+            ("namespace", Object::from(opts.namespace)),
+            ("lnum", opts.lnum.map(|lnum| lnum as i64).into()),
+            ("severity", opts.severity.into()),
+        ])
+    }
+}
+
+impl From<&GetDiagnosticsOpts> for Dictionary {
+    fn from(opts: &GetDiagnosticsOpts) -> Self {
+        opts.clone().into()
+    }
+}
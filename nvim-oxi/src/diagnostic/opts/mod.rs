@@ -0,0 +1,3 @@
+mod get_diagnostics;
+
+pub use get_diagnostics::*;
@@ -0,0 +1,110 @@
+//! Inline rendering of diff hunks, in the style of VCS plugins like
+//! gitsigns.nvim.
+//!
+//! Every line of a hunk gets a sign and a line highlight, and the lines it
+//! removed are displayed as virtual lines above it. The `DiffAdd`,
+//! `DiffChange` and `DiffDelete` highlight groups are used.
+
+use nvim_types::{array::Array, dictionary::Dictionary, object::Object};
+
+use crate::api::types::Namespace;
+use crate::api::{self, Buffer};
+use crate::Result;
+
+/// The kind of change a [`Hunk`] represents.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum HunkKind {
+    /// Lines only present in the buffer.
+    Add,
+
+    /// Lines of the buffer which replaced other lines.
+    Change,
+
+    /// Lines removed from the buffer.
+    Delete,
+}
+
+impl HunkKind {
+    #[inline]
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Change => "change",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// A contiguous change of a buffer, e.g. relative to the version in the
+/// index.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Hunk {
+    pub kind: HunkKind,
+
+    /// The first line of the hunk in the buffer, 0-indexed. For deletions
+    /// it's the line the removed lines used to be above of, which can be
+    /// past the last line.
+    pub start: usize,
+
+    /// The number of lines of the hunk in the buffer. Always 0 for
+    /// deletions.
+    pub count: usize,
+
+    /// The removed lines, displayed as virtual lines. Empty for additions.
+    pub removed: Vec<String>,
+}
+
+impl Hunk {
+    /// `count` lines added starting at `start`.
+    pub fn add(start: usize, count: usize) -> Self {
+        Self { kind: HunkKind::Add, start, count, removed: Vec::new() }
+    }
+
+    /// `count` lines starting at `start` replacing the `removed` ones.
+    pub fn change(start: usize, count: usize, removed: Vec<String>) -> Self {
+        Self { kind: HunkKind::Change, start, count, removed }
+    }
+
+    /// The `removed` lines deleted above `start`.
+    pub fn delete(start: usize, removed: Vec<String>) -> Self {
+        Self { kind: HunkKind::Delete, start, count: 0, removed }
+    }
+}
+
+impl From<Hunk> for Object {
+    fn from(hunk: Hunk) -> Self {
+        Dictionary::from_iter([
+            ("kind", Object::from(hunk.kind.as_str())),
+            ("start", (hunk.start as i64).into()),
+            ("count", (hunk.count as i64).into()),
+            ("removed", hunk.removed.into_iter().collect::<Array>().into()),
+        ])
+        .into()
+    }
+}
+
+/// Renders `hunks` in `buf`, replacing the hunks previously rendered in
+/// `ns`. All the decorations are placed in a single call into Lua.
+pub fn render_hunks<Hunks>(
+    buf: Buffer,
+    ns: Namespace,
+    hunks: Hunks,
+) -> Result<()>
+where
+    Hunks: IntoIterator<Item = Hunk>,
+{
+    let hunks = hunks.into_iter().collect::<Array>();
+    api::exec_lua(
+        include_str!("./render.lua"),
+        [Object::from(buf.0), ns.into(), hunks.into()],
+    )
+}
+
+/// Removes the hunks rendered in `ns` by [`render_hunks`].
+pub fn clear(buf: Buffer, ns: Namespace) -> Result<()> {
+    api::exec_lua(
+        "local buf, ns = ...; vim.api.nvim_buf_clear_namespace(buf, ns, 0, \
+         -1)",
+        [Object::from(buf.0), ns.into()],
+    )
+}
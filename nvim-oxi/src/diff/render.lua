@@ -0,0 +1,42 @@
+-- Places the decorations of every hunk in a single pass, replacing the ones
+-- previously placed in the namespace.
+local buf, ns, hunks = ...
+
+local api = vim.api
+
+api.nvim_buf_clear_namespace(buf, ns, 0, -1)
+
+local styles = {
+  add = { sign = '┃', hl = 'DiffAdd' },
+  change = { sign = '┃', hl = 'DiffChange' },
+  delete = { sign = '‾', hl = 'DiffDelete' },
+}
+
+local last_line = api.nvim_buf_line_count(buf) - 1
+
+for _, hunk in ipairs(hunks) do
+  local style = styles[hunk.kind]
+  local first = math.min(hunk.start, last_line)
+  local last = math.min(hunk.start + math.max(hunk.count, 1) - 1, last_line)
+
+  for lnum = first, last do
+    api.nvim_buf_set_extmark(buf, ns, lnum, 0, {
+      sign_text = style.sign,
+      sign_hl_group = style.hl,
+      line_hl_group = hunk.kind ~= 'delete' and style.hl or nil,
+    })
+  end
+
+  if hunk.removed and #hunk.removed > 0 then
+    local virt_lines = {}
+    for _, line in ipairs(hunk.removed) do
+      table.insert(virt_lines, { { line, 'DiffDelete' } })
+    end
+
+    -- Lines removed at the end of the buffer go below the last line.
+    api.nvim_buf_set_extmark(buf, ns, first, 0, {
+      virt_lines = virt_lines,
+      virt_lines_above = hunk.start <= last_line,
+    })
+  end
+end
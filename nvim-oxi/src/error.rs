@@ -1,5 +1,8 @@
+use std::any::Any;
 use std::fmt;
+use std::path::PathBuf;
 
+use nvim_types::error::{Error as RawNvimError, ErrorType};
 use serde::{de, ser};
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -7,7 +10,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error(transparent)]
-    NvimError(#[from] nvim_types::error::Error),
+    NvimError(#[from] NvimError),
 
     #[error(transparent)]
     FromObjectError(#[from] nvim_types::object::FromObjectError),
@@ -21,11 +24,222 @@ pub enum Error {
     #[error(transparent)]
     IntError(#[from] std::num::TryFromIntError),
 
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    #[error("couldn't parse a handle from \"{0}\"")]
+    HandleParseError(String),
+
+    /// `line` is the line that failed, if it could be determined.
+    #[error(
+        "error executing lines {start}-{end}{}: {source}",
+        line.map(|line| format!(" (at line {line})")).unwrap_or_default()
+    )]
+    ExecStreamError {
+        start: usize,
+        end: usize,
+        line: Option<usize>,
+        source: Box<Error>,
+    },
+
+    #[error("{} already exists, writing it requires forcing", .0.display())]
+    FileExistsError(PathBuf),
+
+    #[error("couldn't open {} for writing", .0.display())]
+    CantOpenForWritingError(PathBuf),
+
+    #[error("couldn't set register '{0}'")]
+    SetRegisterError(char),
+
+    #[error("couldn't set the tag stack of {0}")]
+    SetTagStackError(crate::api::Window),
+
+    #[error("option '{name}' is a {found:?} option, not a {expected:?} one")]
+    OptionTypeError {
+        name: String,
+        expected: crate::api::types::OptionType,
+        found: crate::api::types::OptionType,
+    },
+
+    #[error("'{option}' has no effect unless '{requires}' is set")]
+    PreconditionFailedError { option: &'static str, requires: &'static str },
+
+    #[error("neither telescope.nvim nor fzf-lua are installed")]
+    PickerUnavailableError,
+
+    #[error("this plugin requires Neovim {required} or later, found {found}")]
+    VersionError { required: String, found: String },
+
+    #[error("{0}")]
+    TestHarnessError(String),
+
+    #[error("timed out after {0:?}")]
+    TestTimeoutError(std::time::Duration),
+
+    #[error("couldn't install the logger: {0}")]
+    LoggerError(String),
+
+    #[error("the filetype rule has no {0}")]
+    FiletypeRuleError(&'static str),
+
+    #[error("\"{0}\" isn't a valid healthcheck name")]
+    HealthcheckNameError(String),
+
+    #[error("panicked: {0}")]
+    PanicError(String),
+
+    #[error("{ty} is already borrowed")]
+    StateBorrowError { ty: &'static str },
+
+    #[error("error calling Lua function: {0}")]
+    LuaError(String),
+
     #[error("{0}")]
     SerializeError(String),
 
     #[error("{0}")]
     DeserializeError(String),
+
+    #[error("invalid value at `{path}`: {source}")]
+    DeserializePathError { path: String, source: Box<Error> },
+}
+
+/// An error returned by a Neovim API function.
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+pub struct NvimError {
+    /// Whether the error comes from invalid arguments or from a failure
+    /// while executing the function.
+    pub kind: NvimErrorKind,
+
+    /// The message of the error, e.g. `E5555: API call: ...`.
+    pub message: String,
+
+    /// The name of the API function that returned the error, if known.
+    pub function: Option<&'static str>,
+}
+
+/// The category of a [`NvimError`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum NvimErrorKind {
+    /// The function failed while running, e.g. because a Vimscript command
+    /// or a Lua function raised an error.
+    Exception,
+
+    /// The function was called with invalid arguments, e.g. an invalid
+    /// buffer handle.
+    Validation,
+}
+
+impl fmt::Display for NvimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.function {
+            Some(function) => write!(f, "{function}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl NvimError {
+    /// Returns the number of the first Vim error code in the message, e.g.
+    /// `5108` for `E5108: Error executing lua ...`.
+    pub fn code(&self) -> Option<u32> {
+        self.message.match_indices('E').find_map(|(idx, _)| {
+            let rest = &self.message[idx + 1..];
+            let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+            let (code, after) = rest.split_at(digits);
+            (digits > 0 && after.starts_with(':'))
+                .then(|| code.parse().ok())
+                .flatten()
+        })
+    }
+}
+
+impl From<RawNvimError> for Error {
+    fn from(err: RawNvimError) -> Self {
+        let kind = match err.r#type {
+            ErrorType::kErrorTypeValidation => NvimErrorKind::Validation,
+            _ => NvimErrorKind::Exception,
+        };
+
+        Self::NvimError(NvimError {
+            kind,
+            message: err.to_string(),
+            function: None,
+        })
+    }
+}
+
+impl Error {
+    /// Whether this is an error raised by Neovim because the arguments of an
+    /// API call were invalid.
+    pub fn is_validation(&self) -> bool {
+        matches!(self, Self::NvimError(err) if err.kind == NvimErrorKind::Validation)
+    }
+
+    /// Whether this is an error raised by Neovim while executing an API
+    /// call.
+    pub fn is_exception(&self) -> bool {
+        matches!(self, Self::NvimError(err) if err.kind == NvimErrorKind::Exception)
+    }
+
+    /// Whether this error was raised because the API call isn't allowed
+    /// right now, e.g. because of textlock (`E565`), inside a fast event
+    /// (`E5560`) or in a context where changes aren't allowed (`E523`).
+    /// Such calls usually succeed if retried from the event loop, see
+    /// [`defer_mutation`](crate::defer_mutation).
+    pub fn is_textlock(&self) -> bool {
+        const CODES: [u32; 3] = [523, 565, 5560];
+        match self {
+            Self::NvimError(err) => {
+                err.code().is_some_and(|c| CODES.contains(&c))
+            },
+            Self::LuaError(msg) => {
+                CODES.iter().any(|code| msg.contains(&format!("E{code}:")))
+            },
+            _ => false,
+        }
+    }
+
+    /// Converts the payload of a caught panic into an error.
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let msg = match payload.downcast::<String>() {
+            Ok(msg) => *msg,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(msg) => (*msg).to_owned(),
+                Err(_) => "Box<dyn Any>".to_owned(),
+            },
+        };
+        Self::PanicError(msg)
+    }
+
+    /// Records the API function that returned the error, if it was returned
+    /// by Neovim.
+    pub(crate) fn in_function(mut self, function: &'static str) -> Self {
+        if let Self::NvimError(ref mut err) = self {
+            err.function.get_or_insert(function);
+        }
+        self
+    }
+
+    /// Adds a key or an index (formatted as `[idx]`) at the start of the
+    /// path of a deserialization error, so that the final path points at the
+    /// value that failed to deserialize, e.g. `window.border[2]`.
+    pub(crate) fn at_path_segment(self, segment: &str) -> Self {
+        match self {
+            Self::DeserializePathError { path, source } => {
+                let sep = if path.starts_with('[') { "" } else { "." };
+                Self::DeserializePathError {
+                    path: format!("{segment}{sep}{path}"),
+                    source,
+                }
+            },
+
+            other => Self::DeserializePathError {
+                path: segment.to_owned(),
+                source: Box::new(other),
+            },
+        }
+    }
 }
 
 impl ser::Error for Error {
@@ -39,3 +253,49 @@ impl de::Error for Error {
         Self::DeserializeError(msg.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn err(message: &str) -> NvimError {
+        NvimError {
+            kind: NvimErrorKind::Exception,
+            message: message.into(),
+            function: None,
+        }
+    }
+
+    #[test]
+    fn error_code() {
+        assert_eq!(err("E5108: Error executing lua").code(), Some(5108));
+        assert_eq!(err("Vim:E492: Not an editor command").code(), Some(492));
+        assert_eq!(err("Error: E5 without colon").code(), None);
+        assert_eq!(err("Invalid buffer id: 42").code(), None);
+    }
+
+    #[test]
+    fn textlock_errors() {
+        let textlock = |msg: &str| Error::from(err(msg)).is_textlock();
+        assert!(textlock("E565: Not allowed to change text or change window"));
+        assert!(textlock("Vim:E523: Not allowed here"));
+        assert!(textlock(
+            "E5560: nvim_buf_set_lines must not be called in a lua loop \
+             callback"
+        ));
+        assert!(!textlock("E5108: Error executing lua"));
+        assert!(Error::LuaError("vim/_editor.lua: E565: Not allowed".into())
+            .is_textlock());
+    }
+
+    #[test]
+    fn from_panic() {
+        let err = std::panic::catch_unwind(|| panic!("oh no {}", 1))
+            .map_err(Error::from_panic)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "panicked: oh no 1");
+
+        let err = Error::from_panic(Box::new("static"));
+        assert_eq!(err.to_string(), "panicked: static");
+    }
+}
@@ -0,0 +1,165 @@
+//! Bindings to `vim.filetype`, used to detect the filetype of a file and to
+//! register new detection rules.
+//!
+//! ```ignore
+//! use nvim_oxi::filetype::{self, FtRule};
+//!
+//! let rule =
+//!     FtRule::builder().extension("oxi").filetype("rust").build().unwrap();
+//!
+//! filetype::register(&rule)?;
+//!
+//! let rule = FtRule::builder()
+//!     .pattern(r".*/templates/.*%.html")
+//!     .callback(|(_path, _buf)| Ok(Some("htmldjango".into())))
+//!     .build()
+//!     .unwrap();
+//!
+//! filetype::register(&rule)?;
+//! ```
+
+mod rule;
+
+use std::path::{Path, PathBuf};
+
+use nvim_types::{
+    dictionary::Dictionary,
+    object::Object,
+    string::String as NvimString,
+};
+pub use rule::*;
+
+use crate::api::{self, Buffer};
+use crate::{Error, Result};
+
+/// What `filetype::match` should detect the filetype of.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FiletypeSource {
+    /// A buffer, matched using its name and, if needed, its contents.
+    Buffer(Buffer),
+
+    /// A path, which doesn't need to exist.
+    Path(PathBuf),
+}
+
+impl From<Buffer> for FiletypeSource {
+    fn from(buf: Buffer) -> Self {
+        Self::Buffer(buf)
+    }
+}
+
+impl From<PathBuf> for FiletypeSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&Path> for FiletypeSource {
+    fn from(path: &Path) -> Self {
+        Self::Path(path.to_owned())
+    }
+}
+
+impl From<FiletypeSource> for Dictionary {
+    fn from(source: FiletypeSource) -> Self {
+        match source {
+            FiletypeSource::Buffer(buf) => {
+                Self::from_iter([("buf", Object::from(buf.0))])
+            },
+            FiletypeSource::Path(path) => Self::from_iter([(
+                "filename",
+                Object::from(NvimString::from(path.into_os_string())),
+            )]),
+        }
+    }
+}
+
+/// Binding to `vim.filetype.match()`.
+///
+/// Returns the filetype of `source`, or `None` if it couldn't be detected.
+pub fn r#match(source: impl Into<FiletypeSource>) -> Result<Option<String>> {
+    let args = Dictionary::from(source.into());
+    api::exec_lua("return (vim.filetype.match(...))", [args])
+}
+
+/// Binding to `vim.filetype.add()`.
+///
+/// Registers a new detection rule, taking precedence over the builtin ones.
+/// Fails if the rule doesn't say which files it applies to, or which
+/// filetype to give them.
+pub fn register(rule: &FtRule) -> Result<()> {
+    let rule = validate(rule.clone())?;
+
+    let keys = Dictionary::from_iter([
+        ("extension", rule.extension),
+        ("filename", rule.filename),
+        ("pattern", rule.pattern),
+    ]);
+
+    let value = match (rule.callback, rule.filetype) {
+        (Some(callback), _) => Object::from(callback),
+        (None, filetype) => Object::from(filetype),
+    };
+
+    api::exec_lua(
+        r#"
+        local keys, value = ...
+        local spec = {}
+        for kind, key in pairs(keys) do
+          spec[kind] = { [key] = value }
+        end
+        vim.filetype.add(spec)
+        "#,
+        [Object::from(keys), value],
+    )
+}
+
+/// Checks that `rule` would actually register something, since
+/// `vim.filetype.add()` silently ignores incomplete rules. Empty strings
+/// count as missing.
+fn validate(mut rule: FtRule) -> Result<FtRule> {
+    for key in [&mut rule.extension, &mut rule.filename, &mut rule.pattern] {
+        key.take_if(|key| key.is_empty());
+    }
+    rule.filetype.take_if(|filetype| filetype.is_empty());
+
+    if rule.extension.is_none()
+        && rule.filename.is_none()
+        && rule.pattern.is_none()
+    {
+        return Err(Error::FiletypeRuleError(
+            "extension, filename or pattern",
+        ));
+    }
+
+    if rule.callback.is_none() && rule.filetype.is_none() {
+        return Err(Error::FiletypeRuleError("filetype or callback"));
+    }
+
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incomplete_rules() {
+        let rule = FtRule::builder().filetype("rust").build().unwrap();
+        assert!(matches!(validate(rule), Err(Error::FiletypeRuleError(_))));
+
+        let rule =
+            FtRule::builder().extension("").filetype("rust").build().unwrap();
+        assert!(matches!(validate(rule), Err(Error::FiletypeRuleError(_))));
+
+        let rule = FtRule::builder().extension("rs").build().unwrap();
+        assert!(matches!(validate(rule), Err(Error::FiletypeRuleError(_))));
+
+        let rule = FtRule::builder()
+            .extension("rs")
+            .filetype("rust")
+            .build()
+            .unwrap();
+        assert!(validate(rule).is_ok());
+    }
+}
@@ -0,0 +1,56 @@
+use derive_builder::Builder;
+
+use crate::api::Buffer;
+use crate::lua::LuaFnMut;
+use crate::Result;
+
+/// Arguments passed to the callback of an [`FtRule`]: the full path of the
+/// file and the buffer it's loaded in.
+pub type FtCallbackArgs = (String, Buffer);
+
+/// A filetype detection rule registered with [`register`](super::register).
+///
+/// The rule applies to files with the given extension, name, or full path
+/// matching a Lua pattern, and assigns them either a fixed filetype or the
+/// one returned by a callback.
+#[derive(Clone, Debug, Default, Builder)]
+#[builder(default)]
+pub struct FtRule {
+    /// Extension of the files, without the leading dot.
+    #[builder(setter(into, strip_option))]
+    pub(super) extension: Option<String>,
+
+    /// Name of the files, or their full path.
+    #[builder(setter(into, strip_option))]
+    pub(super) filename: Option<String>,
+
+    /// Lua pattern matched against the full path of the files.
+    #[builder(setter(into, strip_option))]
+    pub(super) pattern: Option<String>,
+
+    /// Filetype assigned to the matching files.
+    #[builder(setter(into, strip_option))]
+    pub(super) filetype: Option<String>,
+
+    /// Function returning the filetype of a matching file, or `None` to let
+    /// other rules decide. Takes precedence over `filetype`.
+    #[builder(setter(custom))]
+    pub(super) callback: Option<LuaFnMut<FtCallbackArgs, Option<String>>>,
+}
+
+impl FtRule {
+    #[inline(always)]
+    pub fn builder() -> FtRuleBuilder {
+        FtRuleBuilder::default()
+    }
+}
+
+impl FtRuleBuilder {
+    pub fn callback<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnMut(FtCallbackArgs) -> Result<Option<String>> + 'static,
+    {
+        self.callback = Some(Some(fun.into()));
+        self
+    }
+}
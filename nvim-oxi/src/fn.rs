@@ -0,0 +1,158 @@
+//! Typed wrappers around commonly used Vimscript functions, i.e. what Lua
+//! plugins reach through `vim.fn`. They're all thin wrappers around
+//! [`api::call_function`], see `:h {name}()` for the details of each one.
+//!
+//! ```ignore
+//! use nvim_oxi::r#fn;
+//!
+//! let file = r#fn::expand("%:p")?;
+//! let config = r#fn::stdpath(r#fn::StdPath::Config)?;
+//! ```
+
+use std::path::PathBuf;
+
+use nvim_types::object::Object;
+
+use crate::api::{self, Buffer};
+use crate::Result;
+
+/// Defines a function calling the Vimscript function of the same name with
+/// its arguments, deserializing the returned value into the return type.
+macro_rules! vimfn {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;
+    )*) => {$(
+        $(#[$attr])*
+        pub fn $name($($arg: $ty),*) -> Result<$ret> {
+            let args: Vec<Object> = vec![$(Object::from($arg)),*];
+            api::call_function(stringify!($name), args)
+        }
+    )*};
+}
+
+/// Defines a function like `vimfn!`, for Vimscript functions returning 0 or
+/// 1 as booleans.
+macro_rules! vimfn_bool {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),*);
+    )*) => {$(
+        $(#[$attr])*
+        pub fn $name($($arg: $ty),*) -> Result<bool> {
+            let args: Vec<Object> = vec![$(Object::from($arg)),*];
+            api::call_function::<_, i64>(stringify!($name), args)
+                .map(|res| res != 0)
+        }
+    )*};
+}
+
+vimfn! {
+    /// Returns the 1-indexed byte column of a position, e.g. `"."` for
+    /// the cursor or `"$"` for the end of the cursor line.
+    fn col(expr: &str) -> usize;
+
+    /// Expands wildcards and special keywords like `%:p` or `<cword>`.
+    fn expand(expr: &str) -> String;
+
+    /// Escapes a file name to be used as a command argument.
+    fn fnameescape(fname: &str) -> String;
+
+    /// Modifies a file name with modifiers like `:p:h` or `:t:r`.
+    fn fnamemodify(fname: &str, mods: &str) -> String;
+
+    /// Returns the current working directory.
+    fn getcwd() -> PathBuf;
+
+    /// Returns the line `lnum` of the current buffer, which can also be
+    /// an expression like `"."`.
+    fn getline(lnum: &str) -> String;
+
+    /// Returns the process id of Neovim.
+    fn getpid() -> u32;
+
+    /// Returns the name of the machine Neovim runs on.
+    fn hostname() -> String;
+
+    /// Returns the 1-indexed line number of a position, e.g. `"."` for the
+    /// cursor, `"$"` for the last line or `"w0"` for the first visible one.
+    fn line(expr: &str) -> usize;
+
+    /// Returns the current mode in the short form of `mode()`. See
+    /// `api::get_mode` for a typed version.
+    fn mode() -> String;
+
+    /// Escapes a string to be used as a shell command argument.
+    fn shellescape(string: &str) -> String;
+
+    /// Returns the number of screen cells `string` takes, expanding tabs
+    /// with the `'tabstop'` of the current buffer.
+    fn strdisplaywidth(string: &str) -> usize;
+
+    /// Runs a shell command, returning its output. Check `v:shell_error`
+    /// for its exit code.
+    fn system(cmd: &str) -> String;
+
+    /// Runs a shell command, returning its output split into lines.
+    fn systemlist(cmd: &str) -> Vec<String>;
+
+    /// Returns the name of a file that doesn't exist yet, to be used as a
+    /// temporary file.
+    fn tempname() -> PathBuf;
+}
+
+vimfn_bool! {
+    /// Whether `name` is an executable in `$PATH`.
+    fn executable(name: &str);
+
+    /// Whether a variable, option, command, function, ... exists, e.g.
+    /// `"g:loaded_foo"` or `":Cmd"`.
+    fn exists(expr: &str);
+
+    /// Whether `file` exists and is readable.
+    fn filereadable(file: &str);
+
+    /// Whether `directory` exists and is a directory.
+    fn isdirectory(directory: &str);
+}
+
+/// Returns the buffer whose name matches `name`, or `None` if there's
+/// none. See `:h bufname()` for how `name` is matched.
+pub fn bufnr(name: &str) -> Result<Option<Buffer>> {
+    let bufnr = api::call_function::<_, i64>("bufnr", [name])?;
+    if bufnr <= 0 {
+        return Ok(None);
+    }
+    Buffer::from_number(u32::try_from(bufnr)?).map(Some)
+}
+
+/// A standard directory, see [`stdpath`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum StdPath {
+    Cache,
+    Config,
+    Data,
+    Log,
+    Run,
+    State,
+}
+
+impl StdPath {
+    fn as_str(self) -> &'static str {
+        use StdPath::*;
+        match self {
+            Cache => "cache",
+            Config => "config",
+            Data => "data",
+            Log => "log",
+            Run => "run",
+            State => "state",
+        }
+    }
+}
+
+/// Returns a standard directory, e.g. where plugins should store their
+/// data or logs.
+pub fn stdpath(what: StdPath) -> Result<PathBuf> {
+    api::call_function("stdpath", [what.as_str()])
+}
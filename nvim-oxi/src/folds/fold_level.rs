@@ -0,0 +1,65 @@
+use serde::{Serialize, Serializer};
+
+/// The value returned by a `'foldexpr'` for a line, see `:h fold-expr`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FoldLevel {
+    /// The line is in a fold of this level, 0 if it isn't in a fold.
+    Level(u32),
+
+    /// The line starts a fold of this level, `>1`.
+    Start(u32),
+
+    /// The line ends a fold of this level, `<1`.
+    End(u32),
+
+    /// The fold level is increased by this amount, `a1`.
+    Add(u32),
+
+    /// The fold level is decreased by this amount, `s1`.
+    Subtract(u32),
+
+    /// The line has the same level as the previous one, `=`.
+    Same,
+
+    /// The level is the lowest of the lines before and after, `-1`.
+    Undefined,
+}
+
+impl Serialize for FoldLevel {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        use FoldLevel::*;
+        match self {
+            Level(level) => serializer.serialize_u32(*level),
+            Start(level) => serializer.collect_str(&format_args!(">{level}")),
+            End(level) => serializer.collect_str(&format_args!("<{level}")),
+            Add(n) => serializer.collect_str(&format_args!("a{n}")),
+            Subtract(n) => serializer.collect_str(&format_args!("s{n}")),
+            Same => serializer.serialize_str("="),
+            Undefined => serializer.serialize_i64(-1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::{FromObject, ToObject};
+
+    #[test]
+    fn fold_levels() {
+        let int =
+            |level: FoldLevel| i64::from_obj(level.to_obj().unwrap()).unwrap();
+        let str = |level: FoldLevel| {
+            String::from_obj(level.to_obj().unwrap()).unwrap()
+        };
+
+        assert_eq!(int(FoldLevel::Level(2)), 2);
+        assert_eq!(int(FoldLevel::Undefined), -1);
+        assert_eq!(str(FoldLevel::Start(1)), ">1");
+        assert_eq!(str(FoldLevel::Subtract(3)), "s3");
+        assert_eq!(str(FoldLevel::Same), "=");
+    }
+}
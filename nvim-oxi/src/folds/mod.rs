@@ -0,0 +1,125 @@
+//! Folds: creating and deleting them, querying their state, and computing
+//! fold levels in Rust with a [`FoldExpr`].
+//!
+//! Lines are 0-indexed, like in `Buffer::get_lines`, and every function
+//! acts on the folds of a specific window, since folds are local to windows.
+//!
+//! ```ignore
+//! use nvim_oxi::folds::{FoldExpr, FoldLevel};
+//!
+//! // Folds every block of lines starting with `#` comments.
+//! let expr = FoldExpr::new(|buf, line| {
+//!     let text = buf.get_lines(line, line + 1, true)?.next();
+//!     let is_comment = text.map_or(false, |l| l.as_bytes().starts_with(b"#"));
+//!     Ok(if is_comment { FoldLevel::Level(1) } else { FoldLevel::Level(0) })
+//! });
+//!
+//! expr.set_for(Window::current())?;
+//! ```
+
+mod fold_level;
+
+use std::ops::Range;
+
+pub use fold_level::FoldLevel;
+
+use crate::api::opts::OptionValueOpts;
+use crate::api::vimscript::opts::ExecOpts;
+use crate::api::{self, Buffer, Window};
+use crate::lua::{self, LuaFnMut};
+use crate::Result;
+
+/// The Lua table storing the fold expression functions.
+const FOLD_EXPR_FNS: &str = "_nvim_oxi_folds";
+
+/// Creates a fold containing `lines` in `win`, like `:{range}fold`. Only
+/// works if `'foldmethod'` is `manual` or `marker`.
+pub fn create(win: Window, lines: Range<usize>) -> Result<()> {
+    run_on_range(win, lines, "fold")
+}
+
+/// Deletes one level of folds in `lines`, like `:{range}folddelete`.
+pub fn delete(win: Window, lines: Range<usize>) -> Result<()> {
+    run_on_range(win, lines, "folddelete")
+}
+
+/// Deletes every fold in `win`, like `zE`.
+pub fn delete_all(win: Window) -> Result<()> {
+    win.call(|()| api::exec2("normal! zE", &ExecOpts::default()).map(|_| ()))
+}
+
+/// Returns the fold level of `line` in `win`, 0 if it isn't in a fold.
+pub fn level(win: Window, line: usize) -> Result<u32> {
+    win.call(move |()| api::call_function("foldlevel", [line as i64 + 1]))
+}
+
+/// Returns the lines of the closed fold `line` is in, or `None` if it
+/// isn't in a closed fold.
+pub fn closed_range(win: Window, line: usize) -> Result<Option<Range<usize>>> {
+    win.call(move |()| {
+        let lnum = line as i64 + 1;
+        let start = api::call_function::<_, i64>("foldclosed", [lnum])?;
+        let end = api::call_function::<_, i64>("foldclosedend", [lnum])?;
+        Ok((start > 0).then(|| start as usize - 1..end as usize))
+    })
+}
+
+/// Whether `line` is in a closed fold in `win`.
+pub fn is_closed(win: Window, line: usize) -> Result<bool> {
+    closed_range(win, line).map(|range| range.is_some())
+}
+
+fn run_on_range(win: Window, lines: Range<usize>, cmd: &str) -> Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let cmdline = format!("{},{}{cmd}", lines.start + 1, lines.end);
+    win.call(move |()| api::exec2(&cmdline, &ExecOpts::default()).map(|_| ()))
+}
+
+/// A `'foldexpr'` implemented in Rust, see the [module-level](self)
+/// documentation.
+///
+/// The function is called by Neovim for every line of the buffer with the
+/// buffer and the 0-indexed line, and returns the line's [`FoldLevel`]. It's
+/// kept alive until the plugin is unloaded, even if the `FoldExpr` is
+/// dropped.
+#[derive(Clone, Debug)]
+pub struct FoldExpr {
+    expr: String,
+}
+
+impl FoldExpr {
+    pub fn new<F>(mut compute: F) -> Self
+    where
+        F: FnMut(Buffer, usize) -> Result<FoldLevel> + 'static,
+    {
+        let fun = LuaFnMut::<(), FoldLevel>::from(move |()| {
+            // The current window and buffer are the ones folds are computed
+            // for, and `v:lnum` is the 1-indexed line.
+            let lnum = api::get_vvar::<usize>("lnum")?;
+            compute(Buffer::current(), lnum.saturating_sub(1))
+        });
+
+        let key = lua::add_global_fn(FOLD_EXPR_FNS, fun.0);
+
+        Self { expr: format!("v:lua.{FOLD_EXPR_FNS}.{key}()") }
+    }
+
+    /// Returns the expression calling the function, to be used as the
+    /// value of `'foldexpr'`.
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// Sets `'foldmethod'` to `expr` and `'foldexpr'` to this expression
+    /// in `win`.
+    pub fn set_for(&self, win: Window) -> Result<()> {
+        let opts = OptionValueOpts::builder()
+            .win(win)
+            .build()
+            .expect("all fields have defaults");
+        api::set_option_value("foldexpr", self.expr.as_str(), &opts)?;
+        api::set_option_value("foldmethod", "expr", &opts)
+    }
+}
@@ -0,0 +1,243 @@
+//! Healthchecks for `:checkhealth`, see `:h health-dev`.
+//!
+//! Neovim finds healthchecks by looking for `lua/{plugin}/health.lua` files
+//! in the runtime directories, which a plugin compiled into a library
+//! doesn't ship. [`register`] writes one under Neovim's cache directory,
+//! adds it to `'runtimepath'` and has it call the given function, so that
+//! `:checkhealth {plugin}` works like for any Lua plugin.
+//!
+//! ```ignore
+//! use nvim_oxi::{health, r#fn};
+//!
+//! health::register("myplugin", || {
+//!     health::start("dependencies")?;
+//!     if r#fn::executable("rg")? {
+//!         health::ok("ripgrep is installed")
+//!     } else {
+//!         health::warn("ripgrep isn't installed", &["Install it to search"])
+//!     }
+//! })?;
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use nvim_types::{array::Array, object::Object, string::String as NvimString};
+use once_cell::unsync::OnceCell;
+
+use crate::api::opts::OptionValueOpts;
+use crate::api::{self, StdPath};
+use crate::lua::{self, LuaFnMut, LuaRefValue, LuaTable, Variadic};
+use crate::{Error, Result};
+
+/// The Lua table storing the healthcheck functions.
+const HEALTH_FNS: &str = "_nvim_oxi_health";
+
+thread_local! {
+    /// The registered healthchecks, released when a plugin is registered
+    /// again.
+    static CHECKS: RefCell<HashMap<String, LuaFnMut<(), ()>>> =
+        RefCell::new(HashMap::new());
+
+    /// The `vim.health` module, loaded by the first report, and whether it
+    /// has the names without the `report_` prefix.
+    static VIM_HEALTH: OnceCell<(LuaTable, bool)> = const { OnceCell::new() };
+}
+
+/// Registers `check` as the healthcheck of `plugin`, run by `:checkhealth
+/// {plugin}`. Registering a plugin again replaces its healthcheck.
+///
+/// The function reports its results with [`start`], [`ok`], [`info`],
+/// [`warn`] and [`error`]. If it fails, its error is reported with
+/// [`error`] instead of aborting `:checkhealth`.
+///
+/// The name of the plugin can contain dots, e.g. `myplugin.lsp` is run by
+/// `:checkhealth myplugin.lsp`.
+pub fn register<F>(plugin: &str, mut check: F) -> Result<()>
+where
+    F: FnMut() -> Result<()> + 'static,
+{
+    if !is_valid_name(plugin) {
+        return Err(Error::HealthcheckNameError(plugin.to_owned()));
+    }
+
+    let fun = LuaFnMut::<(), ()>::from(move |()| {
+        check().or_else(|err| error(&err.to_string(), &[]))
+    });
+    lua::set_global_fn(HEALTH_FNS, plugin, fun.0);
+
+    let previous = CHECKS
+        .with(|checks| checks.borrow_mut().insert(plugin.to_owned(), fun));
+    if let Some(previous) = previous {
+        previous.unref();
+    }
+
+    let dir = api::stdpath(StdPath::Cache)?.join("nvim-oxi").join("health");
+    let mut file = dir.join("lua");
+    file.extend(plugin.split('.'));
+    fs::create_dir_all(&file)?;
+    file.push("health.lua");
+    fs::write(
+        file,
+        format!(
+            "return {{ check = function() {HEALTH_FNS}[\"{plugin}\"]() end \
+             }}\n"
+        ),
+    )?;
+
+    add_to_runtimepath(dir)
+}
+
+/// Whether `plugin` can be passed to `:checkhealth`, i.e. is a
+/// dot-separated list of names that can be used as directories.
+fn is_valid_name(plugin: &str) -> bool {
+    plugin.split('.').all(|part| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
+
+/// Appends `dir` to `'runtimepath'` if it isn't already in it.
+fn add_to_runtimepath(dir: PathBuf) -> Result<()> {
+    let dir = NvimString::from(dir.into_os_string());
+    let opts = OptionValueOpts::default();
+    let rtp = api::get_option_value::<NvimString>("runtimepath", &opts)?;
+
+    if rtp.as_bytes().split(|&b| b == b',').any(|path| path == dir.as_bytes())
+    {
+        return Ok(());
+    }
+
+    let mut value = rtp.into_bytes();
+    value.push(b',');
+    for &byte in dir.as_bytes() {
+        // Commas in directory names are escaped with a backslash.
+        if byte == b',' {
+            value.push(b'\\');
+        }
+        value.push(byte);
+    }
+    api::set_option_value("runtimepath", NvimString::from_bytes(value), &opts)
+}
+
+/// Starts a new section of the report.
+pub fn start(name: &str) -> Result<()> {
+    report("start", name, None)
+}
+
+/// Reports a successful check.
+pub fn ok(msg: &str) -> Result<()> {
+    report("ok", msg, None)
+}
+
+/// Reports an informational message.
+pub fn info(msg: &str) -> Result<()> {
+    report("info", msg, None)
+}
+
+/// Reports a warning, with suggestions on how to fix it.
+pub fn warn(msg: &str, advice: &[&str]) -> Result<()> {
+    report("warn", msg, Some(advice))
+}
+
+/// Reports an error, with suggestions on how to fix it.
+pub fn error(msg: &str, advice: &[&str]) -> Result<()> {
+    report("error", msg, Some(advice))
+}
+
+/// Calls `vim.health.{kind}`, or `vim.health.report_{kind}` before Neovim
+/// 0.10 which doesn't have the shorter names.
+fn report(kind: &str, msg: &str, advice: Option<&[&str]>) -> Result<()> {
+    let (health, short_names) = VIM_HEALTH.with(|health| {
+        health
+            .get_or_try_init(|| {
+                let health = lua::require("vim.health")?;
+                // Getting the function references it, which is released
+                // right away.
+                let start = health.get::<Object>("start")?;
+                let short_names =
+                    LuaRefValue::<(), ()>::try_from(start).is_ok();
+                Ok::<_, Error>((health, short_names))
+            })
+            .copied()
+    })?;
+
+    let kind =
+        if short_names { kind.to_owned() } else { format!("report_{kind}") };
+
+    let advice = advice.map(|advice| Array::from_iter(advice.iter().copied()));
+    let args = Variadic(vec![Object::from(msg), advice.into()]);
+
+    health.call(&kind, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_names() {
+        assert!(is_valid_name("myplugin"));
+        assert!(is_valid_name("my-plugin.lsp_client"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("myplugin."));
+        assert!(!is_valid_name("my plugin"));
+        assert!(!is_valid_name("../myplugin"));
+    }
+}
+
+#[cfg(feature = "test")]
+mod nvim_tests {
+    use super::*;
+    use crate::api::Buffer;
+
+    /// Runs `:checkhealth {plugin}` and returns the contents of the report.
+    fn checkhealth(plugin: &str) -> Result<String> {
+        api::exec(&format!("checkhealth {plugin}"), false)?;
+        let buf = Buffer::current();
+        let lines = buf.get_lines(0, buf.line_count()?, true)?;
+        Ok(lines.map(|line| line.to_string_lossy().into_owned()).collect())
+    }
+
+    #[nvim_oxi::test]
+    fn reports() -> Result<()> {
+        register("nvim_oxi_health", || {
+            start("dependencies")?;
+            ok("everything is fine")?;
+            info("some details")?;
+            warn("something is off", &["fix it"])?;
+            Err(Error::LuaError("the check failed".into()))
+        })?;
+
+        let report = checkhealth("nvim_oxi_health")?;
+        for msg in [
+            "dependencies",
+            "everything is fine",
+            "some details",
+            "something is off",
+            "fix it",
+            "the check failed",
+        ] {
+            assert!(report.contains(msg), "{msg:?} missing from {report:?}");
+        }
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn register_again() -> Result<()> {
+        register("nvim_oxi_health.sub", || ok("first"))?;
+        register("nvim_oxi_health.sub", || ok("second"))?;
+
+        let report = checkhealth("nvim_oxi_health.sub")?;
+        assert!(report.contains("second") && !report.contains("first"));
+
+        assert!(register("nvim oxi", || Ok(())).is_err());
+
+        Ok(())
+    }
+}
@@ -1,14 +1,78 @@
+#[cfg(not(any(feature = "neovim-0-8", feature = "neovim-0-9")))]
+compile_error!(
+    "one of the `neovim-0-8`, `neovim-0-9` or `neovim-nightly` features must \
+     be enabled"
+);
+
+// Lets `oxi-derive` refer to the crate as `::nvim_oxi` from inside it too.
+extern crate self as nvim_oxi;
+
 pub mod api;
+pub mod budget;
+pub mod completion;
+pub mod diagnostic;
+pub mod diff;
 mod error;
-mod lua;
+pub mod filetype;
+pub mod r#fn;
+pub mod folds;
+pub mod health;
+#[cfg(feature = "log")]
+pub mod logger;
+pub mod lsp;
+pub mod lua;
 mod macros;
+pub mod memo;
+#[cfg(feature = "mlua")]
+pub mod mlua;
+pub mod net;
 mod object;
+pub mod options;
+pub mod picker;
+pub mod search;
+pub mod state;
+pub mod statusline;
+#[cfg(feature = "test")]
+pub mod test;
 mod toplevel;
-
-pub use error::{Error, Result};
-pub use lua::{LuaFn, LuaFnMut, LuaFnOnce};
+pub mod treesitter;
+pub mod ui;
+
+pub use error::{Error, NvimError, NvimErrorKind, Result};
+pub use lua::{
+    normalize_integral_floats,
+    LuaFn,
+    LuaFnMut,
+    LuaFnOnce,
+    LuaRefValue,
+    Variadic,
+};
+#[cfg(feature = "alloc-stats")]
+pub use nvim_types::alloc::{alloc_stats, AllocCounts, AllocStats};
+pub use object::{from_object, to_array, to_dictionary, to_object};
+#[cfg(feature = "test")]
+pub use oxi_derive::test;
+pub use oxi_derive::{function, module};
 pub use toplevel::*;
 
+/// Items used by the code generated by the `module`, `function` and `test`
+/// macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use nvim_types::object::Object;
+
+    pub use crate::lua::{__entrypoint, __lazy_entrypoint};
+    use crate::object::FromObject;
+    #[cfg(feature = "test")]
+    pub use crate::test::runner::run as run_test;
+
+    /// Converts the argument `name` of a function exported with
+    /// `#[nvim_oxi::function]`.
+    pub fn arg<T: FromObject>(obj: Object, name: &str) -> crate::Result<T> {
+        T::from_obj(obj).map_err(|err| err.at_path_segment(name))
+    }
+}
+
 // #[no_mangle]
 // pub extern "C" fn test() -> *mut std::os::raw::c_char {
 //     api::get_mode()
@@ -59,10 +123,8 @@ pub use toplevel::*;
 // }
 use crate::api::Buffer;
 
-#[no_mangle]
-extern "C" fn luaopen_libnvim_oxi(lstate: *mut lua::lua_State) -> libc::c_int {
-    lua::init_state(lstate);
-
+#[module]
+fn libnvim_oxi() -> Result<()> {
     // let buf = api::create_buf(true, false).unwrap();
     // buf.set_option("modified", true).unwrap();
 
@@ -192,13 +254,16 @@ extern "C" fn luaopen_libnvim_oxi(lstate: *mut lua::lua_State) -> libc::c_int {
 
     let res = api::Buffer::current().create_user_command(
         "Fooooo",
-        LuaFn::from(|()| Ok(crate::print!("Foo!"))),
+        LuaFn::from(|()| {
+            crate::print!("Foo!");
+            Ok(())
+        }),
         &CreateCommandOpts::builder().build().unwrap(),
     );
 
     crate::print!("{res:?}");
 
-    0
+    Ok(())
 }
 
 // use nvim_oxi as nvim;
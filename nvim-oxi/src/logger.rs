@@ -0,0 +1,187 @@
+//! A [`log`] backend displaying the records logged by a plugin inside
+//! Neovim, or writing them to a file under `stdpath("log")`.
+//!
+//! ```ignore
+//! #[nvim_oxi::module]
+//! fn myplugin() -> nvim_oxi::Result<()> {
+//!     let opts = LoggerOpts::builder().level(log::LevelFilter::Debug).build();
+//!     nvim_oxi::logger::init("myplugin", opts.unwrap())?;
+//!     log::info!("loaded");
+//!     Ok(())
+//! }
+//! ```
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use derive_builder::Builder;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::api::global::opts::NotifyOpts;
+use crate::api::{self, types::LogLevel};
+use crate::r#fn::{self, StdPath};
+use crate::{Error, Result};
+
+/// Options passed to [`init`].
+#[derive(Clone, Debug, Builder)]
+#[builder(default)]
+pub struct LoggerOpts {
+    /// Records less severe than this are discarded.
+    level: LevelFilter,
+
+    /// Where the records are written to.
+    target: LogTarget,
+}
+
+impl Default for LoggerOpts {
+    fn default() -> Self {
+        Self { level: LevelFilter::Info, target: LogTarget::Notify }
+    }
+}
+
+impl LoggerOpts {
+    #[inline(always)]
+    pub fn builder() -> LoggerOptsBuilder {
+        LoggerOptsBuilder::default()
+    }
+}
+
+/// Where the [`Logger`] writes the records to.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LogTarget {
+    /// Displays the records with `vim.notify`, titled with the name of the
+    /// plugin.
+    Notify,
+
+    /// Displays the records in the message area with `nvim_echo`, adding
+    /// them to the message history.
+    Echo,
+
+    /// Appends the records to `{stdpath("log")}/{name}.log`.
+    File,
+}
+
+/// The logger installed by [`init`].
+pub struct Logger {
+    name: String,
+    level: LevelFilter,
+    sink: Sink,
+
+    /// The thread Neovim runs on. The API can't be called from other
+    /// threads, so their records are dropped unless they go to a file.
+    main_thread: ThreadId,
+}
+
+enum Sink {
+    Notify,
+    Echo,
+    File(Mutex<File>),
+}
+
+/// Installs a [`Logger`] for the plugin `name` as the global logger of the
+/// `log` crate. Has to be called from the thread Neovim runs on, usually in
+/// the plugin's entrypoint, and fails if a logger is already installed.
+pub fn init(name: impl Into<String>, opts: LoggerOpts) -> Result<()> {
+    let name = name.into();
+
+    let sink = match opts.target {
+        LogTarget::Notify => Sink::Notify,
+        LogTarget::Echo => Sink::Echo,
+        LogTarget::File => Sink::File(Mutex::new(open_log_file(&name)?)),
+    };
+
+    let logger = Logger {
+        name,
+        level: opts.level,
+        sink,
+        main_thread: thread::current().id(),
+    };
+
+    log::set_boxed_logger(Box::new(logger))
+        .map_err(|err| Error::LoggerError(err.to_string()))?;
+    log::set_max_level(opts.level);
+    Ok(())
+}
+
+fn open_log_file(name: &str) -> Result<File> {
+    let dir = r#fn::stdpath(StdPath::Log)?;
+    fs::create_dir_all(&dir)?;
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{name}.log")))?;
+    Ok(file)
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let msg = record.args().to_string();
+
+        match &self.sink {
+            Sink::File(file) => {
+                let secs = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_secs());
+                if let Ok(mut file) = file.lock() {
+                    let _ = writeln!(
+                        file,
+                        "[{secs}] {:<5} {}: {msg}",
+                        record.level(),
+                        record.target()
+                    );
+                }
+            },
+
+            _ if thread::current().id() != self.main_thread => {},
+
+            Sink::Notify => {
+                let opts = NotifyOpts::builder()
+                    .title(self.name.clone())
+                    .build()
+                    .expect("all fields have defaults");
+                let _ = api::notify(&msg, record.level().into(), &opts);
+            },
+
+            Sink::Echo => {
+                let hl_group = match record.level() {
+                    Level::Error => Some("ErrorMsg"),
+                    Level::Warn => Some("WarningMsg"),
+                    _ => None,
+                };
+                let text = format!("[{}] {msg}", self.name);
+                let _ = api::echo([(text, hl_group)], true);
+            },
+        }
+    }
+
+    fn flush(&self) {
+        if let Sink::File(file) = &self.sink {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.flush();
+            }
+        }
+    }
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Trace => Self::Trace,
+            Level::Debug => Self::Debug,
+            Level::Info => Self::Info,
+            Level::Warn => Self::Warn,
+            Level::Error => Self::Error,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Bindings to `vim.lsp` to send requests to the language servers attached
+//! to a buffer and handle their responses in Rust.
+//!
+//! Params are serialized and results deserialized with serde, so the types
+//! from crates like `lsp-types` can be used directly.
+
+mod types;
+
+use nvim_types::object::Object;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+pub use types::*;
+
+use crate::api::{self, Buffer};
+use crate::lua::LuaFnMut;
+use crate::object::{FromObject, ToObject};
+use crate::Result;
+
+/// A language server's response to a request.
+pub type Response<Res> = std::result::Result<Res, ResponseError>;
+
+/// Arguments passed to an LSP handler: the error, the result, the context
+/// and the config.
+pub(crate) type HandlerArgs = (Object, Object, Object, Object);
+
+/// Sends a request to every client attached to `buf`, calling `handler`
+/// with the response of each one of them.
+pub fn buf_request<Params, Res, F>(
+    buf: Buffer,
+    method: &str,
+    params: Params,
+    handler: F,
+) -> Result<()>
+where
+    Params: Serialize,
+    Res: DeserializeOwned,
+    F: FnMut(Response<Res>, HandlerContext) -> Result<()> + 'static,
+{
+    let params = params.to_obj()?;
+    let handler = to_lua_handler(handler);
+    let res = api::exec_lua(
+        "vim.lsp.buf_request(...)",
+        [Object::from(buf.0), method.into(), params, handler.clone().into()],
+    );
+    // `vim.lsp` keeps the function alive until every client has responded.
+    handler.unref();
+    res
+}
+
+/// Sets the default handler for `method`, used for the responses to the
+/// requests that don't specify one and for server notifications (in which
+/// case the result is the notification's params).
+pub fn set_handler<Res, F>(method: &str, handler: F) -> Result<()>
+where
+    Res: DeserializeOwned,
+    F: FnMut(Response<Res>, HandlerContext) -> Result<()> + 'static,
+{
+    let handler = to_lua_handler(handler);
+    let res = api::exec_lua(
+        "local method, handler = ...; vim.lsp.handlers[method] = handler",
+        [Object::from(method), handler.clone().into()],
+    );
+    // The function is kept alive by `vim.lsp.handlers` instead.
+    handler.unref();
+    res
+}
+
+fn to_lua_handler<Res, F>(mut handler: F) -> LuaFnMut<HandlerArgs, ()>
+where
+    Res: DeserializeOwned,
+    F: FnMut(Response<Res>, HandlerContext) -> Result<()> + 'static,
+{
+    LuaFnMut::from(move |(err, result, ctx, _config): HandlerArgs| {
+        let response = match Option::<ResponseError>::from_obj(err)? {
+            Some(err) => Err(err),
+            None => Ok(Res::from_obj(result)?),
+        };
+        handler(response, HandlerContext::from_obj(ctx)?)
+    })
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[nvim_oxi::test]
+    fn handlers_outlive_their_registry_refs() -> Result<()> {
+        let responses = Rc::new(RefCell::new(Vec::new()));
+
+        {
+            let responses = Rc::clone(&responses);
+            set_handler("oxi/test", move |res: Response<u32>, ctx| {
+                responses.borrow_mut().push((res, ctx.method));
+                Ok(())
+            })?;
+        }
+
+        api::exec_lua::<_, ()>(
+            "collectgarbage()
+             local ctx = { method = 'oxi/test', client_id = 1 }
+             vim.lsp.handlers['oxi/test'](nil, 42, ctx, {})
+             vim.lsp.handlers['oxi/test']({ code = 1, message = 'no' }, nil, \
+             ctx, {})",
+            [(); 0],
+        )?;
+
+        let err = ResponseError { code: 1, message: "no".into() };
+        assert_eq!(
+            *responses.borrow(),
+            [(Ok(42), "oxi/test".into()), (Err(err), "oxi/test".into())]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+use crate::api::Buffer;
+
+/// An error returned by a language server, see the `ResponseError` type of
+/// the LSP specification.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct ResponseError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// The context a handler is called with, see `:h lsp-handler`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct HandlerContext {
+    /// The method of the request or notification being handled.
+    pub method: String,
+
+    /// The id of the client that received the response.
+    pub client_id: u32,
+
+    /// The buffer the request was sent for, if any.
+    pub bufnr: Option<Buffer>,
+}
@@ -6,8 +6,8 @@ use std::marker::{PhantomData, PhantomPinned};
 
 use libc::{c_char, c_double, c_int, c_void, size_t};
 
+/// Opaque type for the state of the Lua interpreter.
 #[repr(C)]
-#[doc(hidden)]
 pub struct lua_State {
     _data: [u8; 0],
     _marker: PhantomData<(*mut u8, PhantomPinned)>,
@@ -21,6 +21,8 @@ pub(crate) const fn lua_upvalueindex(i: c_int) -> c_int {
     LUA_GLOBALSINDEX - i
 }
 
+pub(crate) const LUA_MULTRET: c_int = -1;
+
 pub(crate) const LUA_TNONE: c_int = -1;
 pub(crate) const LUA_TNIL: c_int = 0;
 pub(crate) const LUA_TBOOLEAN: c_int = 1;
@@ -49,16 +51,32 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_error
     pub(crate) fn lua_error(L: *mut lua_State) -> !;
 
-    // https://www.lua.org/manual/5.1/manual.html#lua_call
+    // https://www.lua.org/manual/5.1/manual.html#lua_createtable
+    pub(crate) fn lua_createtable(L: *mut lua_State, narr: c_int, nrec: c_int);
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_getfield
     pub(crate) fn lua_getfield(
         L: *mut lua_State,
         index: c_int,
         k: *const c_char,
     );
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_getmetatable
+    pub(crate) fn lua_getmetatable(L: *mut lua_State, index: c_int) -> c_int;
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_gettable
+    pub(crate) fn lua_gettable(L: *mut lua_State, index: c_int);
+
     // https://www.lua.org/manual/5.1/manual.html#lua_gettop
     pub(crate) fn lua_gettop(L: *mut lua_State) -> c_int;
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_rawequal
+    pub(crate) fn lua_rawequal(
+        L: *mut lua_State,
+        index1: c_int,
+        index2: c_int,
+    ) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_rawgeti
     pub(crate) fn lua_rawgeti(L: *mut lua_State, index: c_int, n: c_int);
 
@@ -68,6 +86,20 @@ extern "C" {
         size: size_t,
     ) -> *mut c_void;
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_next
+    pub(crate) fn lua_next(L: *mut lua_State, index: c_int) -> c_int;
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_objlen
+    pub(crate) fn lua_objlen(L: *mut lua_State, index: c_int) -> size_t;
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_pcall
+    pub(crate) fn lua_pcall(
+        L: *mut lua_State,
+        nargs: c_int,
+        nresults: c_int,
+        errfunc: c_int,
+    ) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_pushinteger
     pub(crate) fn lua_pushboolean(L: *mut lua_State, n: lua_Integer);
 
@@ -100,12 +132,37 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#lua_pushstring
     pub(crate) fn lua_pushstring(L: *mut lua_State, s: *const c_char);
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_pushvalue
+    pub(crate) fn lua_pushvalue(L: *mut lua_State, index: c_int);
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_rawset
+    pub(crate) fn lua_rawset(L: *mut lua_State, index: c_int);
+
     // https://www.lua.org/manual/5.1/manual.html#lua_rawseti
     pub(crate) fn lua_rawseti(L: *mut lua_State, index: c_int, n: c_int);
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_insert
+    pub(crate) fn lua_insert(L: *mut lua_State, index: c_int);
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_remove
+    pub(crate) fn lua_remove(L: *mut lua_State, index: c_int);
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_setfield
+    pub(crate) fn lua_setfield(
+        L: *mut lua_State,
+        index: c_int,
+        k: *const c_char,
+    );
+
+    // https://www.lua.org/manual/5.1/manual.html#lua_setmetatable
+    pub(crate) fn lua_setmetatable(L: *mut lua_State, index: c_int) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_settop
     pub(crate) fn lua_settop(L: *mut lua_State, index: c_int);
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_toboolean
+    pub(crate) fn lua_toboolean(L: *mut lua_State, index: c_int) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_tointeger
     pub(crate) fn lua_tointeger(
         L: *mut lua_State,
@@ -119,6 +176,9 @@ extern "C" {
         len: *mut size_t,
     ) -> *const c_char;
 
+    // https://www.lua.org/manual/5.1/manual.html#lua_tonumber
+    pub(crate) fn lua_tonumber(L: *mut lua_State, index: c_int) -> lua_Number;
+
     // https://www.lua.org/manual/5.1/manual.html#lua_touserdata
     pub(crate) fn lua_touserdata(
         L: *mut lua_State,
@@ -138,6 +198,12 @@ pub(crate) unsafe fn lua_getglobal(L: *mut lua_State, name: *const c_char) {
     lua_getfield(L, LUA_GLOBALSINDEX, name)
 }
 
+// https://www.lua.org/manual/5.1/manual.html#lua_setglobal
+#[inline(always)]
+pub(crate) unsafe fn lua_setglobal(L: *mut lua_State, name: *const c_char) {
+    lua_setfield(L, LUA_GLOBALSINDEX, name)
+}
+
 // https://www.lua.org/manual/5.1/manual.html#lua_pop
 #[inline(always)]
 pub(crate) unsafe fn lua_pop(L: *mut lua_State, n: c_int) {
@@ -158,6 +224,12 @@ extern "C" {
     // https://www.lua.org/manual/5.1/manual.html#luaL_error
     pub(crate) fn luaL_error(L: *mut lua_State, fmt: *const c_char, ...) -> !;
 
+    // https://www.lua.org/manual/5.1/manual.html#luaL_newmetatable
+    pub(crate) fn luaL_newmetatable(
+        L: *mut lua_State,
+        tname: *const c_char,
+    ) -> c_int;
+
     // https://www.lua.org/manual/5.1/manual.html#luaL_ref
     pub(crate) fn luaL_ref(L: *mut lua_State, t: c_int) -> c_int;
 
@@ -1,10 +1,15 @@
-use std::ffi::CStr;
+use std::cell::Cell;
+use std::ffi::{CStr, CString};
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 
-use libc::c_char;
+use libc::{c_char, c_int};
+use nvim_types::LuaRef;
 use once_cell::unsync::OnceCell;
 
 use super::ffi::*;
+use super::{LuaPoppable, LuaPushable};
+use crate::macros::cstr;
 
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L41
 const INTERNAL_CALL_MASK: u64 = 1u64 << (mem::size_of::<u64>() * 8 - 1);
@@ -16,13 +21,21 @@ const VIML_INTERNAL_CALL: u64 = INTERNAL_CALL_MASK;
 pub(crate) const LUA_INTERNAL_CALL: u64 = VIML_INTERNAL_CALL + 1;
 
 thread_local! {
-    static LUA: OnceCell<*mut lua_State> = OnceCell::new();
+    static LUA: OnceCell<*mut lua_State> = const { OnceCell::new() };
+
+    /// Used to generate the keys of the functions stored by
+    /// [`add_global_fn`].
+    static NEXT_GLOBAL_FN: Cell<u64> = const { Cell::new(0) };
 }
 
-/// Initializes the Lua state. It's only called once when the module is loaded.
+/// Initializes the Lua state. It's called every time the module is loaded,
+/// which happens more than once if the module is reloaded, always with the
+/// same state.
 #[inline(always)]
 pub(crate) fn init_state(lstate: *mut lua_State) {
-    LUA.with(|lua| lua.set(lstate).expect("couldn't initialize Lua state"));
+    LUA.with(|lua| {
+        lua.get_or_init(|| lstate);
+    });
 }
 
 /// Runs a piece of code with access to the raw Lua state. Calling this before
@@ -35,6 +48,93 @@ where
     LUA.with(move |lua| unsafe { fun(*(lua.get().unwrap_unchecked())) })
 }
 
+/// Stores the function referenced by `fun` under `key` in the global Lua
+/// table `table`, creating it if needed, so that it can be called from
+/// Vimscript as `v:lua.{table}.{key}()`.
+pub(crate) fn set_global_fn(table: &str, key: &str, fun: LuaRef) {
+    let table = CString::new(table).expect("no nul bytes in table name");
+    let key = CString::new(key).expect("no nul bytes in key");
+
+    with_state(move |lstate| unsafe {
+        lua_getglobal(lstate, table.as_ptr());
+
+        if lua_type(lstate, -1) == LUA_TNIL {
+            lua_pop(lstate, 1);
+            lua_createtable(lstate, 0, 0);
+            lua_pushvalue(lstate, -1);
+            lua_setglobal(lstate, table.as_ptr());
+        }
+
+        lua_rawgeti(lstate, LUA_REGISTRYINDEX, fun);
+        lua_setfield(lstate, -2, key.as_ptr());
+        lua_pop(lstate, 1);
+    })
+}
+
+/// Stores the function referenced by `fun` in the global Lua table `table`
+/// under a new key, which is returned. See [`replace_global_fn`].
+pub(crate) fn add_global_fn(table: &'static str, fun: LuaRef) -> String {
+    let id = NEXT_GLOBAL_FN.with(|next| next.replace(next.get() + 1));
+    let key = format!("f{id}");
+    replace_global_fn(table, &key, fun);
+    key
+}
+
+/// Stores the function referenced by `fun` under `key` in the global Lua
+/// table `table`, replacing the previous one. Unlike [`set_global_fn`] the
+/// table owns the function: `fun` is released right away, and the function
+/// is removed from the table when the plugin is unloaded.
+pub(crate) fn replace_global_fn(table: &'static str, key: &str, fun: LuaRef) {
+    set_global_fn(table, key, fun);
+    with_state(|lstate| unsafe { luaL_unref(lstate, LUA_REGISTRYINDEX, fun) });
+
+    let field = CString::new(key).expect("no nul bytes in key");
+    crate::on_unload(move || {
+        let table = CString::new(table).expect("no nul bytes in table name");
+        with_state(|lstate| unsafe {
+            lua_getglobal(lstate, table.as_ptr());
+            if lua_type(lstate, -1) == LUA_TTABLE {
+                lua_pushnil(lstate);
+                lua_setfield(lstate, -2, field.as_ptr());
+            }
+            lua_pop(lstate, 1);
+        });
+        Ok(())
+    });
+}
+
+/// Calls the function below the `nargs` values on top of the stack with
+/// them as arguments, popping all its return values as `R`, or the error it
+/// raised.
+pub(crate) unsafe fn pcall<R: LuaPoppable>(
+    lstate: *mut lua_State,
+    nargs: c_int,
+) -> crate::Result<R> {
+    let base = lua_gettop(lstate) - nargs - 1;
+
+    if lua_pcall(lstate, nargs, LUA_MULTRET, 0) != 0 {
+        return Err(pop_error(lstate));
+    }
+
+    R::pop_values(lstate, lua_gettop(lstate) - base)
+}
+
+/// Pops an error raised by Lua code, which can be any value, converting it
+/// to a string with `tostring`.
+unsafe fn pop_error(lstate: *mut lua_State) -> crate::Error {
+    if lua_type(lstate, -1) != LUA_TSTRING {
+        lua_getglobal(lstate, cstr!("tostring"));
+        lua_insert(lstate, -2);
+        // If `__tostring` raises, its error is used instead.
+        lua_pcall(lstate, 1, 1, 0);
+    }
+
+    match <String as LuaPoppable>::pop(lstate) {
+        Ok(msg) => crate::Error::LuaError(msg),
+        Err(err) => err,
+    }
+}
+
 /// Pretty prints the contents of the Lua stack to the Neovim message area.
 #[allow(dead_code)]
 pub(crate) unsafe fn debug_stack(lstate: *mut lua_State) {
@@ -59,6 +159,296 @@ pub(super) unsafe fn handle_error(
     err: crate::Error,
 ) -> ! {
     let msg = err.to_string();
+    drop(err);
     lua_pushlstring(lstate, msg.as_ptr() as *const c_char, msg.len());
+    // `lua_error` longjmps out of this frame without running destructors.
+    drop(msg);
     lua_error(lstate);
 }
+
+/// Runs the body of a C function called by Lua, raising the error it
+/// returns as a Lua error.
+///
+/// Unwinding out of an `extern "C"` function is undefined behavior, so a
+/// panic in `fun` is caught and raised as a Lua error too. See
+/// [`setup_panic_hook`](crate::setup_panic_hook) to also print where it
+/// happened.
+pub(super) unsafe fn protect<F>(lstate: *mut lua_State, fun: F) -> c_int
+where
+    F: FnOnce() -> crate::Result<c_int>,
+{
+    panic::catch_unwind(AssertUnwindSafe(fun))
+        .unwrap_or_else(|payload| Err(crate::Error::from_panic(payload)))
+        .unwrap_or_else(|err| handle_error(lstate, err))
+}
+
+/// Releases what the previous load of `module` registered with
+/// [`on_unload`](crate::on_unload), if it's being reloaded. The resources
+/// may have already been deleted by the user, so errors are ignored.
+fn release_previous_load(module: &'static str) {
+    let _ = crate::toplevel::unload_module(module);
+}
+
+/// Called by the `luaopen_*` function generated by `#[nvim_oxi::module]`
+/// for the Lua module `module`. Initializes the Lua state and pushes the
+/// value returned by `body`, which becomes the return value of `require`.
+#[doc(hidden)]
+pub unsafe fn __entrypoint<R, F>(
+    lstate: *mut lua_State,
+    module: &'static str,
+    body: F,
+) -> c_int
+where
+    R: LuaPushable,
+    F: FnOnce() -> crate::Result<R>,
+{
+    init_state(lstate);
+
+    protect(lstate, || {
+        release_previous_load(module);
+        crate::toplevel::loading(module, body).and_then(|ret| ret.push(lstate))
+    })
+}
+
+/// The state of a `#[nvim_oxi::module(lazy)]` module, shared by the
+/// `__index` metamethod of the table returned by `require` and by its stubs.
+enum Lazy<F> {
+    Pending { module: &'static str, stubs: &'static [&'static str], body: F },
+    Loaded,
+    Failed,
+}
+
+/// Called by the `luaopen_*` function generated by
+/// `#[nvim_oxi::module(lazy)]`. Instead of running `body` right away, returns
+/// a proxy table holding a stub for each of the `stubs`. The first call to a
+/// stub or lookup of another field runs `body`, whose fields then replace
+/// the stubs.
+#[doc(hidden)]
+pub unsafe fn __lazy_entrypoint<R, F>(
+    lstate: *mut lua_State,
+    module: &'static str,
+    stubs: &'static [&'static str],
+    body: F,
+) -> c_int
+where
+    R: LuaPushable,
+    F: FnOnce() -> crate::Result<R> + 'static,
+{
+    init_state(lstate);
+
+    protect(lstate, || {
+        release_previous_load(module);
+        Ok(0)
+    });
+
+    lua_createtable(lstate, 0, stubs.len() as c_int);
+
+    // The macro always passes a function item or a closure that doesn't
+    // capture anything, so there's nothing to drop if the body is never run.
+    let ud = lua_newuserdata(lstate, mem::size_of::<Lazy<F>>());
+    std::ptr::write(ud as *mut Lazy<F>, Lazy::Pending { module, stubs, body });
+
+    // Stack: proxy, state.
+    for name in stubs {
+        lua_pushlstring(lstate, name.as_ptr() as *const c_char, name.len());
+        lua_pushvalue(lstate, -2);
+        lua_pushvalue(lstate, -4);
+        lua_pushvalue(lstate, -3);
+        lua_pushcclosure(lstate, lazy_stub::<R, F>, 3);
+        lua_rawset(lstate, -4);
+    }
+
+    lua_createtable(lstate, 0, 1);
+    lua_insert(lstate, -2);
+    lua_pushcclosure(lstate, lazy_index::<R, F>, 1);
+    lua_setfield(lstate, -2, cstr!("__index"));
+
+    lua_setmetatable(lstate, -2);
+    1
+}
+
+/// Runs the body of a lazy module if it hasn't run yet, moving its fields
+/// into the `proxy` table returned by `require` so that they can be iterated
+/// over with `pairs()`. Fields added to the module afterwards are still
+/// found through the proxy's `__index` metamethod.
+unsafe fn lazy_load<R, F>(
+    lstate: *mut lua_State,
+    lazy: &mut Lazy<F>,
+    proxy: c_int,
+) where
+    R: LuaPushable,
+    F: FnOnce() -> crate::Result<R>,
+{
+    let (module, stubs, body) = match mem::replace(lazy, Lazy::Failed) {
+        Lazy::Pending { module, stubs, body } => (module, stubs, body),
+        Lazy::Loaded => {
+            *lazy = Lazy::Loaded;
+            return;
+        },
+        Lazy::Failed => {
+            let msg = "the module's initialization already failed";
+            handle_error(lstate, crate::Error::LuaError(msg.into()));
+        },
+    };
+
+    protect(lstate, || {
+        crate::toplevel::loading(module, body).and_then(|ret| ret.push(lstate))
+    });
+
+    if lua_type(lstate, -1) != LUA_TTABLE {
+        let msg = "lazy modules have to return a table";
+        handle_error(lstate, crate::Error::LuaError(msg.into()));
+    }
+
+    // Stubs of functions the module doesn't have are removed.
+    for name in stubs {
+        lua_pushlstring(lstate, name.as_ptr() as *const c_char, name.len());
+        lua_pushnil(lstate);
+        lua_rawset(lstate, proxy);
+    }
+
+    // Stack: module.
+    lua_pushnil(lstate);
+    while lua_next(lstate, -2) != 0 {
+        lua_pushvalue(lstate, -2);
+        lua_insert(lstate, -2);
+        lua_rawset(lstate, proxy);
+    }
+
+    lua_getmetatable(lstate, proxy);
+    lua_insert(lstate, -2);
+    lua_setfield(lstate, -2, cstr!("__index"));
+    lua_pop(lstate, 1);
+
+    *lazy = Lazy::Loaded;
+}
+
+/// The `__index` metamethod of the table returned by [`__lazy_entrypoint`],
+/// called with the table and the key being looked up.
+unsafe extern "C" fn lazy_index<R, F>(lstate: *mut lua_State) -> c_int
+where
+    R: LuaPushable,
+    F: FnOnce() -> crate::Result<R>,
+{
+    let lazy =
+        &mut *(lua_touserdata(lstate, lua_upvalueindex(1)) as *mut Lazy<F>);
+
+    lazy_load::<R, F>(lstate, lazy, 1);
+
+    lua_pushvalue(lstate, 2);
+    lua_gettable(lstate, 1);
+    1
+}
+
+/// A stub registered by [`__lazy_entrypoint`], whose upvalues are the state
+/// of the module, the table returned by `require` and the name of the
+/// function it stands for. Loads the module and calls the function.
+unsafe extern "C" fn lazy_stub<R, F>(lstate: *mut lua_State) -> c_int
+where
+    R: LuaPushable,
+    F: FnOnce() -> crate::Result<R>,
+{
+    let nargs = lua_gettop(lstate);
+
+    let lazy =
+        &mut *(lua_touserdata(lstate, lua_upvalueindex(1)) as *mut Lazy<F>);
+
+    lua_pushvalue(lstate, lua_upvalueindex(2));
+    let proxy = lua_gettop(lstate);
+
+    lazy_load::<R, F>(lstate, lazy, proxy);
+
+    lua_pushvalue(lstate, lua_upvalueindex(3));
+    lua_gettable(lstate, proxy);
+
+    if lua_type(lstate, -1) == LUA_TNIL {
+        let name = CStr::from_ptr(lua_tolstring(
+            lstate,
+            lua_upvalueindex(3),
+            std::ptr::null_mut(),
+        ));
+        let msg = format!("the module has no `{}`", name.to_string_lossy());
+        handle_error(lstate, crate::Error::LuaError(msg));
+    }
+
+    // Stack: arguments, proxy, function.
+    lua_remove(lstate, proxy);
+    lua_insert(lstate, 1);
+    lua_call(lstate, nargs, LUA_MULTRET);
+    lua_gettop(lstate)
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use std::cell::Cell;
+
+    use nvim_types::{dictionary::Dictionary, object::Object};
+
+    use super::*;
+    use crate::lua::LuaFn;
+    use crate::{api, Result};
+
+    thread_local! {
+        static LOADED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    fn lazy_module() -> Result<Dictionary> {
+        LOADED.with(|loaded| loaded.set(true));
+        let greet =
+            LuaFn::<String, String>::from(|name| Ok(format!("hi {name}")));
+        Ok(Dictionary::from_iter([
+            ("greet", Object::from(greet)),
+            ("version", 1.into()),
+        ]))
+    }
+
+    const KEYS: &str = "local keys = {}
+         for key in pairs(oxi_lazy) do keys[#keys + 1] = key end
+         table.sort(keys)";
+
+    #[nvim_oxi::test]
+    fn global_fns_are_removed_on_unload() -> Result<()> {
+        let fun = LuaFn::<(), i64>::from(|()| Ok(42));
+        let key = add_global_fn("_oxi_test_fns", fun.0);
+
+        let code = format!("local f = _oxi_test_fns.{key} return f and f()");
+        assert_eq!(api::exec_lua::<_, Option<i64>>(&code, [(); 0])?, Some(42));
+
+        crate::unload()?;
+        assert_eq!(api::exec_lua::<_, Option<i64>>(&code, [(); 0])?, None);
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn lazy_modules_register_stubs() -> Result<()> {
+        with_state(|lstate| unsafe {
+            __lazy_entrypoint(
+                lstate,
+                "oxi_lazy",
+                &["greet", "missing"],
+                lazy_module,
+            );
+            lua_setglobal(lstate, cstr!("oxi_lazy"));
+        });
+
+        let keys: Vec<String> =
+            api::exec_lua(&format!("{KEYS} return keys"), [(); 0])?;
+        assert_eq!(keys, ["greet", "missing"]);
+        assert!(!LOADED.with(Cell::get));
+
+        let (greeting, keys): (String, Vec<String>) = api::exec_lua(
+            &format!(
+                "local greeting = oxi_lazy.greet('you')
+                 {KEYS}
+                 return {{ greeting, keys }}"
+            ),
+            [(); 0],
+        )?;
+        assert!(LOADED.with(Cell::get));
+        assert_eq!(greeting, "hi you");
+        assert_eq!(keys, ["greet", "version"]);
+
+        Ok(())
+    }
+}
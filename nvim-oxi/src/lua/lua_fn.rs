@@ -1,4 +1,6 @@
+use std::cell::Cell;
 use std::marker::PhantomData;
+use std::rc::Rc;
 use std::{fmt, mem, ptr};
 
 use libc::c_int;
@@ -66,10 +68,30 @@ from_fn_for_object!(LuaFn);
 from_fn_for_object!(LuaFnMut);
 from_fn_for_object!(LuaFnOnce);
 
+macro_rules! from_fn_for_resource {
+    ($name:ident) => {
+        impl<A, R> From<$name<A, R>> for crate::Resource
+        where
+            A: super::LuaPoppable,
+            R: super::LuaPushable,
+        {
+            fn from(fun: $name<A, R>) -> Self {
+                Self::LuaRef(fun.0)
+            }
+        }
+    };
+}
+
+from_fn_for_resource!(LuaFn);
+from_fn_for_resource!(LuaFnMut);
+from_fn_for_resource!(LuaFnOnce);
+
 macro_rules! create_ref {
     ($lstate:ident, $fun:ident, $cb:ident) => {
         super::with_state(move |$lstate| unsafe {
-            let fun = Box::new(move |l| $fun(A::pop(l)?)?.push(l));
+            let fun = Box::new(move |l| {
+                $fun(A::pop_values(l, lua_gettop(l))?)?.push(l)
+            });
             let ud = lua_newuserdata($lstate, mem::size_of::<$cb>());
             ptr::write(ud as *mut $cb, fun);
             lua_pushcclosure($lstate, c_fun, 1);
@@ -94,7 +116,7 @@ where
                 &**upv
             };
 
-            fun(lstate).unwrap_or_else(|err| super::handle_error(lstate, err))
+            super::protect(lstate, || fun(lstate))
         }
 
         let r#ref = create_ref!(lstate, fun, Cb);
@@ -119,7 +141,7 @@ where
                 &mut **upv
             };
 
-            fun(lstate).unwrap_or_else(|err| super::handle_error(lstate, err))
+            super::protect(lstate, || fun(lstate))
         }
 
         let r#ref = create_ref!(lstate, fun, CbMut);
@@ -145,7 +167,7 @@ where
                 Box::from_raw(&mut **upv)
             };
 
-            fun(lstate).unwrap_or_else(|err| super::handle_error(lstate, err))
+            super::protect(lstate, || fun(lstate))
         }
 
         let r#ref = create_ref!(lstate, fun, CbOnce);
@@ -154,6 +176,35 @@ where
     }
 }
 
+/// Where the output of a function created by `fn_once_with_output` is stored.
+pub(crate) type Output<R> = Rc<Cell<Option<Result<R>>>>;
+
+/// Creates a `LuaFnOnce` which, instead of returning the output of `fun` to
+/// Lua, stores it in the returned cell.
+///
+/// Useful for API functions that call a Lua function synchronously (e.g.
+/// `nvim_buf_call`), since the output can be retrieved right after the call
+/// without a round-trip through Lua.
+pub(crate) fn fn_once_with_output<F, R>(
+    fun: F,
+) -> (LuaFnOnce<(), ()>, Output<R>)
+where
+    F: FnOnce(()) -> Result<R> + 'static,
+    R: 'static,
+{
+    let output = Rc::new(Cell::new(None));
+
+    let fun = {
+        let output = Rc::clone(&output);
+        LuaFnOnce::from(move |()| {
+            output.set(Some(fun(())));
+            Ok(())
+        })
+    };
+
+    (fun, output)
+}
+
 macro_rules! unref {
     () => {
         /// Removes the stored reference from the Lua registry.
@@ -166,22 +217,12 @@ macro_rules! unref {
     };
 }
 
-macro_rules! call_body {
-    ($self:ident, $args:ident) => {
-        todo!()
-    };
-}
-
 impl<A, R> LuaFn<A, R>
 where
     A: super::LuaPoppable,
     R: super::LuaPushable,
 {
     unref!();
-
-    pub fn _call(&self, _args: A) -> crate::Result<R> {
-        call_body!(self, _args)
-    }
 }
 
 impl<A, R> LuaFnMut<A, R>
@@ -190,10 +231,6 @@ where
     R: super::LuaPushable,
 {
     unref!();
-
-    pub fn _call(&mut self, _args: A) -> crate::Result<R> {
-        call_body!(self, _args)
-    }
 }
 
 impl<A, R> LuaFnOnce<A, R>
@@ -202,8 +239,71 @@ where
     R: super::LuaPushable,
 {
     unref!();
+}
 
-    pub fn _call(self, _args: A) -> crate::Result<R> {
-        call_body!(self, _args)
-    }
+/// Defines a method calling the function with the given arguments, each of
+/// them pushed as a separate value.
+macro_rules! define_call {
+    ($call:ident, <$($gen:ident),*>, $args:ty, $($arg:ident: $ty:ty),*) => {
+        impl<$($gen,)* R> LuaFn<$args, R>
+        where
+            $args: super::LuaPoppable,
+            $($ty: super::LuaPushable,)*
+            R: super::LuaPushable + super::LuaPoppable,
+        {
+            /// Calls the function, returning an error if it raises one.
+            pub fn $call(&self, $($arg: $ty),*) -> Result<R> {
+                super::call_ref(self.0, move |_lstate| {
+                    Ok(0 $(+ unsafe { $arg.push(_lstate)? })*)
+                })
+            }
+        }
+
+        impl<$($gen,)* R> LuaFnMut<$args, R>
+        where
+            $args: super::LuaPoppable,
+            $($ty: super::LuaPushable,)*
+            R: super::LuaPushable + super::LuaPoppable,
+        {
+            /// Calls the function, returning an error if it raises one.
+            pub fn $call(&mut self, $($arg: $ty),*) -> Result<R> {
+                super::call_ref(self.0, move |_lstate| {
+                    Ok(0 $(+ unsafe { $arg.push(_lstate)? })*)
+                })
+            }
+        }
+
+        impl<$($gen,)* R> LuaFnOnce<$args, R>
+        where
+            $args: super::LuaPoppable,
+            $($ty: super::LuaPushable,)*
+            R: super::LuaPushable + super::LuaPoppable,
+        {
+            /// Calls the function, returning an error if it raises one. The
+            /// reference to the function is released afterwards.
+            pub fn $call(self, $($arg: $ty),*) -> Result<R> {
+                let res = super::call_ref(self.0, move |_lstate| {
+                    Ok(0 $(+ unsafe { $arg.push(_lstate)? })*)
+                });
+                self.unref();
+                res
+            }
+        }
+    };
 }
+
+define_call!(call0, <>, (),);
+define_call!(call1, <A>, A, a: A);
+define_call!(call2, <A, B>, (A, B), a: A, b: B);
+define_call!(call3, <A, B, C>, (A, B, C), a: A, b: B, c: C);
+define_call!(call4, <A, B, C, D>, (A, B, C, D), a: A, b: B, c: C, d: D);
+define_call!(
+    call5,
+    <A, B, C, D, E>,
+    (A, B, C, D, E),
+    a: A,
+    b: B,
+    c: C,
+    d: D,
+    e: E
+);
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use libc::c_int;
+use nvim_types::object::{Object, ObjectData, ObjectType};
+use nvim_types::LuaRef;
+use serde::{de, Deserialize};
+
+use super::ffi::*;
+use super::{LuaPoppable, LuaPushable};
+use crate::{Error, Result};
+
+/// A Lua function that was created outside of Rust, e.g. a callback stored
+/// by another plugin in one of its config tables, which can be called with
+/// arguments of type `A` and returns a value of type `R`.
+///
+/// It can be obtained from an `Object` of type `kObjectTypeLuaRef` or by
+/// deserializing a field holding a function. The reference is removed from
+/// the Lua registry when the value is dropped, while cloning it creates a
+/// new one.
+pub struct LuaRefValue<A = (), R = ()>(LuaRef, PhantomData<fn(A) -> R>);
+
+impl<A, R> Clone for LuaRefValue<A, R> {
+    fn clone(&self) -> Self {
+        let r#ref = super::with_state(|lstate| unsafe {
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+            luaL_ref(lstate, LUA_REGISTRYINDEX)
+        });
+        Self(r#ref, PhantomData)
+    }
+}
+
+impl<A, R> Drop for LuaRefValue<A, R> {
+    fn drop(&mut self) {
+        super::with_state(|lstate| unsafe {
+            luaL_unref(lstate, LUA_REGISTRYINDEX, self.0);
+        })
+    }
+}
+
+impl<A, R> fmt::Debug for LuaRefValue<A, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LuaRefValue").field(&self.0).finish()
+    }
+}
+
+impl<A, R> TryFrom<Object> for LuaRefValue<A, R> {
+    type Error = Error;
+
+    fn try_from(obj: Object) -> Result<Self> {
+        Ok(Self(obj.into_luaref()?, PhantomData))
+    }
+}
+
+impl<'de, A, R> Deserialize<'de> for LuaRefValue<A, R> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // Lua functions are deserialized as their registry reference.
+        LuaRef::deserialize(deserializer).map(|r#ref| Self(r#ref, PhantomData))
+    }
+}
+
+impl<A, R> LuaRefValue<A, R> {
+    /// Returns an `Object` referencing the same function, e.g. to pass it
+    /// back to an API function. The object shares the reference, so it
+    /// mustn't be used after `self` is dropped.
+    pub fn to_object(&self) -> Object {
+        Object {
+            r#type: ObjectType::kObjectTypeLuaRef,
+            data: ObjectData { luaref: self.0 },
+        }
+    }
+}
+
+impl<A, R> LuaRefValue<A, R>
+where
+    A: LuaPushable,
+    R: LuaPoppable,
+{
+    /// Calls the function, returning an error if it raises one.
+    pub fn call(&self, args: A) -> Result<R> {
+        super::call_ref(self.0, move |lstate| unsafe { args.push(lstate) })
+    }
+}
+
+/// Calls the function referenced by `luaref` with the arguments pushed by
+/// `push_args`, which returns how many it pushed, and pops its return
+/// values.
+pub(crate) fn call_ref<R, F>(luaref: LuaRef, push_args: F) -> Result<R>
+where
+    R: LuaPoppable,
+    F: FnOnce(*mut lua_State) -> Result<c_int>,
+{
+    super::with_state(move |lstate| unsafe {
+        let top = lua_gettop(lstate);
+
+        lua_rawgeti(lstate, LUA_REGISTRYINDEX, luaref);
+
+        let nargs = match push_args(lstate) {
+            Ok(nargs) => nargs,
+            Err(err) => {
+                lua_settop(lstate, top);
+                return Err(err);
+            },
+        };
+
+        super::pcall(lstate, nargs)
+    })
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+    use crate::api;
+    use crate::lua::Variadic;
+
+    fn is_function(r#ref: LuaRef) -> Result<bool> {
+        api::exec_lua(
+            "return type(debug.getregistry()[...]) == 'function'",
+            [r#ref],
+        )
+    }
+
+    #[nvim_oxi::test]
+    fn refs_are_owned() -> Result<()> {
+        let obj: Object =
+            api::exec_lua("return function(a) return a end", [(); 0])?;
+        let fun = LuaRefValue::<u32, u32>::try_from(obj)?;
+        let cloned = fun.clone();
+        assert_ne!(fun.0, cloned.0);
+
+        let r#ref = fun.0;
+        drop(fun);
+        assert!(!is_function(r#ref)?);
+
+        assert!(is_function(cloned.0)?);
+        assert_eq!(cloned.call(7)?, 7);
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn call_errors() -> Result<()> {
+        let obj: Object = api::exec_lua(
+            "return function(kind)
+               if kind == 'string' then error('oops', 0) end
+               error(setmetatable({}, { __tostring = function() return \
+             'custom' end }))
+             end",
+            [(); 0],
+        )?;
+        let fun = LuaRefValue::<Variadic<&str>, ()>::try_from(obj)?;
+
+        for (kind, expected) in [("string", "oops"), ("table", "custom")] {
+            match fun.call(Variadic(vec![kind])) {
+                Err(Error::LuaError(msg)) => assert_eq!(msg, expected),
+                other => panic!("unexpected result: {other:?}"),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,38 @@
 mod ffi;
+#[allow(clippy::module_inception)]
 mod lua;
 mod lua_fn;
+mod lua_ref;
 mod poppable;
 mod pushable;
+mod scope;
+mod table;
+mod userdata;
+mod variadic;
 
+pub use ffi::lua_State;
 pub(crate) use ffi::*;
 pub(crate) use lua::*;
+#[doc(hidden)]
+pub use lua::{__entrypoint, __lazy_entrypoint};
+pub(crate) use lua_fn::fn_once_with_output;
 pub use lua_fn::{LuaFn, LuaFnMut, LuaFnOnce};
-pub(crate) use poppable::LuaPoppable;
-pub(crate) use pushable::LuaPushable;
+pub(crate) use lua_ref::call_ref;
+pub use lua_ref::LuaRefValue;
+pub use oxi_derive::LuaPoppable;
+#[doc(hidden)]
+pub use poppable::__pop_deserialize;
+pub use poppable::normalize_integral_floats;
+pub use poppable::LuaPoppable;
+pub use pushable::LuaPushable;
+pub use scope::{scope, LuaStateRef};
+pub(crate) use table::require;
+pub use table::LuaTable;
+pub use userdata::{
+    create_userdata,
+    MetaMethod,
+    UserData,
+    UserDataMethods,
+    UserDataRef,
+};
+pub use variadic::Variadic;
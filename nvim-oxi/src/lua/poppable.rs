@@ -1,27 +1,106 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ffi::CStr;
 use std::ptr;
 use std::string::String as StdString;
 
-use nvim_types::BufHandle;
+use libc::c_int;
+use nvim_types::{
+    array::Array,
+    dictionary::Dictionary,
+    object::{Object, ObjectData, ObjectType},
+    string::String as NvimString,
+    BufHandle,
+    Integer,
+    TabHandle,
+    WinHandle,
+};
+use serde::de;
 
 use super::ffi::*;
-use crate::api::buffer::opts as bufopts;
-use crate::Result;
+use crate::api::types::Namespace;
+use crate::api::{Buffer, TabPage, Window};
+use crate::object::FromObject;
+use crate::statusline::ClickArgs;
+use crate::{Error, Result};
 
-#[doc(hidden)]
+thread_local! {
+    static NORMALIZE_FLOATS: Cell<bool> = const { Cell::new(true) };
+}
+
+/// Sets whether Lua numbers with no fractional part are converted to
+/// `Integer`s (the default) or kept as `Float`s when popping `Object`s off
+/// the Lua stack, e.g. when receiving a table from a Lua callback.
+///
+/// Since every Lua number is a float, disabling this means that a buffer
+/// handle inside a table will be received as a `Float`.
+pub fn normalize_integral_floats(enable: bool) {
+    NORMALIZE_FLOATS.with(|normalize| normalize.set(enable));
+}
+
+/// Trait implemented by values that can be received from Lua, e.g. as the
+/// arguments of the functions wrapped by [`LuaFn`](crate::LuaFn). Tuples
+/// are popped as multiple arguments, while other types are popped from a
+/// single value.
+///
+/// It can be derived for types implementing `Deserialize`, which are then
+/// popped from a Lua table:
+///
+/// ```ignore
+/// use nvim_oxi::lua::LuaPoppable;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, LuaPoppable)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let distance = LuaFn::from(|p: Point| Ok(p.x.hypot(p.y)));
+/// ```
 pub trait LuaPoppable: Sized {
     /// Assembles itself by popping values off the stack. Fails if there aren't
     /// enough values or if they are of the wrong type.
+    ///
+    /// The values it's made of are always removed from the stack, even if it
+    /// fails.
+    ///
+    /// # Safety
+    ///
+    /// `lstate` has to be a valid pointer to Neovim's Lua state.
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self>;
+
+    /// Pops the `n` values on top of the stack, e.g. the arguments passed to
+    /// a function or the values it returned. Types made of a single value
+    /// use the first one, and missing values are treated as `nil`s.
+    ///
+    /// # Safety
+    ///
+    /// `lstate` has to be a valid pointer to Neovim's Lua state with at least
+    /// `n` values on its stack.
+    unsafe fn pop_values(lstate: *mut lua_State, n: c_int) -> Result<Self> {
+        lua_settop(lstate, lua_gettop(lstate) - n + 1);
+        Self::pop(lstate)
+    }
 }
 
 impl LuaPoppable for () {
     unsafe fn pop(_lstate: *mut lua_State) -> Result<Self> {
         Ok(())
     }
+
+    unsafe fn pop_values(lstate: *mut lua_State, n: c_int) -> Result<Self> {
+        lua_pop(lstate, n);
+        Ok(())
+    }
 }
 
 impl LuaPoppable for lua_Integer {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        if lua_type(lstate, -1) != LUA_TNUMBER {
+            return Err(wrong_type(lstate, "integer"));
+        }
+
         let int = lua_tointeger(lstate, -1);
         lua_pop(lstate, 1);
         Ok(int)
@@ -46,9 +125,72 @@ impl LuaPoppable for usize {
     }
 }
 
+/// Implements `LuaPoppable` for integer types convertible from
+/// `lua_Integer`.
+macro_rules! pop_int {
+    ($($type:ty),+) => {
+        $(
+            impl LuaPoppable for $type {
+                unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+                    Ok(lua_Integer::pop(lstate)?.try_into()?)
+                }
+            }
+        )+
+    };
+}
+
+pop_int!(i8, u8, i16, u16, i64, u64);
+
+impl LuaPoppable for f64 {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        if lua_type(lstate, -1) != LUA_TNUMBER {
+            return Err(wrong_type(lstate, "float"));
+        }
+
+        let n = lua_tonumber(lstate, -1);
+        lua_pop(lstate, 1);
+        Ok(n)
+    }
+}
+
+impl LuaPoppable for bool {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        let b = lua_toboolean(lstate, -1) != 0;
+        lua_pop(lstate, 1);
+        Ok(b)
+    }
+}
+
+impl LuaPoppable for Buffer {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        BufHandle::pop(lstate).map(Into::into)
+    }
+}
+
+impl LuaPoppable for Namespace {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        u32::pop(lstate).map(Into::into)
+    }
+}
+
+impl LuaPoppable for Window {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        WinHandle::pop(lstate).map(Into::into)
+    }
+}
+
+impl LuaPoppable for TabPage {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        TabHandle::pop(lstate).map(Into::into)
+    }
+}
+
 impl LuaPoppable for StdString {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
-        // TODO: check type and return err?
+        if lua_type(lstate, -1) != LUA_TSTRING {
+            return Err(wrong_type(lstate, "String"));
+        }
+
         let mut size = 0;
         let ptr = lua_tolstring(lstate, -1, &mut size);
         let mut str = StdString::with_capacity(size);
@@ -59,85 +201,410 @@ impl LuaPoppable for StdString {
     }
 }
 
-impl<T: LuaPoppable> LuaPoppable for Option<T> {
+impl LuaPoppable for NvimString {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
-        let ltp = lua_type(lstate, -1);
-        crate::print!("{ltp}, {}", ltp != LUA_TNIL && ltp != LUA_TNONE);
+        // `lua_tolstring` would also accept numbers, converting them in
+        // place, and return null for anything else.
+        if lua_type(lstate, -1) != LUA_TSTRING {
+            return Err(wrong_type(lstate, "String"));
+        }
 
-        (ltp != LUA_TNIL && ltp != LUA_TNONE)
-            .then(|| T::pop(lstate))
-            .transpose()
+        let mut size = 0;
+        let ptr = lua_tolstring(lstate, -1, &mut size);
+        let bytes = std::slice::from_raw_parts(ptr as *const u8, size);
+        let string = NvimString::from_bytes(bytes.to_owned());
+        lua_pop(lstate, 1);
+        Ok(string)
     }
 }
 
-impl LuaPoppable for bufopts::OnLinesArgs {
+impl LuaPoppable for Object {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
-        let (h, i) = if lua_gettop(lstate) == 9 {
-            let h = usize::pop(lstate)?;
-            let i = usize::pop(lstate)?;
-            (Some(h), Some(i))
-        } else {
-            (None, None)
+        let obj = match lua_type(lstate, -1) {
+            LUA_TNIL | LUA_TNONE => Object::nil(),
+
+            LUA_TBOOLEAN => (lua_toboolean(lstate, -1) != 0).into(),
+
+            LUA_TNUMBER => {
+                let n = lua_tonumber(lstate, -1);
+                let is_integral = n.fract() == 0.0
+                    && n >= Integer::MIN as f64
+                    && n <= Integer::MAX as f64;
+
+                if is_integral && NORMALIZE_FLOATS.with(Cell::get) {
+                    (n as Integer).into()
+                } else {
+                    n.into()
+                }
+            },
+
+            LUA_TSTRING => return NvimString::pop(lstate).map(Into::into),
+
+            LUA_TTABLE => return pop_table(lstate),
+
+            LUA_TFUNCTION => {
+                lua_pushvalue(lstate, -1);
+                Object {
+                    r#type: ObjectType::kObjectTypeLuaRef,
+                    data: ObjectData {
+                        luaref: luaL_ref(lstate, LUA_REGISTRYINDEX),
+                    },
+                }
+            },
+
+            _ => {
+                let typename = CStr::from_ptr(luaL_typename(lstate, -1));
+                let err = Error::DeserializeError(format!(
+                    "can't convert a Lua {} to an Object",
+                    typename.to_string_lossy()
+                ));
+                lua_pop(lstate, 1);
+                return Err(err);
+            },
         };
-        let g = usize::pop(lstate)?;
-        let f = usize::pop(lstate)?;
-        let e = usize::pop(lstate)?;
-        let d = usize::pop(lstate)?;
-        let c = u32::pop(lstate)?;
-        let b = BufHandle::pop(lstate)?;
-        let a = <StdString as LuaPoppable>::pop(lstate)?;
 
-        Ok((a, b.into(), c, d, e, f, g, h, i))
+        lua_pop(lstate, 1);
+        Ok(obj)
     }
 }
 
-impl LuaPoppable for bufopts::OnBytesArgs {
+impl LuaPoppable for Array {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
-        // TODO: Check that nargs is 12?
-        // let nargs = lua_gettop(lstate);
+        Ok(Object::pop(lstate)?.try_into()?)
+    }
+}
 
-        let n = usize::pop(lstate)?;
-        let m = usize::pop(lstate)?;
-        let l = usize::pop(lstate)?;
-        let i = usize::pop(lstate)?;
-        let h = usize::pop(lstate)?;
-        let g = usize::pop(lstate)?;
-        let f = usize::pop(lstate)?;
-        let e = usize::pop(lstate)?;
-        let d = usize::pop(lstate)?;
-        let c = u32::pop(lstate)?;
-        let b = BufHandle::pop(lstate)?;
-        let a = <StdString as LuaPoppable>::pop(lstate)?;
+impl LuaPoppable for Dictionary {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        Ok(Object::pop(lstate)?.try_into()?)
+    }
+}
+
+/// Pops the table on top of the stack, which becomes an `Array` if it's a
+/// sequence (or it's empty) and a `Dictionary` if all its keys are strings.
+unsafe fn pop_table(lstate: *mut lua_State) -> Result<Object> {
+    let mut indexed = Vec::new();
+    let mut named = Vec::new();
+
+    lua_pushnil(lstate);
+    while lua_next(lstate, -2) != 0 {
+        let value = match Object::pop(lstate) {
+            Ok(value) => value,
+            Err(err) => {
+                // The value was popped, pop the key and the table.
+                lua_pop(lstate, 2);
+                return Err(err);
+            },
+        };
 
-        Ok((a, b.into(), c, d, e, f, g, h, i, l, m, n))
+        match lua_type(lstate, -1) {
+            LUA_TNUMBER => indexed.push((lua_tonumber(lstate, -1), value)),
+
+            LUA_TSTRING => {
+                let mut size = 0;
+                let ptr = lua_tolstring(lstate, -1, &mut size);
+                let key = std::slice::from_raw_parts(ptr as *const u8, size);
+                named.push((NvimString::from_bytes(key.to_owned()), value));
+            },
+
+            _ => {
+                // Pop the key and the table.
+                lua_pop(lstate, 2);
+                return Err(bad_table("non-string, non-integer keys"));
+            },
+        }
+    }
+
+    // Pop the table.
+    lua_pop(lstate, 1);
+
+    match (indexed.is_empty(), named.is_empty()) {
+        (_, true) => {
+            // `lua_next` traverses the table in no particular order.
+            indexed.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+            let is_sequence = indexed
+                .iter()
+                .enumerate()
+                .all(|(pos, &(idx, _))| idx == (pos + 1) as f64);
+
+            if !is_sequence {
+                return Err(bad_table("integer keys that aren't a sequence"));
+            }
+
+            Ok(indexed
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect::<Array>()
+                .into())
+        },
+
+        (true, false) => Ok(named.into_iter().collect::<Dictionary>().into()),
+
+        (false, false) => Err(bad_table("both integer and string keys")),
     }
 }
 
-impl LuaPoppable for bufopts::OnChangedtickArgs {
+#[inline]
+fn bad_table(reason: &str) -> Error {
+    Error::DeserializeError(format!(
+        "can't convert a Lua table with {reason} to an Object"
+    ))
+}
+
+impl<T: LuaPoppable> LuaPoppable for Option<T> {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
-        let c = u32::pop(lstate)?;
-        let b = BufHandle::pop(lstate)?;
-        let a = <StdString as LuaPoppable>::pop(lstate)?;
+        match lua_type(lstate, -1) {
+            LUA_TNIL | LUA_TNONE => {
+                lua_pop(lstate, 1);
+                Ok(None)
+            },
+            _ => T::pop(lstate).map(Some),
+        }
+    }
+}
 
-        Ok((a, b.into(), c))
+impl<T: LuaPoppable> LuaPoppable for Vec<T> {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        if lua_type(lstate, -1) != LUA_TTABLE {
+            return Err(not_a_table(lstate, "Vec"));
+        }
+
+        let len = lua_objlen(lstate, -1);
+        let mut vec = Vec::with_capacity(len);
+        for idx in 1..=len {
+            lua_rawgeti(lstate, -1, idx as _);
+            match T::pop(lstate) {
+                Ok(value) => vec.push(value),
+                Err(err) => {
+                    // Pop the table.
+                    lua_pop(lstate, 1);
+                    return Err(err);
+                },
+            }
+        }
+
+        // Pop the table.
+        lua_pop(lstate, 1);
+        Ok(vec)
     }
 }
 
-impl LuaPoppable for bufopts::OnDetachArgs {
+impl<T: LuaPoppable> LuaPoppable for HashMap<StdString, T> {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
-        let b = BufHandle::pop(lstate)?;
-        let a = <StdString as LuaPoppable>::pop(lstate)?;
+        if lua_type(lstate, -1) != LUA_TTABLE {
+            return Err(not_a_table(lstate, "HashMap"));
+        }
+
+        let mut map = HashMap::new();
 
-        Ok((a, b.into()))
+        lua_pushnil(lstate);
+        while lua_next(lstate, -2) != 0 {
+            let value = match T::pop(lstate) {
+                Ok(value) => value,
+                Err(err) => {
+                    // Pop the key and the table.
+                    lua_pop(lstate, 2);
+                    return Err(err);
+                },
+            };
+
+            // Calling `lua_tolstring` on a number would convert the key in
+            // place, confusing `lua_next`.
+            if lua_type(lstate, -1) != LUA_TSTRING {
+                // Pop the key and the table.
+                lua_pop(lstate, 2);
+                return Err(Error::DeserializeError(
+                    "can't convert a Lua table with non-string keys to a \
+                     HashMap"
+                        .into(),
+                ));
+            }
+
+            lua_pushvalue(lstate, -1);
+            let key = <StdString as LuaPoppable>::pop(lstate)?;
+            map.insert(key, value);
+        }
+
+        // Pop the table.
+        lua_pop(lstate, 1);
+        Ok(map)
     }
 }
 
-impl LuaPoppable for (StdString, StdString, usize) {
+#[inline]
+pub(super) unsafe fn not_a_table(lstate: *mut lua_State, into: &str) -> Error {
+    wrong_type(lstate, into)
+}
+
+/// Pops the value on top of the stack, returning an error saying it can't be
+/// converted to `into`.
+#[inline]
+pub(super) unsafe fn wrong_type(lstate: *mut lua_State, into: &str) -> Error {
+    let typename = CStr::from_ptr(luaL_typename(lstate, -1));
+    lua_pop(lstate, 1);
+    Error::DeserializeError(format!(
+        "can't convert a Lua {} to a {into}",
+        typename.to_string_lossy()
+    ))
+}
+
+/// Implements `LuaPoppable` for tuples, whose elements are the arguments
+/// passed to a function or the values it returned.
+macro_rules! pop_tuple {
+    ($n:literal, $($name:ident)+) => {
+        impl<$($name,)+> LuaPoppable for ($($name,)+)
+        where
+            $($name: LuaPoppable,)+
+        {
+            /// Pops the `$n` values on top of the stack, the first element
+            /// being the deepest one.
+            #[allow(non_snake_case)]
+            unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+                // The elements stay on the stack until they've all been
+                // popped, so the position of each one relative to the top
+                // doesn't change.
+                let mut idx = -$n - 1;
+                let tuple = (|| {
+                    $(
+                        idx += 1;
+                        lua_pushvalue(lstate, idx);
+                        let $name = $name::pop(lstate)?;
+                    )+
+                    Ok(($($name,)+))
+                })();
+
+                lua_pop(lstate, $n);
+                tuple
+            }
+
+            unsafe fn pop_values(
+                lstate: *mut lua_State,
+                n: c_int,
+            ) -> Result<Self> {
+                // Missing values become `nil`s and extra ones, e.g.
+                // arguments added by newer Neovim versions, are discarded.
+                lua_settop(lstate, lua_gettop(lstate) - n + $n);
+                Self::pop(lstate)
+            }
+        }
+    };
+}
+
+pop_tuple!(1, A);
+pop_tuple!(2, A B);
+pop_tuple!(3, A B C);
+pop_tuple!(4, A B C D);
+pop_tuple!(5, A B C D E);
+pop_tuple!(6, A B C D E F);
+pop_tuple!(7, A B C D E F G);
+pop_tuple!(8, A B C D E F G H);
+pop_tuple!(9, A B C D E F G H I);
+pop_tuple!(10, A B C D E F G H I J);
+pop_tuple!(11, A B C D E F G H I J K);
+pop_tuple!(12, A B C D E F G H I J K L);
+
+impl LuaPoppable for ClickArgs {
     unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
-        let c = usize::pop(lstate)?;
-        let b = <StdString as LuaPoppable>::pop(lstate)?;
-        let a = <StdString as LuaPoppable>::pop(lstate)?;
+        let (minwid, clicks, button, mods) =
+            <(i32, u32, StdString, StdString)>::pop(lstate)?;
+
+        Self::from_raw(minwid, clicks, &button, &mods)
+    }
+
+    unsafe fn pop_values(lstate: *mut lua_State, n: c_int) -> Result<Self> {
+        let (minwid, clicks, button, mods) =
+            <(i32, u32, StdString, StdString)>::pop_values(lstate, n)?;
+
+        Self::from_raw(minwid, clicks, &button, &mods)
+    }
+}
+
+/// Pops a value by deserializing the `Object` on top of the stack, used by
+/// `#[derive(LuaPoppable)]`.
+#[doc(hidden)]
+pub unsafe fn __pop_deserialize<T>(lstate: *mut lua_State) -> Result<T>
+where
+    T: de::DeserializeOwned,
+{
+    T::from_obj(Object::pop(lstate)?)
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use crate::lua::{LuaFn, Variadic};
+    use crate::{api, Result};
+
+    #[nvim_oxi::test]
+    fn pop_arguments() -> Result<()> {
+        let add =
+            LuaFn::from(|(a, b): (u32, Option<u32>)| Ok(a + b.unwrap_or(0)));
+
+        // Missing arguments are `nil`s and extra ones are ignored.
+        let sums: Vec<u32> = api::exec_lua(
+            "local add = ... return { add(1, 2), add(3), add(4, 5, 6) }",
+            [add],
+        )?;
+        assert_eq!(sums, [3, 3, 9]);
+
+        let first = LuaFn::<String, String>::from(Ok);
+        let first: String = api::exec_lua(
+            "local first = ... return first('a', 'b')",
+            [first],
+        )?;
+        assert_eq!(first, "a");
 
-        Ok((a, b, c))
+        let count = LuaFn::from(|v: Variadic<u32>| Ok(v.len()));
+        let count: usize =
+            api::exec_lua("local count = ... return count(1, 2, 3)", [count])?;
+        assert_eq!(count, 3);
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn pop_wrong_types() -> Result<()> {
+        let strings = LuaFn::<(Vec<String>, String), ()>::from(|_| Ok(()));
+
+        let ok: Vec<bool> = api::exec_lua(
+            "local strings = ...
+             return {
+               pcall(strings, { 'a' }, 'b'),
+               pcall(strings, { 'a', 1 }, 'b'),
+               (pcall(strings, { 'a' }, 1.5)),
+             }",
+            [strings],
+        )?;
+        assert_eq!(ok, [true, false, false]);
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn pop_errors_leave_the_stack_balanced() -> Result<()> {
+        use nvim_types::object::Object;
+
+        use super::*;
+
+        crate::lua::with_state(|lstate| unsafe {
+            let top = lua_gettop(lstate);
+
+            lua_pushlightuserdata(lstate, ptr::null_mut());
+            assert!(Object::pop(lstate).is_err());
+            assert_eq!(lua_gettop(lstate), top);
+
+            // `{ 1, { light_userdata } }`
+            lua_createtable(lstate, 2, 0);
+            lua_pushinteger(lstate, 1);
+            lua_rawseti(lstate, -2, 1);
+            lua_createtable(lstate, 1, 0);
+            lua_pushlightuserdata(lstate, ptr::null_mut());
+            lua_rawseti(lstate, -2, 1);
+            lua_rawseti(lstate, -2, 2);
+            assert!(Object::pop(lstate).is_err());
+            assert_eq!(lua_gettop(lstate), top);
+        });
+
+        Ok(())
     }
 }
@@ -1,53 +1,100 @@
 use libc::{c_char, c_int};
+use nvim_types::{
+    array::Array,
+    dictionary::Dictionary,
+    object::{Object, ObjectType},
+    string::String as NvimString,
+};
 
 use super::ffi::*;
 use crate::object::ToObject;
 
-#[doc(hidden)]
+/// Trait implemented by values that can be returned to Lua, e.g. from the
+/// functions wrapped by [`LuaFn`](crate::LuaFn). Every `Serialize` type
+/// implements it, with sequences (including tuples) and maps pushed as Lua
+/// tables.
 pub trait LuaPushable {
     /// Pushes all its values on the Lua stack, returning the number of values
     /// that it pushed.
+    ///
+    /// # Safety
+    ///
+    /// `lstate` has to be a valid pointer to Neovim's Lua state.
     unsafe fn push(self, lstate: *mut lua_State) -> crate::Result<c_int>;
 }
 
 impl<T: ToObject> LuaPushable for T {
     unsafe fn push(self, lstate: *mut lua_State) -> crate::Result<c_int> {
-        let obj = self.to_obj()?;
+        push_object(lstate, self.to_obj()?)?;
+        Ok(1)
+    }
+}
 
-        use nvim_types::object::ObjectType::*;
-        match obj.r#type {
-            kObjectTypeNil => lua_pushnil(lstate),
+/// Pushes an `Object` on the stack, converting arrays and dictionaries into
+/// tables. Nothing is left on the stack if it fails.
+unsafe fn push_object(
+    lstate: *mut lua_State,
+    obj: Object,
+) -> crate::Result<()> {
+    use ObjectType::*;
+    match obj.r#type {
+        kObjectTypeNil => lua_pushnil(lstate),
 
-            kObjectTypeBoolean => {
-                let n = if obj.data.boolean { 1 } else { 0 };
-                lua_pushboolean(lstate, n);
-            },
+        kObjectTypeBoolean => {
+            let n = if obj.data.boolean { 1 } else { 0 };
+            lua_pushboolean(lstate, n);
+        },
 
-            kObjectTypeInteger => {
-                let n = obj.data.integer.try_into()?;
-                lua_pushinteger(lstate, n);
-            },
+        kObjectTypeInteger => {
+            let n = obj.data.integer.try_into()?;
+            lua_pushinteger(lstate, n);
+        },
 
-            kObjectTypeFloat => {
-                lua_pushnumber(lstate, obj.data.float);
-            },
+        kObjectTypeFloat => {
+            lua_pushnumber(lstate, obj.data.float);
+        },
 
-            kObjectTypeString => {
-                let string = &obj.data.string;
-                lua_pushlstring(
-                    lstate,
-                    string.data as *const c_char,
-                    string.size,
-                );
-            },
+        kObjectTypeString => push_string(lstate, &obj.data.string),
 
-            kObjectTypeArray => todo!(),
+        kObjectTypeArray => {
+            let array = Array::try_from(obj)?;
+            lua_createtable(lstate, array.len() as _, 0);
 
-            kObjectTypeDictionary => todo!(),
+            for (idx, obj) in array.into_iter().enumerate() {
+                if let Err(err) = push_object(lstate, obj) {
+                    // Pop the table.
+                    lua_pop(lstate, 1);
+                    return Err(err);
+                }
+                lua_rawseti(lstate, -2, (idx + 1) as _);
+            }
+        },
 
-            kObjectTypeLuaRef => panic!("trying to return Lua function"),
-        }
+        kObjectTypeDictionary => {
+            let dict = Dictionary::try_from(obj)?;
+            lua_createtable(lstate, 0, dict.len() as _);
 
-        Ok(1)
+            for (key, obj) in dict {
+                push_string(lstate, &key);
+                if let Err(err) = push_object(lstate, obj) {
+                    // Pop the key and the table.
+                    lua_pop(lstate, 2);
+                    return Err(err);
+                }
+                lua_rawset(lstate, -3);
+            }
+        },
+
+        kObjectTypeLuaRef => {
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, obj.data.luaref);
+        },
     }
+
+    Ok(())
+}
+
+#[inline]
+unsafe fn push_string(lstate: *mut lua_State, string: &NvimString) {
+    let bytes = string.as_bytes();
+    lua_pushlstring(lstate, bytes.as_ptr() as *const c_char, bytes.len());
 }
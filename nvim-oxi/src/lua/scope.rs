@@ -0,0 +1,52 @@
+use std::marker::PhantomData;
+
+use libc::c_int;
+
+use super::ffi::*;
+
+/// A reference to Neovim's raw Lua state, only valid for the duration of a
+/// [`scope`].
+pub struct LuaStateRef {
+    lstate: *mut lua_State,
+    // The state can only be used from the main thread.
+    _marker: PhantomData<*mut ()>,
+}
+
+impl LuaStateRef {
+    /// Returns the raw pointer to the Lua state, to be used with the C API.
+    pub fn as_ptr(&self) -> *mut lua_State {
+        self.lstate
+    }
+
+    /// Returns the index of the top element of the stack, i.e. the number of
+    /// elements in it.
+    pub fn top(&self) -> c_int {
+        unsafe { lua_gettop(self.lstate) }
+    }
+}
+
+/// Runs `fun` with access to the raw Lua state, e.g. to push or pop values
+/// not covered by the conversions done by the crate.
+///
+/// The closure has to leave the stack as it found it. This is checked in
+/// debug builds, where unbalanced stacks cause a panic.
+pub fn scope<F, R>(fun: F) -> R
+where
+    F: FnOnce(&LuaStateRef) -> R,
+{
+    super::with_state(move |lstate| {
+        let state = LuaStateRef { lstate, _marker: PhantomData };
+
+        let top = state.top();
+        let res = fun(&state);
+
+        debug_assert_eq!(
+            top,
+            state.top(),
+            "Lua stack went from {top} to {} elements inside `lua::scope`",
+            state.top()
+        );
+
+        res
+    })
+}
@@ -0,0 +1,173 @@
+use std::ffi::CString;
+use std::fmt;
+
+use libc::c_int;
+use nvim_types::object::{Object, ObjectData, ObjectType};
+use nvim_types::LuaRef;
+
+use super::ffi::*;
+use super::poppable::not_a_table;
+use super::{LuaPoppable, LuaPushable};
+use crate::macros::cstr;
+use crate::{Error, Result};
+
+/// A reference to a Lua table, e.g. the module of another plugin. Unlike a
+/// `Dictionary` its fields aren't copied, so functions stored in it can be
+/// called directly.
+#[derive(Copy, Clone)]
+pub struct LuaTable(LuaRef);
+
+impl fmt::Debug for LuaTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LuaTable").field(&self.0).finish()
+    }
+}
+
+impl LuaTable {
+    /// Returns the value of the field `key`.
+    pub fn get<V: LuaPoppable>(&self, key: &str) -> Result<V> {
+        let key = field_name(key)?;
+        super::with_state(move |lstate| unsafe {
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+            lua_getfield(lstate, -1, key.as_ptr());
+            // Remove the table, leaving only the field on the stack.
+            lua_remove(lstate, -2);
+            V::pop(lstate)
+        })
+    }
+
+    /// Calls the function stored in the field `name`, returning an error if
+    /// it raises one.
+    pub fn call<A, R>(&self, name: &str, args: A) -> Result<R>
+    where
+        A: LuaPushable,
+        R: LuaPoppable,
+    {
+        let key = field_name(name)?;
+        super::with_state(move |lstate| unsafe {
+            let top = lua_gettop(lstate);
+
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+            lua_getfield(lstate, -1, key.as_ptr());
+            lua_remove(lstate, -2);
+
+            if lua_type(lstate, -1) != LUA_TFUNCTION {
+                lua_settop(lstate, top);
+                return Err(Error::LuaError(format!(
+                    "field `{name}` is not a function"
+                )));
+            }
+
+            let nargs = match args.push(lstate) {
+                Ok(nargs) => nargs,
+                Err(err) => {
+                    lua_settop(lstate, top);
+                    return Err(err);
+                },
+            };
+
+            super::pcall(lstate, nargs)
+        })
+    }
+
+    /// Returns an `Object` referencing the same table.
+    pub fn to_object(&self) -> Object {
+        Object {
+            r#type: ObjectType::kObjectTypeLuaRef,
+            data: ObjectData { luaref: self.0 },
+        }
+    }
+
+    /// Removes the stored reference from the Lua registry.
+    pub fn unref(self) {
+        super::with_state(move |lstate| unsafe {
+            luaL_unref(lstate, LUA_REGISTRYINDEX, self.0);
+        })
+    }
+}
+
+impl From<LuaTable> for Object {
+    fn from(table: LuaTable) -> Self {
+        table.to_object()
+    }
+}
+
+impl LuaPoppable for LuaTable {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        if lua_type(lstate, -1) != LUA_TTABLE {
+            return Err(not_a_table(lstate, "LuaTable"));
+        }
+
+        Ok(Self(luaL_ref(lstate, LUA_REGISTRYINDEX)))
+    }
+}
+
+impl LuaPushable for LuaTable {
+    unsafe fn push(self, lstate: *mut lua_State) -> Result<c_int> {
+        lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+        Ok(1)
+    }
+}
+
+/// Calls `require(module)`, returning the module's table.
+pub(crate) fn require(module: &str) -> Result<LuaTable> {
+    super::with_state(move |lstate| unsafe {
+        lua_getglobal(lstate, cstr!("require"));
+        lua_pushlstring(lstate, module.as_ptr() as *const _, module.len());
+        super::pcall(lstate, 1)
+    })
+}
+
+#[inline]
+fn field_name(key: &str) -> Result<CString> {
+    CString::new(key).map_err(|_| {
+        Error::LuaError(format!("field name `{key:?}` contains a nul byte"))
+    })
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use nvim_types::object::Object;
+
+    use crate::lua::{self, Variadic};
+    use crate::{api, Error, Result};
+
+    #[nvim_oxi::test]
+    fn call_returns() -> Result<()> {
+        api::exec_lua::<_, ()>(
+            "package.loaded['oxi_call_returns'] = {
+               pair = function(a, b) return b, a end,
+               many = function(...) return ... end,
+               none = function() end,
+               fail = function() error({ code = 1 }) end,
+             }",
+            [(); 0],
+        )?;
+
+        let module = lua::require("oxi_call_returns")?;
+
+        let (b, a): (u32, String) = module
+            .call("pair", Variadic(vec![Object::from("a"), 2.into()]))?;
+        assert_eq!((b, a.as_str()), (2, "a"));
+
+        let Variadic(many): Variadic<u32> =
+            module.call("many", Variadic(vec![1, 2, 3]))?;
+        assert_eq!(many, [1, 2, 3]);
+
+        // Return values that aren't asked for are popped anyway.
+        let top =
+            || lua::with_state(|lstate| unsafe { lua::lua_gettop(lstate) });
+        let before = top();
+        module.call::<_, ()>("none", ())?;
+        module.call::<_, ()>("many", Variadic(vec![1, 2]))?;
+        assert_eq!(top(), before);
+
+        // Errors that aren't strings are converted with `tostring`.
+        let err = module.call::<_, ()>("fail", ()).unwrap_err();
+        assert!(
+            matches!(err, Error::LuaError(msg) if msg.starts_with("table: "))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,400 @@
+use std::any;
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::{fmt, mem, ptr};
+
+use libc::c_int;
+use nvim_types::object::{Object, ObjectData, ObjectType};
+use nvim_types::LuaRef;
+
+use super::ffi::*;
+use super::{LuaPoppable, LuaPushable};
+use crate::macros::cstr;
+use crate::{Error, Result};
+
+/// The alignment of the memory returned by `lua_newuserdata`.
+const MAX_ALIGN: usize = mem::align_of::<f64>();
+
+/// Trait implemented by Rust types that can be handed to Lua as userdata
+/// with [`create_userdata`].
+///
+/// ```ignore
+/// use nvim_oxi::lua::{self, MetaMethod, UserData, UserDataMethods};
+///
+/// struct Counter(u32);
+///
+/// impl UserData for Counter {
+///     fn add_methods(methods: &mut UserDataMethods<Self>) {
+///         methods.add_method("incr", |counter, by: u32| {
+///             counter.0 += by;
+///             Ok(counter.0)
+///         });
+///
+///         methods.add_meta_method(MetaMethod::ToString, |counter, ()| {
+///             Ok(format!("Counter({})", counter.0))
+///         });
+///     }
+/// }
+///
+/// let counter = lua::create_userdata(Counter(0));
+/// ```
+///
+/// The value is dropped when Lua garbage collects the userdata.
+pub trait UserData: Sized + 'static {
+    /// Registers the methods callable from Lua as `value:method(..)`, and the
+    /// metamethods of the userdata.
+    fn add_methods(_methods: &mut UserDataMethods<Self>) {}
+}
+
+type Method<T> = Box<dyn FnMut(&mut T, *mut lua_State) -> Result<c_int>>;
+
+/// The methods of a [`UserData`] type.
+pub struct UserDataMethods<T> {
+    methods: Vec<(String, Method<T>)>,
+    meta_methods: Vec<(MetaMethod, Method<T>)>,
+}
+
+impl<T: UserData> UserDataMethods<T> {
+    /// Adds a method called `name`, which receives a mutable reference to
+    /// the value and the rest of the arguments.
+    pub fn add_method<A, R, F>(&mut self, name: &str, fun: F)
+    where
+        A: LuaPoppable,
+        R: LuaPushable,
+        F: FnMut(&mut T, A) -> Result<R> + 'static,
+    {
+        self.methods.push((name.to_owned(), Self::wrap(fun)));
+    }
+
+    /// Adds a metamethod. The value is always the first operand, so binary
+    /// operators fail if the userdata appears on the right hand side.
+    pub fn add_meta_method<A, R, F>(&mut self, meta: MetaMethod, fun: F)
+    where
+        A: LuaPoppable,
+        R: LuaPushable,
+        F: FnMut(&mut T, A) -> Result<R> + 'static,
+    {
+        self.meta_methods.push((meta, Self::wrap(fun)));
+    }
+
+    fn wrap<A, R, F>(mut fun: F) -> Method<T>
+    where
+        A: LuaPoppable,
+        R: LuaPushable,
+        F: FnMut(&mut T, A) -> Result<R> + 'static,
+    {
+        Box::new(move |data, lstate| unsafe {
+            fun(data, A::pop_values(lstate, lua_gettop(lstate))?)?.push(lstate)
+        })
+    }
+}
+
+/// A Lua metamethod, see `:h lua-metatable` in Neovim or the Lua manual.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MetaMethod {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Pow,
+    Unm,
+    Concat,
+    Len,
+    Eq,
+    Lt,
+    Le,
+    Call,
+    ToString,
+}
+
+impl MetaMethod {
+    fn name(&self) -> *const libc::c_char {
+        use MetaMethod::*;
+        match self {
+            Add => cstr!("__add"),
+            Sub => cstr!("__sub"),
+            Mul => cstr!("__mul"),
+            Div => cstr!("__div"),
+            Mod => cstr!("__mod"),
+            Pow => cstr!("__pow"),
+            Unm => cstr!("__unm"),
+            Concat => cstr!("__concat"),
+            Len => cstr!("__len"),
+            Eq => cstr!("__eq"),
+            Lt => cstr!("__lt"),
+            Le => cstr!("__le"),
+            Call => cstr!("__call"),
+            ToString => cstr!("__tostring"),
+        }
+    }
+}
+
+/// A reference to a userdata created with [`create_userdata`].
+///
+/// It can be passed to Lua by converting it into an `Object` or returning it
+/// from a function, and received back as the argument of a function, which
+/// fails if the Lua value isn't a userdata of type `T`.
+///
+/// Each `UserDataRef` owns a slot in the Lua registry, released on drop.
+/// Cloning it creates a new slot referencing the same userdata. The value
+/// itself is dropped once Lua doesn't hold any other reference to it.
+pub struct UserDataRef<T>(LuaRef, PhantomData<T>);
+
+impl<T> Clone for UserDataRef<T> {
+    fn clone(&self) -> Self {
+        super::with_state(|lstate| unsafe {
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+            Self(luaL_ref(lstate, LUA_REGISTRYINDEX), PhantomData)
+        })
+    }
+}
+
+impl<T> Drop for UserDataRef<T> {
+    fn drop(&mut self) {
+        super::with_state(|lstate| unsafe {
+            luaL_unref(lstate, LUA_REGISTRYINDEX, self.0);
+        })
+    }
+}
+
+impl<T> fmt::Debug for UserDataRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("UserDataRef").field(&self.0).finish()
+    }
+}
+
+/// Moves `data` into a new Lua userdata.
+pub fn create_userdata<T: UserData>(data: T) -> UserDataRef<T> {
+    super::with_state(move |lstate| unsafe {
+        let ud = lua_newuserdata(lstate, userdata_size::<T>());
+        ptr::write(aligned::<T>(ud), RefCell::new(data));
+        push_metatable::<T>(lstate);
+        lua_setmetatable(lstate, -2);
+        UserDataRef(luaL_ref(lstate, LUA_REGISTRYINDEX), PhantomData)
+    })
+}
+
+impl<T: UserData> UserDataRef<T> {
+    /// Calls `fun` with a shared reference to the value, failing if it's
+    /// currently being mutated, e.g. by one of its methods.
+    pub fn with<F, R>(&self, fun: F) -> Result<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let cell = unsafe { &*self.as_ptr()? };
+        let data = cell.try_borrow().map_err(|_| borrow_error::<T>())?;
+        Ok(fun(&data))
+    }
+
+    /// Calls `fun` with a mutable reference to the value, failing if it's
+    /// currently borrowed.
+    pub fn with_mut<F, R>(&self, fun: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let cell = unsafe { &*self.as_ptr()? };
+        let mut data =
+            cell.try_borrow_mut().map_err(|_| borrow_error::<T>())?;
+        Ok(fun(&mut data))
+    }
+
+    /// Returns an `Object` holding a new reference to the same userdata.
+    pub fn to_object(&self) -> Object {
+        self.clone().into()
+    }
+
+    fn as_ptr(&self) -> Result<*mut RefCell<T>> {
+        // The userdata is kept alive by the reference, so the pointer stays
+        // valid after popping it.
+        let ud = super::with_state(|lstate| unsafe {
+            lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+            let ud = to_userdata::<T>(lstate, -1);
+            lua_pop(lstate, 1);
+            ud
+        });
+
+        if ud.is_null() {
+            Err(type_error::<T>())
+        } else {
+            Ok(ud)
+        }
+    }
+}
+
+/// The `Object` takes over the reference, e.g. to hand it to Neovim.
+impl<T: UserData> From<UserDataRef<T>> for Object {
+    fn from(data: UserDataRef<T>) -> Self {
+        let luaref = data.0;
+        mem::forget(data);
+        Object {
+            r#type: ObjectType::kObjectTypeLuaRef,
+            data: ObjectData { luaref },
+        }
+    }
+}
+
+impl<T: UserData> LuaPoppable for UserDataRef<T> {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        if to_userdata::<T>(lstate, -1).is_null() {
+            lua_pop(lstate, 1);
+            return Err(type_error::<T>());
+        }
+
+        Ok(Self(luaL_ref(lstate, LUA_REGISTRYINDEX), PhantomData))
+    }
+}
+
+impl<T: UserData> LuaPushable for UserDataRef<T> {
+    unsafe fn push(self, lstate: *mut lua_State) -> Result<c_int> {
+        lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+        Ok(1)
+    }
+}
+
+#[inline]
+fn borrow_error<T>() -> Error {
+    Error::StateBorrowError { ty: any::type_name::<T>() }
+}
+
+#[inline]
+fn type_error<T>() -> Error {
+    Error::DeserializeError(format!(
+        "expected a userdata of type {}",
+        any::type_name::<T>()
+    ))
+}
+
+/// The size of the userdata holding a `T`, with enough padding to align the
+/// value. Lua only guarantees the alignment of `LUAI_MAXALIGN`, i.e. of a
+/// double, a pointer or a long.
+#[inline]
+fn userdata_size<T>() -> usize {
+    let align = mem::align_of::<RefCell<T>>();
+    mem::size_of::<RefCell<T>>() + align.saturating_sub(MAX_ALIGN)
+}
+
+/// Returns the address of the value in the userdata starting at `ud`.
+#[inline]
+fn aligned<T>(ud: *mut libc::c_void) -> *mut RefCell<T> {
+    let offset = (ud as *mut u8).align_offset(mem::align_of::<RefCell<T>>());
+    (ud as *mut u8).wrapping_add(offset) as *mut RefCell<T>
+}
+
+fn metatable_name<T>() -> CString {
+    CString::new(format!("nvim_oxi::{}", any::type_name::<T>()))
+        .expect("type names don't contain nul bytes")
+}
+
+/// Pushes the metatable shared by every userdata of type `T`, creating it
+/// the first time.
+unsafe fn push_metatable<T: UserData>(lstate: *mut lua_State) {
+    if luaL_newmetatable(lstate, metatable_name::<T>().as_ptr()) == 0 {
+        return;
+    }
+
+    let mut methods =
+        UserDataMethods { methods: Vec::new(), meta_methods: Vec::new() };
+    T::add_methods(&mut methods);
+
+    lua_pushcfunction(lstate, gc::<T>);
+    lua_setfield(lstate, -2, cstr!("__gc"));
+
+    lua_createtable(lstate, 0, methods.methods.len() as _);
+    for (name, method) in methods.methods {
+        let name = CString::new(name).expect("no nul bytes in method name");
+        push_method(lstate, method);
+        lua_setfield(lstate, -2, name.as_ptr());
+    }
+    lua_setfield(lstate, -2, cstr!("__index"));
+
+    for (meta, method) in methods.meta_methods {
+        push_method(lstate, method);
+        lua_setfield(lstate, -2, meta.name());
+    }
+}
+
+/// Pushes a C closure calling `method`. The method is never freed since it
+/// lives as long as the metatable, i.e. forever.
+unsafe fn push_method<T: UserData>(lstate: *mut lua_State, method: Method<T>) {
+    let ud = lua_newuserdata(lstate, mem::size_of::<Method<T>>());
+    ptr::write(ud as *mut Method<T>, method);
+    lua_pushcclosure(lstate, call_method::<T>, 1);
+}
+
+unsafe extern "C" fn call_method<T: UserData>(
+    lstate: *mut lua_State,
+) -> c_int {
+    let method =
+        &mut *(lua_touserdata(lstate, lua_upvalueindex(1)) as *mut Method<T>);
+
+    let cell = to_userdata::<T>(lstate, 1);
+    if cell.is_null() {
+        let msg = format!("expected a {} as `self`", any::type_name::<T>());
+        super::handle_error(lstate, Error::DeserializeError(msg));
+    }
+
+    // Leave only the arguments on the stack.
+    lua_remove(lstate, 1);
+
+    super::protect(lstate, || match (*cell).try_borrow_mut() {
+        Ok(mut data) => method(&mut data, lstate),
+        Err(_) => Err(borrow_error::<T>()),
+    })
+}
+
+unsafe extern "C" fn gc<T: UserData>(lstate: *mut lua_State) -> c_int {
+    let ud = aligned::<T>(lua_touserdata(lstate, 1));
+    super::protect(lstate, || {
+        ptr::drop_in_place(ud);
+        Ok(0)
+    })
+}
+
+/// Returns a pointer to the value of the userdata at `idx`, or a null
+/// pointer if it isn't a userdata of type `T`.
+unsafe fn to_userdata<T: UserData>(
+    lstate: *mut lua_State,
+    idx: c_int,
+) -> *mut RefCell<T> {
+    let ud = lua_touserdata(lstate, idx);
+
+    if ud.is_null() || lua_getmetatable(lstate, idx) == 0 {
+        return ptr::null_mut();
+    }
+
+    lua_getfield(lstate, LUA_REGISTRYINDEX, metatable_name::<T>().as_ptr());
+    let is_t = lua_rawequal(lstate, -1, -2) != 0;
+    lua_pop(lstate, 2);
+
+    if is_t {
+        aligned::<T>(ud)
+    } else {
+        ptr::null_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(align(32))]
+    struct OverAligned;
+
+    #[test]
+    fn over_aligned_values_fit() {
+        let size = userdata_size::<OverAligned>();
+        let buf = vec![0u64; size / 8 + 8];
+
+        // Every address Lua could return, i.e. every multiple of the
+        // maximum alignment.
+        for start in 0..4 {
+            let ud = buf[start..].as_ptr() as *mut libc::c_void;
+            let value = aligned::<OverAligned>(ud);
+            let offset = value as usize - ud as usize;
+            assert_eq!(value as usize % 32, 0);
+            assert!(offset + mem::size_of::<RefCell<OverAligned>>() <= size);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::ops::{Deref, DerefMut};
+
+use libc::c_int;
+
+use super::ffi::*;
+use super::{LuaPoppable, LuaPushable};
+use crate::Result;
+
+/// A variable number of Lua values, e.g. the arguments of a function called
+/// with `...`.
+///
+/// When popped it takes every value it's given, so it can only be used as
+/// the whole argument type of a function, i.e. `LuaFn<Variadic<T>, R>`. When
+/// pushed, every item is pushed as a separate value, which can be used to
+/// return multiple values to Lua.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Variadic<T>(pub Vec<T>);
+
+impl<T> Deref for Variadic<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Variadic<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for Variadic<T> {
+    fn from(vec: Vec<T>) -> Self {
+        Self(vec)
+    }
+}
+
+impl<T> FromIterator<T> for Variadic<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> IntoIterator for Variadic<T> {
+    type IntoIter = std::vec::IntoIter<T>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T: LuaPoppable> LuaPoppable for Variadic<T> {
+    unsafe fn pop(lstate: *mut lua_State) -> Result<Self> {
+        Self::pop_values(lstate, lua_gettop(lstate))
+    }
+
+    unsafe fn pop_values(lstate: *mut lua_State, n: c_int) -> Result<Self> {
+        let mut values = Vec::with_capacity(n as usize);
+        for popped in 0..n {
+            match T::pop(lstate) {
+                Ok(value) => Vec::push(&mut values, value),
+                Err(err) => {
+                    lua_pop(lstate, n - popped - 1);
+                    return Err(err);
+                },
+            }
+        }
+        values.reverse();
+        Ok(Self(values))
+    }
+}
+
+impl<T: LuaPushable> LuaPushable for Variadic<T> {
+    unsafe fn push(self, lstate: *mut lua_State) -> Result<c_int> {
+        let mut pushed = 0;
+        for value in self.0 {
+            pushed += value.push(lstate)?;
+        }
+        Ok(pushed)
+    }
+}
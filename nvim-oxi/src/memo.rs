@@ -0,0 +1,170 @@
+//! Opt-in memoization of pure queries for the duration of a single
+//! iteration of the event loop.
+//!
+//! The functions in this module behave like their counterparts in
+//! [`api`](crate::api), except that the result of each distinct query is
+//! cached until the end of the current iteration, when a callback scheduled
+//! with [`schedule`](crate::schedule) clears the cache. This is useful when
+//! the same options or highlight groups are queried many times while
+//! rendering, e.g. by every segment of a statusline.
+//!
+//! Changes made by the plugin itself in the same iteration are not seen by
+//! later queries unless [`invalidate`] is called.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use nvim_types::{dictionary::Dictionary, object::Object};
+
+use crate::api::{self, opts::OptionValueOpts};
+#[cfg(feature = "neovim-0-9")]
+use crate::api::{opts::GetHighlightOpts, types::Namespace};
+use crate::object::FromObject;
+use crate::Result;
+
+thread_local! {
+    static CACHE: RefCell<HashMap<String, Object>> =
+        RefCell::new(HashMap::new());
+
+    static FLUSH_SCHEDULED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Memoized version of [`api::get_option_value`].
+pub fn get_option_value<Value>(
+    name: &str,
+    opts: &OptionValueOpts,
+) -> Result<Value>
+where
+    Value: FromObject,
+{
+    let mut key = format!("option\0{name}\0{opts:?}");
+
+    // Local options are read from the current buffer or window if `opts`
+    // doesn't name one, which can change within the same iteration.
+    if opts.buf.is_none() && opts.win.is_none() {
+        let (buf, win) = (api::get_current_buf(), api::get_current_win());
+        key.push_str(&format!("\0{buf:?}\0{win:?}"));
+    }
+
+    let obj = cached(key, || api::get_option_value::<Object>(name, opts))?;
+    Value::from_obj(obj)
+}
+
+/// Memoized version of [`api::get_hl`].
+#[cfg(feature = "neovim-0-9")]
+pub fn get_hl(ns: Namespace, opts: &GetHighlightOpts) -> Result<Dictionary> {
+    let key = format!("hl\0{ns:?}\0{opts:?}");
+    let obj = cached(key, || api::get_hl(ns, opts).map(Into::into))?;
+    Dictionary::from_obj(obj)
+}
+
+/// Memoized version of [`api::get_hl_by_name`].
+#[cfg(not(feature = "neovim-0-9"))]
+pub fn get_hl_by_name(name: &str, rgb: bool) -> Result<Dictionary> {
+    let key = format!("hl\0{name}\0{rgb}");
+    let obj = cached(key, || api::get_hl_by_name(name, rgb).map(Into::into))?;
+    Dictionary::from_obj(obj)
+}
+
+/// Memoized version of [`api::strwidth`].
+pub fn strwidth(text: &str) -> Result<usize> {
+    let key = format!("strwidth\0{text}");
+    let obj = cached(key, || api::strwidth(text).map(|w| (w as i64).into()))?;
+    usize::from_obj(obj)
+}
+
+/// Clears the cache right away, e.g. after setting an option which was
+/// already queried in the current iteration.
+pub fn invalidate() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// Returns the object cached under `key`, calling `query` to get it if it's
+/// missing.
+fn cached<F>(key: String, query: F) -> Result<Object>
+where
+    F: FnOnce() -> Result<Object>,
+{
+    if let Some(obj) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return Ok(obj);
+    }
+
+    let obj = query()?;
+    CACHE.with(|cache| cache.borrow_mut().insert(key, obj.clone()));
+    schedule_flush();
+    Ok(obj)
+}
+
+fn schedule_flush() {
+    if FLUSH_SCHEDULED.with(|scheduled| scheduled.replace(true)) {
+        return;
+    }
+
+    crate::schedule(|()| {
+        FLUSH_SCHEDULED.with(|scheduled| scheduled.set(false));
+        invalidate();
+        Ok(())
+    });
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+
+    fn tabstop() -> Result<u32> {
+        get_option_value("tabstop", &OptionValueOpts::default())
+    }
+
+    #[nvim_oxi::test]
+    fn cache_hits_and_invalidation() -> Result<()> {
+        api::exec("setlocal tabstop=3", false)?;
+        assert_eq!(tabstop()?, 3);
+
+        // Changes aren't seen until the cache is invalidated.
+        api::exec("setlocal tabstop=5", false)?;
+        assert_eq!(tabstop()?, 3);
+
+        invalidate();
+        assert_eq!(tabstop()?, 5);
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn local_options_follow_the_current_buffer() -> Result<()> {
+        api::exec("setlocal tabstop=3", false)?;
+        assert_eq!(tabstop()?, 3);
+
+        api::exec("enew | setlocal tabstop=7", false)?;
+        assert_eq!(tabstop()?, 7);
+
+        api::exec("buffer #", false)?;
+        assert_eq!(tabstop()?, 3);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "neovim-0-9")]
+    #[nvim_oxi::test]
+    fn highlights() -> Result<()> {
+        let opts =
+            GetHighlightOpts::builder().name("OxiMemo").build().unwrap();
+        let bold = || -> Result<Option<bool>> {
+            get_hl(Namespace::GLOBAL, &opts)?
+                .get("bold")
+                .map(|bold| bool::from_obj(bold.clone()))
+                .transpose()
+        };
+
+        api::exec("highlight OxiMemo gui=bold", false)?;
+        assert_eq!(bold()?, Some(true));
+
+        api::exec("highlight clear OxiMemo", false)?;
+        assert_eq!(bold()?, Some(true));
+
+        invalidate();
+        assert_eq!(bold()?, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Interoperability with the [`mlua`](https://docs.rs/mlua) crate, enabled by
+//! the `mlua` feature.
+//!
+//! The [`Lua`] handle returned by [`lua`] is bound to Neovim's own Lua state,
+//! so values created with it can be passed to the API after being converted
+//! with [`from_value`], and vice versa with [`to_value`]. `Object` also
+//! implements `mlua`'s [`FromLua`] and [`IntoLua`], so it can be used
+//! wherever `mlua` expects a convertible value.
+
+use ::mlua::{FromLua, IntoLua, Lua, Result as LuaResult, Value};
+use nvim_types::object::Object;
+use once_cell::unsync::OnceCell;
+
+use crate::lua;
+
+thread_local! {
+    static MLUA: OnceCell<&'static Lua> = const { OnceCell::new() };
+}
+
+/// Returns an [`mlua::Lua`](Lua) handle bound to Neovim's Lua state.
+pub fn lua() -> &'static Lua {
+    MLUA.with(|mlua| {
+        *mlua.get_or_init(|| {
+            lua::with_state(|lstate| unsafe {
+                Box::leak(Box::new(Lua::init_from_ptr(lstate.cast())))
+            })
+        })
+    })
+}
+
+/// Converts an [`Object`] into an [`mlua::Value`](Value). Lua references are
+/// converted into the values they point to.
+///
+/// This is the same as calling [`IntoLua::into_lua`] on the object, which
+/// also lets `Object`s be passed directly to `mlua` functions.
+pub fn to_value(lua: &Lua, obj: Object) -> LuaResult<Value<'_>> {
+    obj.into_lua(lua)
+}
+
+/// Converts an [`mlua::Value`](Value) into an [`Object`]. Functions are stored
+/// in the registry and converted into Lua references, while tables are
+/// converted into arrays if their keys are exactly the integers from 1 to
+/// their length and into dictionaries otherwise.
+///
+/// This is the same as calling [`FromLua::from_lua`] with the handle
+/// returned by [`lua`], which also lets `mlua` functions return `Object`s.
+pub fn from_value(value: Value<'_>) -> LuaResult<Object> {
+    Object::from_lua(value, lua())
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use nvim_types::{array::Array, dictionary::Dictionary};
+
+    use super::*;
+
+    fn eval(src: &str) -> LuaResult<Object> {
+        from_value(lua().load(src).eval()?)
+    }
+
+    #[nvim_oxi::test]
+    fn tables_to_objects() -> crate::Result<()> {
+        let seq = Array::try_from(eval("{ 'a', 'b', 'c' }").unwrap())?;
+        assert_eq!(seq.len(), 3);
+
+        // A hole and a string key make it a dictionary, which then can't
+        // have integer keys.
+        assert!(eval("{ 'a', nil, 'c', x = 1 }").is_err());
+
+        let dict =
+            Dictionary::try_from(eval("{ x = 1, y = { 2.5 } }").unwrap())?;
+        assert_eq!(dict.len(), 2);
+
+        Ok(())
+    }
+
+    #[nvim_oxi::test]
+    fn functions_round_trip() -> crate::Result<()> {
+        let fun = eval("function(n) return n * 2 end").unwrap();
+        let Value::Function(fun) = to_value(lua(), fun).unwrap() else {
+            panic!("expected a function");
+        };
+        assert_eq!(fun.call::<_, i64>(21).unwrap(), 42);
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! Local servers listening on a named pipe or a TCP socket, e.g. to let
+//! external tools control the editor.
+//!
+//! The servers run on Neovim's own event loop, and every callback is called
+//! on the main thread, so the API can be used freely inside them.
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+use nvim_types::{object::Object, string::String as NvimString};
+use once_cell::unsync::OnceCell;
+
+use crate::api;
+use crate::lua::{LuaFnMut, LuaPoppable, LuaTable, Variadic};
+use crate::Result;
+
+thread_local! {
+    /// The Lua module managing the uv handles, loaded by the first call.
+    static NET: OnceCell<LuaTable> = const { OnceCell::new() };
+}
+
+/// A server created with [`listen_pipe`] or [`listen_tcp`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Server {
+    id: u32,
+    port: Option<u16>,
+}
+
+impl Server {
+    /// The port the server is listening on. `None` for pipe servers.
+    ///
+    /// Useful to know which port was picked when listening on port 0.
+    #[inline]
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Stops accepting new connections. Already accepted connections stay
+    /// open.
+    pub fn close(self) -> Result<()> {
+        call("close", [Object::from(self.id)])
+    }
+}
+
+/// A connection accepted by a [`Server`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Connection {
+    id: u32,
+}
+
+impl Connection {
+    /// Queues `data` to be written to the other end.
+    pub fn write<D: AsRef<[u8]>>(&self, data: D) -> Result<()> {
+        let data = NvimString::from_bytes(data.as_ref().to_owned());
+        call("write", [Object::from(self.id), data.into()])
+    }
+
+    /// Starts reading from the connection, calling `on_data` with every
+    /// chunk of data received. It's called with `None` once the other end
+    /// closes the connection.
+    pub fn on_data<F>(&self, mut on_data: F) -> Result<()>
+    where
+        F: FnMut(Option<Vec<u8>>) -> Result<()> + 'static,
+    {
+        let on_data = LuaFnMut::<Option<NvimString>, ()>::from(
+            move |data: Option<NvimString>| {
+                on_data(data.map(NvimString::into_bytes))
+            },
+        );
+        let res = call(
+            "read_start",
+            [Object::from(self.id), on_data.clone().into()],
+        );
+        // The read callback holds on to the function until the connection
+        // is closed.
+        on_data.unref();
+        res
+    }
+
+    /// Closes the connection.
+    pub fn close(self) -> Result<()> {
+        call("close", [Object::from(self.id)])
+    }
+}
+
+/// Listens on the named pipe (a Unix domain socket on Unix) at `path`,
+/// calling `on_connection` with every connection accepted.
+pub fn listen_pipe<P, F>(path: P, on_connection: F) -> Result<Server>
+where
+    P: AsRef<Path>,
+    F: FnMut(Connection) -> Result<()> + 'static,
+{
+    let path = NvimString::from(path.as_ref().as_os_str().to_owned());
+    listen("pipe", path, None, on_connection)
+}
+
+/// Listens on the TCP socket `addr`, calling `on_connection` with every
+/// connection accepted. Binding to port 0 picks a free port, which can be
+/// retrieved with [`Server::port`].
+pub fn listen_tcp<F>(addr: SocketAddr, on_connection: F) -> Result<Server>
+where
+    F: FnMut(Connection) -> Result<()> + 'static,
+{
+    let ip = NvimString::from(addr.ip().to_string());
+    listen("tcp", ip, Some(addr.port()), on_connection)
+}
+
+fn listen<F>(
+    kind: &str,
+    address: NvimString,
+    port: Option<u16>,
+    mut on_connection: F,
+) -> Result<Server>
+where
+    F: FnMut(Connection) -> Result<()> + 'static,
+{
+    let on_connection =
+        LuaFnMut::<u32, ()>::from(move |id| on_connection(Connection { id }));
+
+    let res = call::<(u32, Option<u16>)>(
+        "listen",
+        [
+            Object::from(kind),
+            address.into(),
+            port.into(),
+            on_connection.into(),
+        ],
+    );
+    // The listen callback holds on to the function until the server is
+    // closed.
+    on_connection.unref();
+
+    let (id, port) = res?;
+    Ok(Server { id, port })
+}
+
+/// Calls the function `fun` of the Lua module managing the uv handles.
+fn call<R: LuaPoppable>(
+    fun: &str,
+    args: impl IntoIterator<Item = Object>,
+) -> Result<R> {
+    let net = NET.with(|net| {
+        net.get_or_try_init(|| {
+            api::exec_lua::<_, ()>(include_str!("./net.lua"), [(); 0])?;
+            crate::lua::require("nvim_oxi.net")
+        })
+        .copied()
+    })?;
+
+    net.call(fun, args.into_iter().collect::<Variadic<_>>())
+}
+
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+
+    #[nvim_oxi::test]
+    fn listen_and_close() -> Result<()> {
+        let server = listen_tcp("127.0.0.1:0".parse().unwrap(), |_| Ok(()))?;
+        let port = server.port().expect("tcp servers have a port");
+        assert_ne!(port, 0);
+
+        // The port is taken, so listening on it again fails.
+        let addr = SocketAddr::from(([127, 0, 0, 1], port));
+        assert!(listen_tcp(addr, |_| Ok(())).is_err());
+
+        server.close()?;
+        assert!(Connection { id: server.id }.write("closed").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+-- Pipe and TCP servers created from Rust. The uv handles are kept in a
+-- table shared by every plugin and identified by integer ids.
+local name = 'nvim_oxi.net'
+
+if package.loaded[name] then
+  return
+end
+
+local uv = vim.uv or vim.loop
+
+local M = { handles = {}, next_id = 1 }
+
+local function register(handle)
+  local id = M.next_id
+  M.next_id = id + 1
+  M.handles[id] = handle
+  return id
+end
+
+local function get(id)
+  return M.handles[id] or error('connection ' .. id .. ' is closed')
+end
+
+local function new_handle(kind)
+  if kind == 'pipe' then
+    return uv.new_pipe(false)
+  end
+  return uv.new_tcp()
+end
+
+-- Closing a handle releases its callbacks, and with them the Rust closures.
+function M.close(id)
+  local handle = M.handles[id]
+  M.handles[id] = nil
+  if handle and not handle:is_closing() then
+    handle:close()
+  end
+end
+
+-- Returns the id of the server and the port it's bound to, if it's a TCP
+-- server. `on_connection` is called on the main thread with the id of each
+-- new connection.
+function M.listen(kind, address, port, on_connection)
+  local server = new_handle(kind)
+
+  local ok, err = server:bind(address, port)
+  if ok then
+    ok, err = server:listen(128, function(listen_err)
+      if listen_err then
+        return
+      end
+      local client = new_handle(kind)
+      if not server:accept(client) then
+        client:close()
+        return
+      end
+      local id = register(client)
+      vim.schedule(function()
+        on_connection(id)
+      end)
+    end)
+  end
+
+  if not ok then
+    server:close()
+    error(err, 0)
+  end
+
+  local bound = kind == 'tcp' and server:getsockname().port or nil
+  return register(server), bound
+end
+
+function M.write(id, data)
+  get(id):write(data)
+end
+
+-- Calls `on_data` on the main thread with every chunk read from the
+-- connection, and with nil once it's closed by the other end.
+function M.read_start(id, on_data)
+  local ok, err = get(id):read_start(function(err, data)
+    if err or not data then
+      M.close(id)
+      data = nil
+    end
+    vim.schedule(function()
+      on_data(data)
+    end)
+  end)
+  if not ok then
+    error(err, 0)
+  end
+end
+
+package.loaded[name] = M
@@ -71,7 +71,23 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     {
         use ObjectType::*;
         let (variant, obj) = match self.obj.r#type {
-            kObjectTypeDictionary => todo!(),
+            kObjectTypeDictionary => {
+                let dict = ManuallyDrop::into_inner(unsafe {
+                    self.obj.data.dictionary
+                });
+
+                if dict.len() != 1 {
+                    return Err(de::Error::invalid_length(
+                        dict.len(),
+                        &"a dictionary with a single entry",
+                    ));
+                }
+
+                let (variant, obj) =
+                    dict.into_iter().next().expect("checked length");
+
+                (variant.into_string()?, Some(obj))
+            },
 
             kObjectTypeString => (
                 ManuallyDrop::into_inner(unsafe { self.obj.data.string })
@@ -96,7 +112,7 @@ impl<'de> de::Deserializer<'de> for Deserializer {
                 let iter =
                     ManuallyDrop::into_inner(unsafe { self.obj.data.array })
                         .into_iter();
-                let mut deserializer = SeqDeserializer { iter };
+                let mut deserializer = SeqDeserializer { iter, idx: 0 };
                 visitor.visit_seq(&mut deserializer)
             },
 
@@ -140,7 +156,8 @@ impl<'de> de::Deserializer<'de> for Deserializer {
                     self.obj.data.dictionary
                 })
                 .into_iter();
-                let mut deserializer = MapDeserializer { iter, obj: None };
+                let mut deserializer =
+                    MapDeserializer { iter, key: StdString::new(), obj: None };
                 visitor.visit_map(&mut deserializer)
             },
 
@@ -167,18 +184,31 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     #[inline]
     fn deserialize_newtype_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
+        // Lua references can only be told apart from integers by `Object`s,
+        // which expect them as a map with a single reserved key.
+        if name == nvim_types::OBJECT_NEWTYPE
+            && self.obj.r#type == ObjectType::kObjectTypeLuaRef
+        {
+            let luaref = unsafe { self.obj.data.luaref };
+            let pair = (nvim_types::LUAREF_KEY, luaref);
+            return visitor.visit_map(de::value::MapDeserializer::new(
+                std::iter::once(pair),
+            ));
+        }
+
         visitor.visit_newtype_struct(self)
     }
 }
 
 struct SeqDeserializer {
     iter: nvim_types::array::ArrayIter,
+    idx: usize,
 }
 
 impl<'de> de::SeqAccess<'de> for SeqDeserializer {
@@ -188,8 +218,13 @@ impl<'de> de::SeqAccess<'de> for SeqDeserializer {
     where
         T: de::DeserializeSeed<'de>,
     {
-        while let Some(obj) = self.iter.next() {
-            return seed.deserialize(Deserializer { obj }).map(Some);
+        if let Some(obj) = self.iter.next() {
+            let idx = self.idx;
+            self.idx += 1;
+            return seed
+                .deserialize(Deserializer { obj })
+                .map(Some)
+                .map_err(|err| err.at_path_segment(&format!("[{idx}]")));
         }
 
         Ok(None)
@@ -202,6 +237,7 @@ impl<'de> de::SeqAccess<'de> for SeqDeserializer {
 
 struct MapDeserializer {
     iter: nvim_types::dictionary::DictIter,
+    key: StdString,
     obj: Option<Object>,
 }
 
@@ -212,7 +248,8 @@ impl<'de> de::MapAccess<'de> for MapDeserializer {
     where
         K: de::DeserializeSeed<'de>,
     {
-        while let Some((name, obj)) = self.iter.next() {
+        if let Some((name, obj)) = self.iter.next() {
+            self.key = name.to_string_lossy().into_owned();
             self.obj = Some(obj);
             return seed
                 .deserialize(Deserializer { obj: name.into() })
@@ -227,7 +264,9 @@ impl<'de> de::MapAccess<'de> for MapDeserializer {
         V: de::DeserializeSeed<'de>,
     {
         match self.obj.take() {
-            Some(obj) => seed.deserialize(Deserializer { obj }),
+            Some(obj) => seed
+                .deserialize(Deserializer { obj })
+                .map_err(|err| err.at_path_segment(&self.key)),
             _ => Err(de::Error::custom("object is missing")),
         }
     }
@@ -250,13 +289,18 @@ impl<'de> de::EnumAccess<'de> for EnumDeserializer {
     where
         V: de::DeserializeSeed<'de>,
     {
+        let deserializer = VariantDeserializer {
+            variant: self.variant.clone(),
+            obj: self.obj,
+        };
         let variant = self.variant.into_deserializer();
-        let deserializer = VariantDeserializer { obj: self.obj };
         seed.deserialize(variant).map(|v| (v, deserializer))
     }
 }
 
 struct VariantDeserializer {
+    /// The name of the variant, added to the path of errors in its fields.
+    variant: StdString,
     obj: Option<Object>,
 }
 
@@ -268,7 +312,9 @@ impl<'de> de::VariantAccess<'de> for VariantDeserializer {
         T: de::DeserializeSeed<'de>,
     {
         match self.obj {
-            Some(obj) => seed.deserialize(Deserializer { obj }),
+            Some(obj) => seed
+                .deserialize(Deserializer { obj })
+                .map_err(|err| err.at_path_segment(&self.variant)),
 
             _ => Err(de::Error::invalid_type(
                 de::Unexpected::UnitVariant,
@@ -289,7 +335,8 @@ impl<'de> de::VariantAccess<'de> for VariantDeserializer {
             Some(obj) => de::Deserializer::deserialize_map(
                 Deserializer { obj },
                 visitor,
-            ),
+            )
+            .map_err(|err| err.at_path_segment(&self.variant)),
 
             _ => Err(de::Error::invalid_type(
                 de::Unexpected::UnitVariant,
@@ -306,7 +353,8 @@ impl<'de> de::VariantAccess<'de> for VariantDeserializer {
             Some(obj) => de::Deserializer::deserialize_seq(
                 Deserializer { obj },
                 visitor,
-            ),
+            )
+            .map_err(|err| err.at_path_segment(&self.variant)),
 
             _ => Err(de::Error::invalid_type(
                 de::Unexpected::UnitVariant,
@@ -326,3 +374,85 @@ impl<'de> de::VariantAccess<'de> for VariantDeserializer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::object::FromObject;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Opts {
+        #[serde(default)]
+        enable: bool,
+        window: Window,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Window {
+        #[serde(default = "default_width")]
+        width: u32,
+        border: Vec<String>,
+    }
+
+    fn default_width() -> u32 {
+        80
+    }
+
+    fn window(border: Object) -> Object {
+        Object::from_iter([(
+            "window",
+            Object::from_iter([("border", border)]),
+        )])
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let opts = Opts::from_obj(window(Object::from_iter(["a"]))).unwrap();
+        assert!(!opts.enable);
+        assert_eq!(opts.window.width, 80);
+    }
+
+    #[test]
+    fn unknown_fields_are_denied() {
+        let obj = Object::from_iter([
+            (
+                "window",
+                Object::from_iter([("border", Object::from_iter(["a"]))]),
+            ),
+            ("foo", Object::from(true)),
+        ]);
+        let err = Opts::from_obj(obj).unwrap_err().to_string();
+        assert!(err.contains("unknown field `foo`"), "{err}");
+    }
+
+    #[test]
+    fn errors_name_the_key_path() {
+        let border = Object::from_iter([Object::from("a"), Object::from(1)]);
+        let err = Opts::from_obj(window(border)).unwrap_err().to_string();
+        assert!(
+            err.starts_with("invalid value at `window.border[1]`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn errors_name_the_enum_variant() {
+        #[derive(Deserialize, Debug)]
+        #[serde(rename_all = "lowercase")]
+        #[allow(dead_code)]
+        enum Size {
+            Fixed { width: u32 },
+        }
+
+        let obj = Object::from_iter([(
+            "fixed",
+            Object::from_iter([("width", Object::from("wide"))]),
+        )]);
+        let err = Size::from_obj(obj).unwrap_err().to_string();
+        assert!(err.starts_with("invalid value at `fixed.width`"), "{err}");
+    }
+}
@@ -5,5 +5,46 @@ mod to_object;
 
 use de::Deserializer;
 pub(crate) use from_object::FromObject;
+use nvim_types::{array::Array, dictionary::Dictionary, object::Object};
 use ser::Serializer;
+use serde::{de::DeserializeOwned, Serialize};
 pub(crate) use to_object::ToObject;
+
+use crate::{Error, Result};
+
+/// Serializes any Rust value into a Neovim `Object`.
+///
+/// `Object`s, `Array`s and `Dictionary`s are themselves serializable, so they
+/// can be nested inside the value, Lua references included.
+pub fn to_object<T: Serialize + ?Sized>(value: &T) -> Result<Object> {
+    value.serialize(Serializer)
+}
+
+/// Serializes a Rust value into a `Dictionary`, failing if it isn't
+/// represented as one, e.g. because it's not a struct or a map.
+pub fn to_dictionary<T: Serialize + ?Sized>(value: &T) -> Result<Dictionary> {
+    let obj = to_object(value)?;
+    let ty = obj.r#type;
+    Dictionary::try_from(obj).map_err(|_| {
+        Error::SerializeError(format!("expected a dictionary, got {ty:?}"))
+    })
+}
+
+/// Serializes a Rust value into an `Array`, failing if it isn't represented
+/// as one, e.g. because it's not a sequence or a tuple.
+pub fn to_array<T: Serialize + ?Sized>(value: &T) -> Result<Array> {
+    let obj = to_object(value)?;
+    let ty = obj.r#type;
+    Array::try_from(obj).map_err(|_| {
+        Error::SerializeError(format!("expected an array, got {ty:?}"))
+    })
+}
+
+/// Deserializes a Neovim `Object` into any Rust value, the inverse of
+/// [`to_object`].
+///
+/// `Dictionary`s and `Array`s can be passed directly, which is handy to
+/// convert the options or the arguments of a callback into a typed struct.
+pub fn from_object<T: DeserializeOwned>(obj: impl Into<Object>) -> Result<T> {
+    T::deserialize(Deserializer { obj: obj.into() })
+}
@@ -1,25 +1,504 @@
-use nvim_types::object::Object;
-use serde::ser;
+use nvim_types::{
+    array::Array,
+    dictionary::Dictionary,
+    object::{Object, ObjectData, ObjectType},
+    string::String as NvimString,
+    LuaRef,
+};
+use serde::ser::{self, Serialize};
 
-use crate::Result;
+use crate::{Error, Result};
 
 /// A struct for serializing Rust values into Neovim `Object`s.
 #[derive(Debug)]
 pub(super) struct Serializer;
 
-// impl ser::Serializer for Serializer {
-//     type Error = crate::Error;
-//     type Ok = Object;
-//     type SerializeMap = ();
-//     type SerializeSeq = ();
-//     type SerializeStruct = ();
-//     type SerializeStructVariant = ();
-//     type SerializeTuple = ();
-//     type SerializeTupleStruct = ();
-//     type SerializeTupleVariant = ();
-
-//     #[inline]
-//     fn serialize_bool(self, value: bool) -> Result<Self::Ok> {
-//         Ok(value.into())
-//     }
-// }
+impl ser::Serializer for Serializer {
+    type Error = crate::Error;
+    type Ok = Object;
+    type SerializeMap = MapSerializer;
+    type SerializeSeq = SeqSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+
+    #[inline]
+    fn serialize_bool(self, value: bool) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_i8(self, value: i8) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_i16(self, value: i16) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_i32(self, value: i32) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_i64(self, value: i64) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_u8(self, value: u8) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_u16(self, value: u16) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_u32(self, value: u32) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_u64(self, value: u64) -> Result<Object> {
+        Ok(i64::try_from(value)?.into())
+    }
+
+    #[inline]
+    fn serialize_f32(self, value: f32) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_f64(self, value: f64) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_char(self, value: char) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_str(self, value: &str) -> Result<Object> {
+        Ok(value.into())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, value: &[u8]) -> Result<Object> {
+        Ok(NvimString::from_bytes(value.to_owned()).into())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Object> {
+        Ok(Object::nil())
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Object>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Object> {
+        Ok(Object::nil())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Object> {
+        Ok(Object::nil())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Object> {
+        Ok(variant.into())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Object>
+    where
+        T: ?Sized + Serialize,
+    {
+        let obj = value.serialize(self)?;
+
+        // `Object`s serialize their Lua references as integers wrapped in
+        // this newtype.
+        if name == nvim_types::OBJECT_NEWTYPE {
+            let luaref = LuaRef::try_from(obj).map_err(|err| {
+                Error::SerializeError(format!("invalid Lua reference: {err}"))
+            })?;
+            return Ok(Object {
+                r#type: ObjectType::kObjectTypeLuaRef,
+                data: ObjectData { luaref },
+            });
+        }
+
+        Ok(obj)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Object>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(Dictionary::from_iter([(variant, value.serialize(self)?)]).into())
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(SeqSerializer { items: Vec::with_capacity(len.unwrap_or(0)) })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    #[inline]
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(MapSerializer {
+            pairs: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct> {
+        self.serialize_map(Some(len))
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Serializes sequences and tuples into `Array`s.
+pub(super) struct SeqSerializer {
+    items: Vec<Object>,
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Error = crate::Error;
+    type Ok = Object;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object> {
+        // Collecting the items into an `Array` would filter out the `nil`s.
+        Ok(Array::from(self.items).into())
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Error = crate::Error;
+    type Ok = Object;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Error = crate::Error;
+    type Ok = Object;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serializes maps and structs into `Dictionary`s. Entries whose value is
+/// `nil` are skipped.
+pub(super) struct MapSerializer {
+    pairs: Vec<(NvimString, Object)>,
+    key: Option<NvimString>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Error = crate::Error;
+    type Ok = Object;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = key.serialize(Serializer)?;
+        let ty = key.r#type;
+        self.key = Some(NvimString::try_from(key).map_err(|_| {
+            Error::SerializeError(format!(
+                "dictionary keys must be strings, got {ty:?}"
+            ))
+        })?);
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self.key.take().expect("key is serialized before value");
+        self.pairs.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object> {
+        Ok(self.pairs.into_iter().collect::<Dictionary>().into())
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Error = crate::Error;
+    type Ok = Object;
+
+    #[inline]
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.pairs.push((key.into(), value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// Serializes enum variants with fields into a `Dictionary` with a single
+/// entry, mapping the name of the variant to its fields.
+pub(super) struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl VariantSerializer<SeqSerializer> {
+    #[inline]
+    fn wrap(variant: &'static str, obj: Object) -> Object {
+        Dictionary::from_iter([(variant, obj)]).into()
+    }
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Error = crate::Error;
+    type Ok = Object;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object> {
+        let fields = ser::SerializeSeq::end(self.inner)?;
+        Ok(Self::wrap(self.variant, fields))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Error = crate::Error;
+    type Ok = Object;
+
+    #[inline]
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<Object> {
+        let fields = ser::SerializeMap::end(self.inner)?;
+        Ok(VariantSerializer::wrap(self.variant, fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nvim_types::object::ObjectType;
+
+    use super::*;
+    use crate::object::{FromObject, ToObject};
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Foo {
+        bar: Option<u32>,
+        baz: Vec<Option<String>>,
+        kind: Kind,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Kind {
+        Unit,
+    }
+
+    #[test]
+    fn skip_nil_fields_keep_nil_items() {
+        let foo = Foo {
+            bar: None,
+            baz: vec![None, Some("a".into())],
+            kind: Kind::Unit,
+        };
+
+        let obj = foo.to_obj().unwrap();
+        assert_eq!(obj.r#type, ObjectType::kObjectTypeDictionary);
+
+        let dict = Dictionary::try_from(obj).unwrap();
+        assert_eq!(dict.len(), 2);
+
+        let (_, baz) = dict.into_iter().next().unwrap();
+        assert_eq!(Array::try_from(baz).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn round_trip() {
+        let foo = Foo { bar: Some(3), baz: vec![], kind: Kind::Unit };
+        let obj = crate::to_object(&foo).unwrap();
+        assert_eq!(Foo::from_obj(obj).unwrap(), foo);
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug)]
+    enum Event {
+        Key { code: u32, extra: Object },
+        Mouse(i64, i64),
+    }
+
+    #[test]
+    fn objects_round_trip_inside_values() {
+        let luaref = Object {
+            r#type: ObjectType::kObjectTypeLuaRef,
+            data: nvim_types::object::ObjectData { luaref: 7 },
+        };
+        let extra = Object::from(Array::from(vec![Object::nil(), luaref]));
+
+        let obj = crate::to_object(&Event::Key { code: 1, extra }).unwrap();
+        let Event::Key { code, extra } = crate::from_object(obj).unwrap()
+        else {
+            panic!("expected a key event");
+        };
+        assert_eq!(code, 1);
+
+        let items = Array::try_from(extra).unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items[0].is_nil());
+        assert_eq!(items[1].clone().into_luaref().unwrap(), 7);
+
+        let obj = crate::to_object(&Event::Mouse(2, 3)).unwrap();
+        let event = crate::from_object::<Event>(obj).unwrap();
+        assert!(matches!(event, Event::Mouse(2, 3)));
+    }
+
+    #[test]
+    fn dictionaries_convert_to_structs() {
+        let foo = Foo { bar: Some(3), baz: vec![], kind: Kind::Unit };
+        let dict = crate::to_dictionary(&foo).unwrap();
+        assert_eq!(crate::from_object::<Foo>(dict).unwrap(), foo);
+
+        let array = crate::to_array(&(1, "a")).unwrap();
+        assert_eq!(crate::from_object::<(u8, String)>(array).unwrap().1, "a");
+
+        assert!(crate::to_array(&foo).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use super::{BufferOption, GlobalOption, WindowOption};
+
+/// Defines a function returning the accessor of each option.
+macro_rules! options {
+    ($($kind:ident $name:ident: $ty:ty;)*) => {
+        $(
+            #[doc = concat!("The `'", stringify!($name), "'` option.")]
+            #[inline]
+            pub const fn $name() -> $kind<$ty> {
+                $kind::new(stringify!($name))
+            }
+        )*
+    };
+}
+
+include!("generated.rs");
+
+#[cfg(feature = "test")]
+mod tests {
+    use crate::{api, Result};
+
+    /// Splits the output of `generate.lua` into its first line, naming the
+    /// Neovim version it comes from, and the options.
+    fn split(generated: &str) -> (&str, &str) {
+        let options = generated.find("options! {").unwrap();
+        (generated.lines().next().unwrap(), &generated[options..])
+    }
+
+    #[nvim_oxi::test]
+    fn generated_options_are_up_to_date() -> Result<()> {
+        let (version, options) = split(include_str!("generated.rs"));
+
+        let generated: String =
+            api::exec_lua(include_str!("generate.lua"), [(); 0])?;
+        let (running_version, running_options) = split(&generated);
+
+        // Other versions have different options.
+        if version == running_version {
+            assert_eq!(
+                options, running_options,
+                "run `generate.lua` to update `generated.rs`"
+            );
+        }
+
+        Ok(())
+    }
+}
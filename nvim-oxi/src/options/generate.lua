@@ -0,0 +1,44 @@
+-- Prints the accessors of every option of the running Neovim, which are
+-- checked in as `generated.rs`:
+--
+--     nvim -l generate.lua > generated.rs
+--
+-- Global-local options are listed under their local scope, since they can
+-- be set per buffer or window.
+
+local kinds = { global = 'GlobalOption', buf = 'BufferOption', win = 'WindowOption' }
+local types = { boolean = 'bool', number = 'i64', string = 'String' }
+
+local infos = vim.api.nvim_get_all_options_info()
+
+local names = vim.tbl_keys(infos)
+table.sort(names)
+
+local version = vim.version()
+
+local lines = {
+  ('// Generated by `generate.lua` from the options of Neovim %d.%d, don\'t edit by'):format(
+    version.major,
+    version.minor
+  ),
+  '// hand. Run `nvim -l generate.lua > generated.rs` in this directory to update',
+  '// it.',
+  '',
+  'options! {',
+}
+
+for _, name in ipairs(names) do
+  local info = infos[name]
+  lines[#lines + 1] = ('    %s %s: %s;'):format(kinds[info.scope], name, types[info.type])
+end
+
+lines[#lines + 1] = '}'
+
+local generated = table.concat(lines, '\n') .. '\n'
+
+-- `arg` is only set when running the script with `nvim -l`.
+if arg then
+  io.write(generated)
+end
+
+return generated
@@ -0,0 +1,350 @@
+// Generated by `generate.lua` from the options of Neovim 0.9, don't edit by
+// hand. Run `nvim -l generate.lua > generated.rs` in this directory to update
+// it.
+
+options! {
+    GlobalOption aleph: i64;
+    GlobalOption allowrevins: bool;
+    GlobalOption ambiwidth: String;
+    WindowOption arabic: bool;
+    GlobalOption arabicshape: bool;
+    GlobalOption autochdir: bool;
+    BufferOption autoindent: bool;
+    BufferOption autoread: bool;
+    GlobalOption autowrite: bool;
+    GlobalOption autowriteall: bool;
+    GlobalOption background: String;
+    GlobalOption backspace: String;
+    GlobalOption backup: bool;
+    BufferOption backupcopy: String;
+    GlobalOption backupdir: String;
+    GlobalOption backupext: String;
+    GlobalOption backupskip: String;
+    GlobalOption belloff: String;
+    BufferOption binary: bool;
+    BufferOption bomb: bool;
+    GlobalOption breakat: String;
+    WindowOption breakindent: bool;
+    WindowOption breakindentopt: String;
+    GlobalOption browsedir: String;
+    BufferOption bufhidden: String;
+    BufferOption buflisted: bool;
+    BufferOption buftype: String;
+    GlobalOption casemap: String;
+    GlobalOption cdhome: bool;
+    GlobalOption cdpath: String;
+    GlobalOption cedit: String;
+    BufferOption channel: i64;
+    GlobalOption charconvert: String;
+    BufferOption cindent: bool;
+    BufferOption cinkeys: String;
+    BufferOption cinoptions: String;
+    BufferOption cinscopedecls: String;
+    BufferOption cinwords: String;
+    GlobalOption clipboard: String;
+    GlobalOption cmdheight: i64;
+    GlobalOption cmdwinheight: i64;
+    WindowOption colorcolumn: String;
+    GlobalOption columns: i64;
+    BufferOption comments: String;
+    BufferOption commentstring: String;
+    BufferOption complete: String;
+    BufferOption completefunc: String;
+    GlobalOption completeopt: String;
+    BufferOption completeslash: String;
+    WindowOption concealcursor: String;
+    WindowOption conceallevel: i64;
+    GlobalOption confirm: bool;
+    BufferOption copyindent: bool;
+    GlobalOption cpoptions: String;
+    WindowOption cursorbind: bool;
+    WindowOption cursorcolumn: bool;
+    WindowOption cursorline: bool;
+    WindowOption cursorlineopt: String;
+    GlobalOption debug: String;
+    BufferOption define: String;
+    GlobalOption delcombine: bool;
+    BufferOption dictionary: String;
+    WindowOption diff: bool;
+    GlobalOption diffexpr: String;
+    GlobalOption diffopt: String;
+    GlobalOption digraph: bool;
+    GlobalOption directory: String;
+    GlobalOption display: String;
+    GlobalOption eadirection: String;
+    GlobalOption emoji: bool;
+    GlobalOption encoding: String;
+    BufferOption endoffile: bool;
+    BufferOption endofline: bool;
+    GlobalOption equalalways: bool;
+    BufferOption equalprg: String;
+    GlobalOption errorbells: bool;
+    GlobalOption errorfile: String;
+    BufferOption errorformat: String;
+    GlobalOption eventignore: String;
+    BufferOption expandtab: bool;
+    GlobalOption exrc: bool;
+    BufferOption fileencoding: String;
+    GlobalOption fileencodings: String;
+    BufferOption fileformat: String;
+    GlobalOption fileformats: String;
+    GlobalOption fileignorecase: bool;
+    BufferOption filetype: String;
+    WindowOption fillchars: String;
+    BufferOption fixendofline: bool;
+    GlobalOption foldclose: String;
+    WindowOption foldcolumn: String;
+    WindowOption foldenable: bool;
+    WindowOption foldexpr: String;
+    WindowOption foldignore: String;
+    WindowOption foldlevel: i64;
+    GlobalOption foldlevelstart: i64;
+    WindowOption foldmarker: String;
+    WindowOption foldmethod: String;
+    WindowOption foldminlines: i64;
+    WindowOption foldnestmax: i64;
+    GlobalOption foldopen: String;
+    WindowOption foldtext: String;
+    BufferOption formatexpr: String;
+    BufferOption formatlistpat: String;
+    BufferOption formatoptions: String;
+    BufferOption formatprg: String;
+    GlobalOption fsync: bool;
+    GlobalOption gdefault: bool;
+    GlobalOption grepformat: String;
+    BufferOption grepprg: String;
+    GlobalOption guicursor: String;
+    GlobalOption guifont: String;
+    GlobalOption guifontwide: String;
+    GlobalOption guioptions: String;
+    GlobalOption guitablabel: String;
+    GlobalOption guitabtooltip: String;
+    GlobalOption helpfile: String;
+    GlobalOption helpheight: i64;
+    GlobalOption helplang: String;
+    GlobalOption hidden: bool;
+    GlobalOption history: i64;
+    GlobalOption hkmap: bool;
+    GlobalOption hkmapp: bool;
+    GlobalOption hlsearch: bool;
+    GlobalOption icon: bool;
+    GlobalOption iconstring: String;
+    GlobalOption ignorecase: bool;
+    GlobalOption imcmdline: bool;
+    GlobalOption imdisable: bool;
+    BufferOption iminsert: i64;
+    BufferOption imsearch: i64;
+    GlobalOption inccommand: String;
+    BufferOption include: String;
+    BufferOption includeexpr: String;
+    GlobalOption incsearch: bool;
+    BufferOption indentexpr: String;
+    BufferOption indentkeys: String;
+    BufferOption infercase: bool;
+    GlobalOption isfname: String;
+    GlobalOption isident: String;
+    BufferOption iskeyword: String;
+    GlobalOption isprint: String;
+    GlobalOption joinspaces: bool;
+    GlobalOption jumpoptions: String;
+    BufferOption keymap: String;
+    GlobalOption keymodel: String;
+    BufferOption keywordprg: String;
+    GlobalOption langmap: String;
+    GlobalOption langmenu: String;
+    GlobalOption langnoremap: bool;
+    GlobalOption langremap: bool;
+    GlobalOption laststatus: i64;
+    GlobalOption lazyredraw: bool;
+    WindowOption linebreak: bool;
+    GlobalOption lines: i64;
+    GlobalOption linespace: i64;
+    BufferOption lisp: bool;
+    BufferOption lispoptions: String;
+    BufferOption lispwords: String;
+    WindowOption list: bool;
+    WindowOption listchars: String;
+    GlobalOption loadplugins: bool;
+    GlobalOption magic: bool;
+    GlobalOption makeef: String;
+    GlobalOption makeencoding: String;
+    BufferOption makeprg: String;
+    BufferOption matchpairs: String;
+    GlobalOption matchtime: i64;
+    GlobalOption maxfuncdepth: i64;
+    GlobalOption maxmapdepth: i64;
+    GlobalOption maxmempattern: i64;
+    GlobalOption menuitems: i64;
+    GlobalOption mkspellmem: String;
+    BufferOption modeline: bool;
+    GlobalOption modelineexpr: bool;
+    GlobalOption modelines: i64;
+    BufferOption modifiable: bool;
+    BufferOption modified: bool;
+    GlobalOption more: bool;
+    GlobalOption mouse: String;
+    GlobalOption mousefocus: bool;
+    GlobalOption mousehide: bool;
+    GlobalOption mousemodel: String;
+    GlobalOption mousemoveevent: bool;
+    GlobalOption mousescroll: String;
+    GlobalOption mousetime: i64;
+    BufferOption nrformats: String;
+    WindowOption number: bool;
+    WindowOption numberwidth: i64;
+    BufferOption omnifunc: String;
+    GlobalOption opendevice: bool;
+    GlobalOption operatorfunc: String;
+    GlobalOption packpath: String;
+    GlobalOption paragraphs: String;
+    GlobalOption paste: bool;
+    GlobalOption pastetoggle: String;
+    GlobalOption patchexpr: String;
+    GlobalOption patchmode: String;
+    BufferOption path: String;
+    BufferOption preserveindent: bool;
+    GlobalOption previewheight: i64;
+    WindowOption previewwindow: bool;
+    GlobalOption pumblend: i64;
+    GlobalOption pumheight: i64;
+    GlobalOption pumwidth: i64;
+    GlobalOption pyxversion: i64;
+    GlobalOption quickfixtextfunc: String;
+    BufferOption quoteescape: String;
+    BufferOption readonly: bool;
+    GlobalOption redrawdebug: String;
+    GlobalOption redrawtime: i64;
+    GlobalOption regexpengine: i64;
+    WindowOption relativenumber: bool;
+    GlobalOption remap: bool;
+    GlobalOption report: i64;
+    GlobalOption revins: bool;
+    WindowOption rightleft: bool;
+    WindowOption rightleftcmd: String;
+    GlobalOption ruler: bool;
+    GlobalOption rulerformat: String;
+    GlobalOption runtimepath: String;
+    WindowOption scroll: i64;
+    BufferOption scrollback: i64;
+    WindowOption scrollbind: bool;
+    GlobalOption scrolljump: i64;
+    WindowOption scrolloff: i64;
+    GlobalOption scrollopt: String;
+    GlobalOption sections: String;
+    GlobalOption secure: bool;
+    GlobalOption selection: String;
+    GlobalOption selectmode: String;
+    GlobalOption sessionoptions: String;
+    GlobalOption shada: String;
+    GlobalOption shadafile: String;
+    GlobalOption shell: String;
+    GlobalOption shellcmdflag: String;
+    GlobalOption shellpipe: String;
+    GlobalOption shellquote: String;
+    GlobalOption shellredir: String;
+    GlobalOption shellslash: bool;
+    GlobalOption shelltemp: bool;
+    GlobalOption shellxescape: String;
+    GlobalOption shellxquote: String;
+    GlobalOption shiftround: bool;
+    BufferOption shiftwidth: i64;
+    GlobalOption shortmess: String;
+    WindowOption showbreak: String;
+    GlobalOption showcmd: bool;
+    GlobalOption showcmdloc: String;
+    GlobalOption showfulltag: bool;
+    GlobalOption showmatch: bool;
+    GlobalOption showmode: bool;
+    GlobalOption showtabline: i64;
+    GlobalOption sidescroll: i64;
+    WindowOption sidescrolloff: i64;
+    WindowOption signcolumn: String;
+    GlobalOption smartcase: bool;
+    BufferOption smartindent: bool;
+    GlobalOption smarttab: bool;
+    BufferOption softtabstop: i64;
+    WindowOption spell: bool;
+    BufferOption spellcapcheck: String;
+    BufferOption spellfile: String;
+    BufferOption spelllang: String;
+    BufferOption spelloptions: String;
+    GlobalOption spellsuggest: String;
+    GlobalOption splitbelow: bool;
+    GlobalOption splitkeep: String;
+    GlobalOption splitright: bool;
+    GlobalOption startofline: bool;
+    WindowOption statuscolumn: String;
+    WindowOption statusline: String;
+    GlobalOption suffixes: String;
+    BufferOption suffixesadd: String;
+    BufferOption swapfile: bool;
+    GlobalOption switchbuf: String;
+    BufferOption synmaxcol: i64;
+    BufferOption syntax: String;
+    GlobalOption tabline: String;
+    GlobalOption tabpagemax: i64;
+    BufferOption tabstop: i64;
+    GlobalOption tagbsearch: bool;
+    BufferOption tagcase: String;
+    BufferOption tagfunc: String;
+    GlobalOption taglength: i64;
+    GlobalOption tagrelative: bool;
+    BufferOption tags: String;
+    GlobalOption tagstack: bool;
+    GlobalOption termbidi: bool;
+    GlobalOption termguicolors: bool;
+    GlobalOption termpastefilter: String;
+    BufferOption textwidth: i64;
+    BufferOption thesaurus: String;
+    BufferOption thesaurusfunc: String;
+    GlobalOption tildeop: bool;
+    GlobalOption timeout: bool;
+    GlobalOption timeoutlen: i64;
+    GlobalOption title: bool;
+    GlobalOption titlelen: i64;
+    GlobalOption titleold: String;
+    GlobalOption titlestring: String;
+    GlobalOption ttimeout: bool;
+    GlobalOption ttimeoutlen: i64;
+    GlobalOption ttyfast: bool;
+    GlobalOption undodir: String;
+    BufferOption undofile: bool;
+    BufferOption undolevels: i64;
+    GlobalOption undoreload: i64;
+    GlobalOption updatecount: i64;
+    GlobalOption updatetime: i64;
+    BufferOption varsofttabstop: String;
+    BufferOption vartabstop: String;
+    GlobalOption verbose: i64;
+    GlobalOption verbosefile: String;
+    GlobalOption viewdir: String;
+    GlobalOption viewoptions: String;
+    WindowOption virtualedit: String;
+    GlobalOption visualbell: bool;
+    GlobalOption warn: bool;
+    GlobalOption whichwrap: String;
+    GlobalOption wildchar: i64;
+    GlobalOption wildcharm: i64;
+    GlobalOption wildignore: String;
+    GlobalOption wildignorecase: bool;
+    GlobalOption wildmenu: bool;
+    GlobalOption wildmode: String;
+    GlobalOption wildoptions: String;
+    GlobalOption winaltkeys: String;
+    WindowOption winbar: String;
+    WindowOption winblend: i64;
+    WindowOption winfixheight: bool;
+    WindowOption winfixwidth: bool;
+    GlobalOption winheight: i64;
+    WindowOption winhighlight: String;
+    GlobalOption winminheight: i64;
+    GlobalOption winminwidth: i64;
+    GlobalOption winwidth: i64;
+    WindowOption wrap: bool;
+    BufferOption wrapmargin: i64;
+    GlobalOption wrapscan: bool;
+    GlobalOption write: bool;
+    GlobalOption writeany: bool;
+    GlobalOption writebackup: bool;
+    GlobalOption writedelay: i64;
+}
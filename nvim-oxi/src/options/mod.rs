@@ -0,0 +1,148 @@
+//! Strongly-typed accessors for Neovim's options.
+//!
+//! Every option is scoped either globally, to a buffer or to a window, and
+//! its accessors only accept the matching kind of target:
+//!
+//! ```ignore
+//! use nvim_oxi::options;
+//!
+//! let sw = options::shiftwidth().get()?;
+//! options::shiftwidth().set_for(buf, sw * 2)?;
+//! ```
+//!
+//! Global-local options, like `'scrolloff'`, are buffer- or window-local
+//! options whose local value falls back on the global one. They're listed
+//! with their local scope, and their global value can be accessed with
+//! `get_global` and `set_global`.
+//!
+//! The accessors are generated by `generate.lua` from the options of a
+//! Neovim release, see the header of `generated.rs`. Options that aren't
+//! listed, e.g. because they were added in a later release, can be accessed
+//! by creating a [`GlobalOption`], [`BufferOption`] or [`WindowOption`]
+//! manually.
+
+mod defs;
+mod scoped;
+
+use std::marker::PhantomData;
+
+pub use defs::*;
+pub use scoped::{BufferOptions, WindowOptions};
+
+use crate::api::opts::{OptionScope, OptionValueOpts};
+use crate::api::{self, Buffer, Window};
+use crate::object::{FromObject, ToObject};
+use crate::Result;
+
+/// Builds the `OptionValueOpts` used by the scoped accessors.
+macro_rules! scoped_opts {
+    ($field:ident, $value:expr) => {
+        OptionValueOpts::builder()
+            .$field($value)
+            .build()
+            .expect("all fields have defaults")
+    };
+}
+
+/// Defines an option type, with the `get`/`set` methods acting on the
+/// current value of the option.
+macro_rules! option {
+    ($name:ident, $kind:literal) => {
+        #[doc = concat!("A ", $kind, " option whose value is a `T`.")]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+        pub struct $name<T> {
+            name: &'static str,
+            ty: PhantomData<fn() -> T>,
+        }
+
+        impl<T> $name<T> {
+            /// Creates an accessor for the option called `name`. Nothing
+            /// checks that the option exists or that it's of type `T`.
+            #[inline]
+            pub const fn new(name: &'static str) -> Self {
+                Self { name, ty: PhantomData }
+            }
+
+            /// The name of the option.
+            #[inline]
+            pub const fn name(&self) -> &'static str {
+                self.name
+            }
+        }
+
+        impl<T: FromObject> $name<T> {
+            /// Gets the current value of the option.
+            pub fn get(&self) -> Result<T> {
+                api::get_option_value(self.name, &OptionValueOpts::default())
+            }
+
+            /// Gets the global value of the option, like `:setglobal`
+            /// would. For local options it's the value new buffers or
+            /// windows start with, and for global-local ones the value used
+            /// where no local one is set.
+            pub fn get_global(&self) -> Result<T> {
+                api::get_option_value(
+                    self.name,
+                    &scoped_opts!(scope, OptionScope::Global),
+                )
+            }
+        }
+
+        impl<T: ToObject> $name<T> {
+            /// Sets the option, like `:set` would.
+            pub fn set(&self, value: T) -> Result<()> {
+                let opts = OptionValueOpts::default();
+                api::set_option_value(self.name, value, &opts)
+            }
+
+            /// Sets the global value of the option, like `:setglobal`
+            /// would.
+            pub fn set_global(&self, value: T) -> Result<()> {
+                let opts = scoped_opts!(scope, OptionScope::Global);
+                api::set_option_value(self.name, value, &opts)
+            }
+        }
+    };
+}
+
+option!(GlobalOption, "global");
+option!(BufferOption, "buffer-local");
+option!(WindowOption, "window-local");
+
+impl<T: FromObject> BufferOption<T> {
+    /// Gets the value of the option in `buf`.
+    pub fn get_for(&self, buf: Buffer) -> Result<T> {
+        api::get_option_value(self.name, &scoped_opts!(buf, buf))
+    }
+
+    /// Gets the value the option would have in a new buffer of the given
+    /// filetype, without creating one.
+    ///
+    /// Note that this triggers the ftplugins and the `FileType`
+    /// autocommands of the filetype.
+    #[cfg(feature = "neovim-nightly")]
+    pub fn get_for_filetype(&self, filetype: &str) -> Result<T> {
+        api::get_option_value(self.name, &scoped_opts!(filetype, filetype))
+    }
+}
+
+impl<T: ToObject> BufferOption<T> {
+    /// Sets the value of the option in `buf`.
+    pub fn set_for(&self, buf: Buffer, value: T) -> Result<()> {
+        api::set_option_value(self.name, value, &scoped_opts!(buf, buf))
+    }
+}
+
+impl<T: FromObject> WindowOption<T> {
+    /// Gets the value of the option in `win`.
+    pub fn get_for(&self, win: Window) -> Result<T> {
+        api::get_option_value(self.name, &scoped_opts!(win, win))
+    }
+}
+
+impl<T: ToObject> WindowOption<T> {
+    /// Sets the value of the option in `win`.
+    pub fn set_for(&self, win: Window, value: T) -> Result<()> {
+        api::set_option_value(self.name, value, &scoped_opts!(win, win))
+    }
+}
@@ -0,0 +1,62 @@
+use crate::api::opts::OptionValueOpts;
+use crate::api::{self, Buffer, Window};
+use crate::object::{FromObject, ToObject};
+use crate::Result;
+
+/// The options local to a buffer, returned by `Buffer::options`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BufferOptions(pub(crate) Buffer);
+
+/// The options local to a window, returned by `Window::options`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WindowOptions(pub(crate) Window);
+
+impl BufferOptions {
+    /// Gets the value of the option called `name` in this buffer. Fails if
+    /// the specified type couldn't be deserialized from the returned object.
+    pub fn get<Value: FromObject>(&self, name: &str) -> Result<Value> {
+        api::get_option_value(name, &self.opts())
+    }
+
+    /// Sets the value of the option called `name` in this buffer.
+    pub fn set<Value: ToObject>(
+        &self,
+        name: &str,
+        value: Value,
+    ) -> Result<()> {
+        api::set_option_value(name, value, &self.opts())
+    }
+
+    #[inline]
+    fn opts(&self) -> OptionValueOpts {
+        OptionValueOpts::builder()
+            .buf(self.0)
+            .build()
+            .expect("all fields have defaults")
+    }
+}
+
+impl WindowOptions {
+    /// Gets the value of the option called `name` in this window. Fails if
+    /// the specified type couldn't be deserialized from the returned object.
+    pub fn get<Value: FromObject>(&self, name: &str) -> Result<Value> {
+        api::get_option_value(name, &self.opts())
+    }
+
+    /// Sets the value of the option called `name` in this window.
+    pub fn set<Value: ToObject>(
+        &self,
+        name: &str,
+        value: Value,
+    ) -> Result<()> {
+        api::set_option_value(name, value, &self.opts())
+    }
+
+    #[inline]
+    fn opts(&self) -> OptionValueOpts {
+        OptionValueOpts::builder()
+            .win(self.0)
+            .build()
+            .expect("all fields have defaults")
+    }
+}
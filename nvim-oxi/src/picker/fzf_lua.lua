@@ -0,0 +1,62 @@
+-- Opens a fzf-lua picker for a source registered from Rust.
+local title, next_batch, on_select, preview = ...
+
+local builtin = require("fzf-lua.previewer.builtin")
+
+-- fzf only deals with lines, so each one starts with the index of its item,
+-- which is hidden from the user and from the matching, and is parsed back
+-- from the selected lines. Items with the same display text stay distinct.
+local function index(line)
+  return tonumber(line:match("^(%d+)\t"))
+end
+
+local Previewer = builtin.base:extend()
+
+function Previewer:new(o, opts, fzf_win)
+  Previewer.super.new(self, o, opts, fzf_win)
+  setmetatable(self, Previewer)
+  return self
+end
+
+function Previewer:populate_preview_buf(entry_str)
+  local bufnr = self:get_tmp_buffer()
+  preview(index(entry_str), bufnr)
+  self:set_preview_buf(bufnr)
+  self.win:update_scrollbar()
+end
+
+-- Streams the items in batches, yielding to fzf after each line.
+local function contents(fzf_cb)
+  coroutine.wrap(function()
+    local co = coroutine.running()
+    while true do
+      local batch = next_batch()
+      if #batch == 0 then
+        break
+      end
+      for _, item in ipairs(batch) do
+        fzf_cb(item.idx .. "\t" .. item.display, function()
+          coroutine.resume(co)
+        end)
+        coroutine.yield()
+      end
+    end
+    fzf_cb()
+  end)()
+end
+
+require("fzf-lua").fzf_exec(contents, {
+  prompt = title .. "> ",
+  previewer = Previewer,
+  fzf_opts = {
+    ["--delimiter"] = "\t",
+    ["--with-nth"] = "2..",
+  },
+  actions = {
+    ["default"] = function(selected)
+      if selected[1] then
+        on_select(index(selected[1]))
+      end
+    end,
+  },
+})
@@ -0,0 +1,258 @@
+//! Pickers backed by whichever fuzzy finder the user has installed.
+//!
+//! A [`Source`] provides the items, how to display and preview them and
+//! what to do when one is selected, and [`open`] hands it to
+//! [telescope.nvim] or [fzf-lua] without any Lua glue on the plugin side.
+//!
+//! [telescope.nvim]: https://github.com/nvim-telescope/telescope.nvim
+//! [fzf-lua]: https://github.com/ibhagwan/fzf-lua
+
+use std::any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_types::object::Object;
+use serde::Serialize;
+
+use crate::api::{self, Buffer};
+use crate::lua::LuaFnMut;
+use crate::{Error, Result};
+
+/// A source of items for a picker.
+pub trait Source: 'static {
+    type Item: 'static;
+
+    /// The items to pick from, e.g. a `Vec` or a lazy iterator.
+    type Items: IntoIterator<Item = Self::Item>;
+
+    /// The title of the picker.
+    fn title(&self) -> String;
+
+    /// Returns the items to pick from. Called once when the picker opens,
+    /// after which they're streamed to the backend in batches, so a lazy
+    /// iterator lets the user start picking before all of them have been
+    /// produced.
+    fn items(&mut self) -> Result<Self::Items>;
+
+    /// The text the item is displayed and matched as.
+    fn display(&self, item: &Self::Item) -> String;
+
+    /// Fills `buf` with a preview of the item. Doesn't preview anything
+    /// by default.
+    fn preview(&mut self, _item: &Self::Item, _buf: Buffer) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called with the item the user picked.
+    fn on_select(&mut self, item: &Self::Item) -> Result<()>;
+}
+
+/// The fuzzy finders a [`Source`] can be opened with.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Backend {
+    Telescope,
+    FzfLua,
+}
+
+impl Backend {
+    /// The first backend which is installed, if any.
+    pub fn detect() -> Result<Option<Self>> {
+        for backend in [Self::Telescope, Self::FzfLua] {
+            if backend.is_installed()? {
+                return Ok(Some(backend));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether the backend's Lua module can be required.
+    pub fn is_installed(&self) -> Result<bool> {
+        api::exec_lua("return (pcall(require, ...))", [self.lua_module()])
+    }
+
+    #[inline]
+    const fn lua_module(&self) -> &'static str {
+        match self {
+            Self::Telescope => "telescope",
+            Self::FzfLua => "fzf-lua",
+        }
+    }
+
+    #[inline]
+    const fn shim(&self) -> &'static str {
+        match self {
+            Self::Telescope => include_str!("./telescope.lua"),
+            Self::FzfLua => include_str!("./fzf_lua.lua"),
+        }
+    }
+}
+
+/// Arguments passed to the preview callback.
+pub(crate) type PreviewArgs = (usize, Buffer);
+
+/// How many items are sent to the backend at a time.
+const BATCH_SIZE: usize = 256;
+
+/// An item as seen by the backend, which identifies it by its index.
+#[derive(Clone, Serialize)]
+struct Entry {
+    idx: usize,
+    display: String,
+}
+
+/// The items of a picker. The [`Source`] is kept apart, so that it can use
+/// an item without the picker being borrowed.
+struct Picker<S: Source> {
+    /// The items streamed to the backend so far.
+    items: Vec<Rc<S::Item>>,
+
+    /// The items left to stream, or `None` once they've all been sent.
+    pending: Option<<S::Items as IntoIterator>::IntoIter>,
+}
+
+impl<S: Source> Picker<S> {
+    fn new(source: &mut S) -> Result<Self> {
+        let pending = source.items()?.into_iter();
+        Ok(Self { items: Vec::new(), pending: Some(pending) })
+    }
+
+    /// Returns the next batch of entries, which is empty once every item
+    /// has been sent.
+    fn next_batch(&mut self, source: &S) -> Vec<Entry> {
+        let Some(pending) = &mut self.pending else { return Vec::new() };
+
+        let start = self.items.len();
+        self.items.extend(pending.by_ref().take(BATCH_SIZE).map(Rc::new));
+        if self.items.len() - start < BATCH_SIZE {
+            self.pending = None;
+        }
+
+        self.items[start..]
+            .iter()
+            .enumerate()
+            .map(|(offset, item)| Entry {
+                idx: start + offset,
+                display: source.display(item),
+            })
+            .collect()
+    }
+
+    /// Returns the item at index `idx`, if it has been streamed.
+    fn item(&self, idx: usize) -> Option<Rc<S::Item>> {
+        self.items.get(idx).cloned()
+    }
+}
+
+/// The error returned when the source is called back into while it's
+/// already in use.
+fn borrow_error<S: Source>() -> Error {
+    Error::StateBorrowError { ty: any::type_name::<S>() }
+}
+
+/// Opens `source` with the first installed backend, returning which one.
+/// Fails with `PickerUnavailableError` if none is installed.
+pub fn open<S: Source>(source: S) -> Result<Backend> {
+    let backend = Backend::detect()?.ok_or(Error::PickerUnavailableError)?;
+    open_with(backend, source)?;
+    Ok(backend)
+}
+
+/// Opens `source` with the given backend.
+pub fn open_with<S: Source>(backend: Backend, mut source: S) -> Result<()> {
+    let title = source.title();
+    let picker = Rc::new(RefCell::new(Picker::new(&mut source)?));
+    let source = Rc::new(RefCell::new(source));
+
+    let next_batch = {
+        let (picker, source) = (Rc::clone(&picker), Rc::clone(&source));
+        LuaFnMut::<(), Vec<Entry>>::from(move |()| {
+            let source =
+                source.try_borrow().map_err(|_| borrow_error::<S>())?;
+            Ok(picker.borrow_mut().next_batch(&source))
+        })
+    };
+
+    // Only the source stays borrowed while it uses an item, and calling back
+    // into it in the meantime fails instead of panicking.
+    let on_select = {
+        let (picker, source) = (Rc::clone(&picker), Rc::clone(&source));
+        LuaFnMut::<usize, ()>::from(move |idx| {
+            let Some(item) = picker.borrow().item(idx) else { return Ok(()) };
+            let mut source =
+                source.try_borrow_mut().map_err(|_| borrow_error::<S>())?;
+            source.on_select(&item)
+        })
+    };
+
+    let preview = LuaFnMut::<PreviewArgs, ()>::from(move |(idx, buf)| {
+        let Some(item) = picker.borrow().item(idx) else { return Ok(()) };
+        let mut source =
+            source.try_borrow_mut().map_err(|_| borrow_error::<S>())?;
+        source.preview(&item, buf)
+    });
+
+    let res = api::exec_lua(
+        backend.shim(),
+        [
+            Object::from(title),
+            next_batch.clone().into(),
+            on_select.into(),
+            preview.into(),
+        ],
+    );
+
+    // The backend holds on to the functions for as long as the picker is
+    // open, so their registry refs aren't needed anymore.
+    next_batch.unref();
+    on_select.unref();
+    preview.unref();
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Numbers;
+
+    impl Source for Numbers {
+        type Item = usize;
+        type Items = std::ops::Range<usize>;
+
+        fn title(&self) -> String {
+            "Numbers".into()
+        }
+
+        fn items(&mut self) -> Result<Self::Items> {
+            Ok(0..600)
+        }
+
+        fn display(&self, item: &usize) -> String {
+            item.to_string()
+        }
+
+        fn on_select(&mut self, _item: &usize) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn items_are_streamed_in_batches() {
+        let mut picker = Picker::new(&mut Numbers).unwrap();
+
+        let mut sizes = Vec::new();
+        loop {
+            let batch = picker.next_batch(&Numbers);
+            if batch.is_empty() {
+                break;
+            }
+            assert!(batch.iter().all(|e| e.display == e.idx.to_string()));
+            sizes.push(batch.len());
+        }
+
+        assert_eq!(sizes, [256, 256, 88]);
+        assert_eq!(picker.items.len(), 600);
+        assert!(picker.pending.is_none());
+    }
+}
@@ -0,0 +1,64 @@
+-- Opens a telescope.nvim picker for a source registered from Rust.
+local title, next_batch, on_select, preview = ...
+
+local pickers = require("telescope.pickers")
+local previewers = require("telescope.previewers")
+local actions = require("telescope.actions")
+local action_state = require("telescope.actions.state")
+local conf = require("telescope.config").values
+local scheduler = require("plenary.async.util").scheduler
+
+local function make_entry(item)
+  return { value = item.idx, display = item.display, ordinal = item.display }
+end
+
+-- Streams the items in batches, giving the UI a chance to redraw between
+-- them. Telescope calls the finder again every time the prompt changes, so
+-- the entries received so far are kept and replayed.
+local entries, done = {}, false
+
+local finder = setmetatable({ close = function() end }, {
+  __call = function(_, _, process_result, process_complete)
+    for _, entry in ipairs(entries) do
+      if process_result(entry) then
+        return
+      end
+    end
+
+    while not done do
+      local batch = next_batch()
+      done = #batch == 0
+      for _, item in ipairs(batch) do
+        local entry = make_entry(item)
+        table.insert(entries, entry)
+        if process_result(entry) then
+          return
+        end
+      end
+      scheduler()
+    end
+
+    process_complete()
+  end,
+})
+
+pickers.new({}, {
+  prompt_title = title,
+  finder = finder,
+  sorter = conf.generic_sorter({}),
+  previewer = previewers.new_buffer_previewer({
+    define_preview = function(self, entry)
+      preview(entry.value, self.state.bufnr)
+    end,
+  }),
+  attach_mappings = function(prompt_bufnr)
+    actions.select_default:replace(function()
+      local entry = action_state.get_selected_entry()
+      actions.close(prompt_bufnr)
+      if entry then
+        on_select(entry.value)
+      end
+    end)
+    return true
+  end,
+}):find()
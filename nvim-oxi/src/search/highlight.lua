@@ -0,0 +1,79 @@
+-- Highlights the matches of a pattern in a buffer and keeps them up to date
+-- as the buffer changes.
+local buf, pattern, ns, hl_group = ...
+
+if buf == 0 then
+  buf = vim.api.nvim_get_current_buf()
+end
+
+-- Compiling the pattern first makes invalid patterns fail right away.
+local regex = vim.regex(pattern)
+
+-- Every call bumps the id stored in the buffer variable, which detaches the
+-- callbacks attached by the previous calls.
+local var = 'nvim_oxi_search_' .. ns
+local id = ((vim.b[buf][var] or {}).id or 0) + 1
+vim.b[buf][var] = { id = id }
+
+-- Returns the matches in the 0-indexed, end exclusive range of lines
+-- `first..last` as `{ lnum, start_col, end_col }` triples.
+local function matches(first, last)
+  local found = {}
+
+  if last <= first then
+    return found
+  end
+
+  if vim.fn.exists('*matchbufline') == 1 then
+    for _, m in ipairs(vim.fn.matchbufline(buf, pattern, first + 1, last)) do
+      table.insert(found, { m.lnum - 1, m.byteidx, m.byteidx + #m.text })
+    end
+    return found
+  end
+
+  for lnum = first, last - 1 do
+    local line = vim.api.nvim_buf_get_lines(buf, lnum, lnum + 1, true)[1]
+    local col = 0
+    while col <= #line do
+      local start, stop = regex:match_line(buf, lnum, col)
+      if not start then
+        break
+      end
+      table.insert(found, { lnum, col + start, col + stop })
+      col = col + math.max(stop, start + 1)
+    end
+  end
+
+  return found
+end
+
+local function highlight(first, last)
+  vim.api.nvim_buf_clear_namespace(buf, ns, first, last)
+
+  local count = 0
+  for _, m in ipairs(matches(first, last)) do
+    -- Empty matches can't be highlighted.
+    if m[3] > m[2] then
+      vim.api.nvim_buf_set_extmark(buf, ns, m[1], m[2], {
+        end_col = m[3],
+        hl_group = hl_group,
+      })
+      count = count + 1
+    end
+  end
+  return count
+end
+
+local count = highlight(0, vim.api.nvim_buf_line_count(buf))
+
+vim.api.nvim_buf_attach(buf, false, {
+  on_lines = function(_, _, _, first, _, last_new)
+    local state = vim.b[buf][var]
+    if not state or state.id ~= id then
+      return true
+    end
+    highlight(first, last_new)
+  end,
+})
+
+return count
@@ -0,0 +1,59 @@
+//! Persistent highlighting of the matches of a search pattern.
+//!
+//! Unlike `'hlsearch'`, which only highlights the last search pattern, any
+//! number of patterns can be highlighted at once, one per namespace, each
+//! with its own highlight group. The highlights are stored as extmarks and
+//! are updated as the buffer changes.
+
+use nvim_types::object::Object;
+
+use crate::api::types::Namespace;
+use crate::api::{self, Buffer};
+use crate::Result;
+
+/// Highlights every match of the Vim regex `pattern` in `buf` with
+/// `hl_group`, returning the number of matches.
+///
+/// The matches are updated on every change to the buffer until
+/// [`clear_matches`] is called with the same namespace, or this function is
+/// called again to highlight a different pattern. Matches spanning multiple
+/// lines are not highlighted.
+pub fn highlight_matches(
+    buf: Buffer,
+    pattern: &str,
+    ns: Namespace,
+    hl_group: &str,
+) -> Result<usize> {
+    api::exec_lua(
+        include_str!("./highlight.lua"),
+        [Object::from(buf.0), pattern.into(), ns.into(), hl_group.into()],
+    )
+}
+
+/// Highlights the matches of the last search pattern, i.e. the contents of
+/// the `/` register, with the `Search` highlight group.
+///
+/// Clears the namespace if there hasn't been any search yet.
+pub fn highlight_last_search(buf: Buffer, ns: Namespace) -> Result<usize> {
+    let pattern = api::call_function::<_, String>("getreg", ["/"])?;
+
+    if pattern.is_empty() {
+        clear_matches(buf, ns)?;
+        return Ok(0);
+    }
+
+    highlight_matches(buf, &pattern, ns, "Search")
+}
+
+/// Removes the highlights added by [`highlight_matches`] in `ns`, and stops
+/// updating them.
+pub fn clear_matches(buf: Buffer, ns: Namespace) -> Result<()> {
+    api::exec_lua(
+        r#"
+        local buf, ns = ...
+        vim.b[buf]['nvim_oxi_search_' .. ns] = nil
+        vim.api.nvim_buf_clear_namespace(buf, ns, 0, -1)
+        "#,
+        [Object::from(buf.0), ns.into()],
+    )
+}
@@ -0,0 +1,118 @@
+//! A container for the global state of a plugin.
+//!
+//! Callbacks registered with Neovim need to be `'static`, so plugins often
+//! end up keeping their state in a `static mut` or in an ad-hoc
+//! `thread_local!`. This module provides a single place to keep it, with one
+//! value per type and namespace whose borrows are checked at runtime. The
+//! namespace is the name of the plugin's Lua module, so plugins built into
+//! the same library don't share their state.
+//!
+//! ```ignore
+//! use nvim_oxi::state;
+//!
+//! #[derive(Default)]
+//! struct Counter(usize);
+//!
+//! let counter = state::get_or_init::<Counter>();
+//! counter.borrow_mut()?.0 += 1;
+//! ```
+
+use std::any::{self, Any, TypeId};
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::{Error, Result};
+
+type Key = (TypeId, String);
+
+thread_local! {
+    static NAMESPACE: RefCell<String> = const { RefCell::new(String::new()) };
+
+    static STATE: RefCell<HashMap<Key, Rc<dyn Any>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Sets the namespace used by [`get_or_init`] from now on. Values stored in
+/// other namespaces are left untouched.
+///
+/// [`#[nvim_oxi::module]`](crate::module) sets it to the name of the Lua
+/// module while loading it, so there's usually no need to call this.
+pub fn set_namespace(name: impl Into<String>) {
+    NAMESPACE.with(|ns| *ns.borrow_mut() = name.into());
+}
+
+/// Returns a reference to the value of type `T` in the current namespace,
+/// initializing it with `T::default()` the first time it's requested.
+pub fn get_or_init<T: Default + 'static>() -> StateRef<T> {
+    let namespace = NAMESPACE.with(|ns| ns.borrow().clone());
+    let key = (TypeId::of::<T>(), namespace);
+
+    let value = STATE.with(move |state| {
+        state
+            .borrow_mut()
+            .entry(key)
+            .or_insert_with(|| Rc::new(RefCell::new(T::default())))
+            .clone()
+    });
+
+    match value.downcast::<RefCell<T>>() {
+        Ok(value) => StateRef(value),
+        Err(_) => unreachable!("values are keyed by their type id"),
+    }
+}
+
+/// A handle to a value stored with [`get_or_init`]. Cloning it is cheap and
+/// returns a handle to the same value.
+pub struct StateRef<T>(Rc<RefCell<T>>);
+
+impl<T> Clone for StateRef<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for StateRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("StateRef").field(&self.0).finish()
+    }
+}
+
+impl<T> StateRef<T> {
+    /// Immutably borrows the value, failing if it's currently mutably
+    /// borrowed.
+    pub fn borrow(&self) -> Result<Ref<'_, T>> {
+        self.0
+            .try_borrow()
+            .map_err(|_| Error::StateBorrowError { ty: any::type_name::<T>() })
+    }
+
+    /// Mutably borrows the value, failing if it's currently borrowed.
+    pub fn borrow_mut(&self) -> Result<RefMut<'_, T>> {
+        self.0
+            .try_borrow_mut()
+            .map_err(|_| Error::StateBorrowError { ty: any::type_name::<T>() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter(usize);
+
+    #[test]
+    fn shared_per_namespace() {
+        get_or_init::<Counter>().borrow_mut().unwrap().0 += 1;
+        assert_eq!(get_or_init::<Counter>().borrow().unwrap().0, 1);
+
+        set_namespace("other");
+        assert_eq!(get_or_init::<Counter>().borrow().unwrap().0, 0);
+
+        let counter = get_or_init::<Counter>();
+        let _guard = counter.borrow().unwrap();
+        assert!(counter.borrow_mut().is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt;
+
+use super::RENDER_FNS;
+use crate::lua::{self, LuaFnMut};
+use crate::{Error, Result};
+
+/// The mouse button used to click on a statusline region.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// The modifier keys held down during a click.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ClickModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+/// Arguments passed to a [`ClickHandler`] (see `:h 'statusline'`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ClickArgs {
+    /// The `minwid` of the clicked region, which handlers shared by
+    /// multiple regions can use to tell them apart.
+    pub minwid: i32,
+
+    /// The number of clicks, e.g. 2 for a double click.
+    pub clicks: u32,
+
+    pub button: MouseButton,
+
+    pub mods: ClickModifiers,
+}
+
+impl ClickArgs {
+    pub(crate) fn from_raw(
+        minwid: i32,
+        clicks: u32,
+        button: &str,
+        mods: &str,
+    ) -> Result<Self> {
+        let button = match button {
+            "l" => MouseButton::Left,
+            "r" => MouseButton::Right,
+            "m" => MouseButton::Middle,
+            other => {
+                return Err(Error::DeserializeError(format!(
+                    "unknown mouse button \"{other}\""
+                )))
+            },
+        };
+
+        let mods = ClickModifiers {
+            shift: mods.contains('s'),
+            ctrl: mods.contains('c'),
+            alt: mods.contains('a'),
+            meta: mods.contains('m'),
+        };
+
+        Ok(Self { minwid, clicks, button, mods })
+    }
+}
+
+/// A Rust function that can be called when a region of the statusline, the
+/// winbar or the tabline is clicked.
+///
+/// Handlers are meant to be created once and then attached to as many
+/// regions as needed, either via [`Segment::on_click`](super::Segment::on_click)
+/// or via [`region`](ClickHandler::region) when building statusline strings
+/// by hand.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ClickHandler {
+    func: String,
+}
+
+impl ClickHandler {
+    pub fn new<F>(fun: F) -> Self
+    where
+        F: FnMut(ClickArgs) -> Result<()> + 'static,
+    {
+        let fun = LuaFnMut::<ClickArgs, ()>::from(fun);
+        let key = lua::add_global_fn(RENDER_FNS, fun.0);
+        Self { func: format!("v:lua.{RENDER_FNS}.{key}") }
+    }
+
+    /// Returns the statusline string that makes `text` clickable. Unlike
+    /// [`Segment`](super::Segment)s, `text` can contain other `%` items.
+    pub fn region(&self, minwid: i32, text: &str) -> String {
+        format!("{}{text}%X", self.start(minwid))
+    }
+
+    /// Returns the item starting a clickable region.
+    pub(crate) fn start(&self, minwid: i32) -> impl fmt::Display + '_ {
+        struct Start<'a>(&'a str, i32);
+
+        impl fmt::Display for Start<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "%{}@{}@", self.1, self.0)
+            }
+        }
+
+        Start(&self.func, minwid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn click_args_from_raw() {
+        let args = ClickArgs::from_raw(3, 2, "r", "s c ").unwrap();
+
+        assert_eq!(args.minwid, 3);
+        assert_eq!(args.clicks, 2);
+        assert_eq!(args.button, MouseButton::Right);
+        assert_eq!(
+            args.mods,
+            ClickModifiers { shift: true, ctrl: true, ..Default::default() }
+        );
+
+        assert!(ClickArgs::from_raw(0, 1, "x", "    ").is_err());
+    }
+}
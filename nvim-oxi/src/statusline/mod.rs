@@ -0,0 +1,95 @@
+//! A framework to build the statusline, the winbar or the tabline out of
+//! [`Segment`]s produced by a Rust function.
+//!
+//! Segments can be made clickable by attaching them a [`ClickHandler`].
+//!
+//! ```ignore
+//! use nvim_oxi::statusline::{self, Segment, Target};
+//!
+//! statusline::set(Target::Statusline, |win| {
+//!     let name = win.get_buf()?.get_name()?;
+//!     Ok(vec![
+//!         Segment::new(name.display().to_string()).hl("Title"),
+//!         Segment::separator(),
+//!         Segment::new(format!("{win}")),
+//!     ])
+//! })?;
+//! ```
+
+mod click;
+mod segment;
+
+pub use click::*;
+pub use segment::*;
+
+use crate::api::opts::{EvalStatuslineOpts, OptionValueOpts};
+use crate::api::{self, Window};
+use crate::lua::{self, LuaFnMut};
+use crate::Result;
+
+/// The Lua table storing the render functions.
+const RENDER_FNS: &str = "_nvim_oxi_statusline";
+
+/// The lines that can be rendered with [`set`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Target {
+    Statusline,
+    Winbar,
+
+    Tabline,
+}
+
+impl Target {
+    #[inline]
+    const fn option_name(&self) -> &'static str {
+        match self {
+            Self::Statusline => "statusline",
+            Self::Winbar => "winbar",
+            Self::Tabline => "tabline",
+        }
+    }
+}
+
+/// Sets `target` to be drawn by `render`, which receives the window being
+/// drawn and returns the segments to display. The tabline isn't drawn for
+/// a specific window, so its `render` receives the current window.
+pub fn set<F>(target: Target, mut render: F) -> Result<()>
+where
+    F: FnMut(Window) -> Result<Vec<Segment>> + 'static,
+{
+    let fun = LuaFnMut::<(), String>::from(move |()| {
+        let win = match target {
+            Target::Tabline => Window::current(),
+            _ => Window::from_number(api::get_var("statusline_winid")?)?,
+        };
+        Ok(segment::render(&render(win)?))
+    });
+
+    // Setting a target again releases its previous function.
+    let key = target.option_name();
+    lua::replace_global_fn(RENDER_FNS, key, fun.0);
+
+    let expr = format!("%!v:lua.{RENDER_FNS}.{key}()");
+    api::set_option_value(
+        target.option_name(),
+        expr,
+        &OptionValueOpts::default(),
+    )
+}
+
+/// Returns the display width the segments would have in `target` when
+/// drawn in `win`.
+pub fn width(
+    segments: &[Segment],
+    target: Target,
+    win: Window,
+) -> Result<usize> {
+    let opts = EvalStatuslineOpts::builder()
+        .winid(win)
+        .use_tabline(target == Target::Tabline)
+        .use_winbar(target == Target::Winbar)
+        .build()
+        .expect("all fields have defaults");
+
+    api::eval_statusline(&render(segments), &opts).map(|infos| infos.width)
+}
@@ -0,0 +1,94 @@
+use super::ClickHandler;
+
+/// A piece of a statusline, winbar or tabline.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Segment {
+    text: String,
+    hl_group: Option<String>,
+    on_click: Option<(ClickHandler, i32)>,
+    is_separator: bool,
+}
+
+impl Segment {
+    /// Creates a segment displaying `text` verbatim, i.e. without
+    /// interpreting any `%` item.
+    pub fn new<Text: Into<String>>(text: Text) -> Self {
+        Self { text: text.into(), ..Default::default() }
+    }
+
+    /// Creates a separation point. The space left in the line is evenly
+    /// distributed among the separators, which can be used to align
+    /// segments to the center or to the right.
+    pub fn separator() -> Self {
+        Self { is_separator: true, ..Default::default() }
+    }
+
+    /// Highlights the segment with the given highlight group.
+    pub fn hl<Group: Into<String>>(mut self, group: Group) -> Self {
+        self.hl_group = Some(group.into());
+        self
+    }
+
+    /// Calls `handler` when the segment is clicked, passing it `minwid`.
+    pub fn on_click(mut self, handler: &ClickHandler, minwid: i32) -> Self {
+        self.on_click = Some((handler.clone(), minwid));
+        self
+    }
+
+    /// Appends the segment to a statusline string.
+    fn render_into(&self, line: &mut String) {
+        if self.is_separator {
+            line.push_str("%=");
+            return;
+        }
+
+        if let Some((handler, minwid)) = &self.on_click {
+            line.push_str(&handler.start(*minwid).to_string());
+        }
+
+        if let Some(group) = &self.hl_group {
+            line.push_str("%#");
+            line.push_str(group);
+            line.push('#');
+        }
+
+        line.push_str(&self.text.replace('%', "%%"));
+
+        if self.hl_group.is_some() {
+            line.push_str("%*");
+        }
+
+        if self.on_click.is_some() {
+            line.push_str("%X");
+        }
+    }
+}
+
+/// Renders a list of segments into a string using the statusline syntax
+/// (see `:h 'statusline'`).
+pub fn render<'a, Segments>(segments: Segments) -> String
+where
+    Segments: IntoIterator<Item = &'a Segment>,
+{
+    let mut line = String::new();
+    for segment in segments {
+        segment.render_into(&mut line);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_segments() {
+        let segments = [
+            Segment::new("main.rs").hl("Title"),
+            Segment::separator(),
+            Segment::new("100%"),
+        ];
+
+        assert_eq!(render(&segments), "%#Title#main.rs%*%=100%%");
+    }
+}
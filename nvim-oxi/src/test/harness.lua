@@ -0,0 +1,32 @@
+local env = vim.env
+
+if env.NVIM_OXI_HARNESS_MODULE then
+  package.preload[env.NVIM_OXI_HARNESS_MODULE] = assert(package.loadlib(
+    env.NVIM_OXI_HARNESS_LIBRARY,
+    'luaopen_' .. env.NVIM_OXI_HARNESS_MODULE
+  ))
+end
+
+local ok, value
+local chunk, err = loadfile(env.NVIM_OXI_HARNESS_CODE)
+if chunk then
+  ok, value = pcall(chunk)
+else
+  ok, value = false, err
+end
+
+local result = {
+  ok = ok,
+  messages = vim.split(vim.fn.execute('messages'), '\n', { trimempty = true }),
+}
+if ok then
+  result.value = value
+else
+  result.error = tostring(value)
+end
+
+local file = assert(io.open(env.NVIM_OXI_HARNESS_OUTPUT, 'w'))
+file:write(vim.json.encode(result))
+file:close()
+
+vim.cmd(ok and 'qall!' or 'cquit 1')
@@ -0,0 +1,266 @@
+//! Running the same code against several Neovim releases, e.g. to check
+//! that a plugin works with every release it supports.
+
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{Harness, Output};
+use crate::{Error, Result};
+
+/// The environment variable [`Matrix::from_env`] reads the Neovim
+/// executables from, separated like the entries of `$PATH`.
+pub const NVIMS_ENV: &str = "NVIM_OXI_TEST_NVIMS";
+
+/// A Neovim version, as reported by `nvim --version`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+
+    /// Whether it's a development build (e.g. a nightly) leading up to the
+    /// release with this number.
+    pub dev: bool,
+}
+
+impl Version {
+    /// The oldest version the APIs enabled by the `neovim-*` features can
+    /// run on.
+    pub const fn required() -> Self {
+        if cfg!(feature = "neovim-nightly") {
+            Self { major: 0, minor: 10, patch: 0, dev: true }
+        } else if cfg!(feature = "neovim-0-9") {
+            Self { major: 0, minor: 9, patch: 0, dev: false }
+        } else {
+            Self { major: 0, minor: 8, patch: 0, dev: false }
+        }
+    }
+
+    /// Parses the first line of `nvim --version`, e.g. `NVIM v0.9.5` or
+    /// `NVIM v0.10.0-dev-1234+g0123abc`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let version = line.trim().strip_prefix("NVIM v")?;
+        let (numbers, dev) = match version.split_once('-') {
+            Some((numbers, suffix)) => (numbers, suffix.starts_with("dev")),
+            None => (version, false),
+        };
+
+        let mut numbers = numbers.splitn(3, '.').map(str::parse);
+        let mut next = || numbers.next()?.ok();
+        Some(Self { major: next()?, minor: next()?, patch: next()?, dev })
+    }
+
+    /// Returns the version of the Neovim executable at `nvim`.
+    pub fn of(nvim: &Path) -> Result<Self> {
+        let output = Command::new(nvim).arg("--version").output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout.lines().next().and_then(Self::parse).ok_or_else(|| {
+            Error::TestHarnessError(format!(
+                "couldn't get the version of {}",
+                nvim.display()
+            ))
+        })
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // A development build comes before the release it leads up to.
+        let key = |v: &Self| (v.major, v.minor, v.patch, !v.dev);
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.dev {
+            f.write_str("-dev")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the same code with a [`Harness`] against several Neovim
+/// executables, skipping the ones older than [`Version::required`].
+///
+/// ```ignore
+/// use nvim_oxi::test::{Harness, Matrix};
+///
+/// let harness = Harness::new().module("myplugin", "target/debug/libmyplugin.so");
+/// let report = Matrix::new(harness)
+///     .nvim("/opt/nvim-0.8/bin/nvim")
+///     .nvim("/opt/nvim-nightly/bin/nvim")
+///     .run_lua("require('myplugin').setup()");
+///
+/// assert!(report.is_success(), "{report}");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Matrix {
+    harness: Harness,
+    nvims: Vec<PathBuf>,
+}
+
+/// The outcome of running the code against every executable of a
+/// [`Matrix`].
+#[derive(Debug)]
+pub struct Report {
+    pub runs: Vec<Run>,
+}
+
+/// The outcome of running the code against a single executable.
+#[derive(Debug)]
+pub struct Run {
+    pub nvim: PathBuf,
+
+    /// `None` if the version couldn't be detected.
+    pub version: Option<Version>,
+
+    pub outcome: Outcome,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Passed(Output),
+    Failed(Error),
+
+    /// The executable is older than the version the APIs enabled by the
+    /// `neovim-*` features need.
+    Skipped {
+        required: Version,
+    },
+}
+
+impl Matrix {
+    /// Creates an empty matrix running code with `harness`, whose Neovim
+    /// executable is replaced by each of the matrix's.
+    pub fn new(harness: Harness) -> Self {
+        Self { harness, nvims: Vec::new() }
+    }
+
+    /// Creates a matrix with the executables listed in the
+    /// `NVIM_OXI_TEST_NVIMS` environment variable, or `None` if it's not
+    /// set.
+    pub fn from_env(harness: Harness) -> Option<Self> {
+        let nvims = env::var_os(NVIMS_ENV)?;
+        Some(Self { harness, nvims: env::split_paths(&nvims).collect() })
+    }
+
+    /// Adds a Neovim executable to run the code against.
+    pub fn nvim(mut self, path: impl Into<PathBuf>) -> Self {
+        self.nvims.push(path.into());
+        self
+    }
+
+    /// Runs `code` against every executable in turn.
+    pub fn run_lua(&self, code: &str) -> Report {
+        let required = Version::required();
+
+        let runs = self
+            .nvims
+            .iter()
+            .map(|nvim| {
+                let version = match Version::of(nvim) {
+                    Ok(version) => version,
+                    Err(err) => {
+                        return Run {
+                            nvim: nvim.clone(),
+                            version: None,
+                            outcome: Outcome::Failed(err),
+                        }
+                    },
+                };
+
+                let outcome = if version < required {
+                    Outcome::Skipped { required }
+                } else {
+                    match self.harness.clone().nvim(nvim).run_lua(code) {
+                        Ok(output) => Outcome::Passed(output),
+                        Err(err) => Outcome::Failed(err),
+                    }
+                };
+
+                Run { nvim: nvim.clone(), version: Some(version), outcome }
+            })
+            .collect();
+
+        Report { runs }
+    }
+}
+
+impl Report {
+    /// Whether the code didn't fail with any executable. Skipped ones don't
+    /// count as failures.
+    pub fn is_success(&self) -> bool {
+        !self.runs.iter().any(|run| matches!(run.outcome, Outcome::Failed(_)))
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for run in &self.runs {
+            match run.version {
+                Some(version) => write!(f, "{version}")?,
+                None => f.write_str("unknown version")?,
+            }
+            write!(f, " ({}): ", run.nvim.display())?;
+
+            match &run.outcome {
+                Outcome::Passed(_) => writeln!(f, "passed")?,
+                Outcome::Failed(err) => writeln!(f, "failed: {err}")?,
+                Outcome::Skipped { required } => {
+                    writeln!(f, "skipped, needs {required} or later")?
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(major: u32, minor: u32, patch: u32, dev: bool) -> Version {
+        Version { major, minor, patch, dev }
+    }
+
+    #[test]
+    fn parse_versions() {
+        assert_eq!(
+            Version::parse("NVIM v0.9.5"),
+            Some(version(0, 9, 5, false))
+        );
+        assert_eq!(
+            Version::parse("NVIM v0.10.0-dev-1234+g0123abc"),
+            Some(version(0, 10, 0, true))
+        );
+        assert_eq!(Version::parse("VIM - Vi IMproved 9.0"), None);
+    }
+
+    #[test]
+    fn dev_builds_come_before_their_release() {
+        assert!(version(0, 9, 5, false) < version(0, 10, 0, true));
+        assert!(version(0, 10, 0, true) < version(0, 10, 0, false));
+        assert!(version(0, 7, 2, false) < Version::required());
+    }
+
+    #[test]
+    fn missing_executables_fail() {
+        let report = Matrix::new(Harness::new())
+            .nvim("/nonexistent/nvim")
+            .run_lua("return 1");
+
+        assert!(!report.is_success());
+        assert!(report.runs[0].version.is_none());
+    }
+}
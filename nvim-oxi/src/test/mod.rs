@@ -0,0 +1,263 @@
+//! Utilities to test plugins inside a real Neovim instance.
+//!
+//! Tests are usually written with the [`test`](macro@crate::test) attribute,
+//! which builds the plugin and runs each test in its own headless Neovim.
+//! [`Harness`] is the building block it's based on, which can also be used
+//! directly to run a piece of Lua code against a plugin and check the
+//! editor's state afterwards.
+//!
+//! This module is only available with the `test` feature, which plugins
+//! usually enable on a dev-dependency on `nvim-oxi`.
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use nvim_oxi::test::Harness;
+//!
+//! let output = Harness::new()
+//!     .module("myplugin", "target/debug/libmyplugin.so")
+//!     .timeout(Duration::from_secs(5))
+//!     .run_lua(r#"
+//!         require("myplugin").setup()
+//!         return vim.api.nvim_buf_line_count(0)
+//!     "#)?;
+//!
+//! assert_eq!(output.value::<u32>()?, 1);
+//! ```
+//!
+//! Code running inside Neovim can use [`wait_for`] to wait for asynchronous
+//! work, e.g. a scheduled callback, instead of sleeping.
+//!
+//! A [`Matrix`] runs the same code against several Neovim executables. If
+//! the `NVIM_OXI_TEST_NVIMS` environment variable lists some, separated like
+//! the entries of `$PATH`, every `#[nvim_oxi::test]` is run against each of
+//! them, skipping the ones too old for the enabled `neovim-*` features.
+//!
+//! This crate is tested the same way: the modules gated behind the `test`
+//! feature hold `#[nvim_oxi::test]`s that call into Neovim, while the plain
+//! `#[test]`s only cover code that doesn't need a running instance.
+
+mod matrix;
+pub(crate) mod runner;
+
+use std::env;
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub use matrix::*;
+use nvim_types::object::Object;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::api;
+use crate::lua::LuaFnMut;
+use crate::{Error, Result};
+
+/// Launches a headless Neovim with a clean configuration, optionally
+/// loading a plugin's library, and runs a piece of Lua code in it.
+#[derive(Clone, Debug)]
+pub struct Harness {
+    nvim: PathBuf,
+    module: Option<(String, PathBuf)>,
+    timeout: Duration,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self {
+            nvim: "nvim".into(),
+            module: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// The outcome of [`Harness::run_lua`].
+#[derive(Clone, Debug)]
+pub struct Output {
+    /// The messages in the message history (see `:h :messages`) after
+    /// running the code.
+    pub messages: Vec<String>,
+
+    /// Everything Neovim wrote to stderr.
+    pub stderr: String,
+
+    value: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct RawOutput {
+    ok: bool,
+    #[serde(default)]
+    messages: Vec<String>,
+    value: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+impl Harness {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the Neovim executable to launch, `nvim` in `$PATH` by default.
+    pub fn nvim(mut self, path: impl Into<PathBuf>) -> Self {
+        self.nvim = path.into();
+        self
+    }
+
+    /// Makes `require(name)` load the module from `library`, i.e. by calling
+    /// its `luaopen_<name>` function.
+    pub fn module(
+        mut self,
+        name: impl Into<String>,
+        library: impl Into<PathBuf>,
+    ) -> Self {
+        self.module = Some((name.into(), library.into()));
+        self
+    }
+
+    /// Sets how long Neovim can run before being killed, 10 seconds by
+    /// default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Runs `code` in a new Neovim instance, which quits right after.
+    ///
+    /// Fails if the code raises an error, if Neovim crashes (e.g. because
+    /// of a panic in the plugin) or if it doesn't quit before the timeout.
+    pub fn run_lua(&self, code: &str) -> Result<Output> {
+        let dir = TempDir::new()?;
+        let driver = dir.0.join("harness.lua");
+        let script = dir.0.join("code.lua");
+        let output = dir.0.join("output.json");
+        let stderr = dir.0.join("stderr");
+
+        fs::write(&driver, include_str!("./harness.lua"))?;
+        fs::write(&script, code)?;
+
+        let mut cmd = Command::new(&self.nvim);
+        cmd.args(["--headless", "--clean", "-c"])
+            .arg("lua dofile(vim.env.NVIM_OXI_HARNESS_DRIVER)")
+            .env("NVIM_OXI_HARNESS_DRIVER", &driver)
+            .env("NVIM_OXI_HARNESS_CODE", &script)
+            .env("NVIM_OXI_HARNESS_OUTPUT", &output)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(File::create(&stderr)?);
+
+        if let Some((name, library)) = &self.module {
+            cmd.env("NVIM_OXI_HARNESS_MODULE", name)
+                .env("NVIM_OXI_HARNESS_LIBRARY", library);
+        }
+
+        let status = self.wait(cmd)?;
+        let stderr = fs::read_to_string(&stderr).unwrap_or_default();
+
+        let raw = match fs::read(&output) {
+            Ok(bytes) => serde_json::from_slice::<RawOutput>(&bytes)
+                .map_err(|err| Error::TestHarnessError(err.to_string()))?,
+
+            Err(_) => {
+                return Err(Error::TestHarnessError(format!(
+                    "Neovim exited ({status}) before running the code: \
+                     {stderr}"
+                )))
+            },
+        };
+
+        if !raw.ok {
+            return Err(Error::LuaError(raw.error.unwrap_or_default()));
+        }
+
+        Ok(Output { messages: raw.messages, stderr, value: raw.value })
+    }
+
+    fn wait(&self, mut cmd: Command) -> Result<ExitStatus> {
+        let mut child = cmd.spawn().map_err(|err| {
+            Error::TestHarnessError(format!(
+                "couldn't launch {}: {err}",
+                self.nvim.display()
+            ))
+        })?;
+
+        let start = Instant::now();
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(status);
+            }
+
+            if start.elapsed() > self.timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Error::TestTimeoutError(self.timeout));
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+impl Output {
+    /// Deserializes the value returned by the Lua code, which is converted
+    /// with `vim.json.encode`. A missing value deserializes from `null`.
+    pub fn value<T: DeserializeOwned>(&self) -> Result<T> {
+        let value = self.value.clone().unwrap_or_default();
+        serde_json::from_value(value)
+            .map_err(|err| Error::DeserializeError(err.to_string()))
+    }
+}
+
+/// Runs Neovim's event loop until `condition` returns `true`, failing if it
+/// doesn't within `timeout`. The condition is checked every 10ms.
+///
+/// Unlike sleeping, this processes events in the meantime, so scheduled
+/// callbacks, timers and redraws get a chance to run.
+pub fn wait_for<F>(mut condition: F, timeout: Duration) -> Result<()>
+where
+    F: FnMut() -> bool + 'static,
+{
+    let fun = LuaFnMut::<(), bool>::from(move |()| Ok(condition()));
+
+    let met = api::exec_lua::<_, bool>(
+        "local timeout, condition = ... return (vim.wait(timeout, condition, \
+         10))",
+        [Object::from(i64::try_from(timeout.as_millis())?), fun.into()],
+    );
+    fun.unref();
+
+    match met? {
+        true => Ok(()),
+        false => Err(Error::TestTimeoutError(timeout)),
+    }
+}
+
+/// A temporary directory removed on drop.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let dir = env::temp_dir().join(format!(
+            "nvim-oxi-harness-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        fs::create_dir_all(&dir)?;
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
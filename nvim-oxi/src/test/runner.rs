@@ -0,0 +1,109 @@
+//! Runs the tests defined with `#[nvim_oxi::test]` inside a headless Neovim
+//! instance.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use super::{Harness, Matrix};
+
+/// Builds the library containing the test `name`, loads it in a headless
+/// Neovim and calls its entrypoint, panicking if it returns an error, panics
+/// or if Neovim can't be started. If `NVIM_OXI_TEST_NVIMS` is set this is
+/// done with each of the Neovim executables it lists.
+///
+/// `features` lists every feature declared by the crate and whether it's
+/// enabled in the test binary, so that the library is built with the same
+/// ones.
+pub fn run(
+    manifest_dir: &str,
+    crate_name: &str,
+    name: &str,
+    features: &[(&str, bool)],
+) {
+    let library = build(manifest_dir, crate_name, features);
+    let module = format!("__nvim_oxi_test_{name}");
+
+    let harness = Harness::new().module(module.as_str(), library);
+    let code = format!("require({module:?})");
+
+    if let Some(matrix) = Matrix::from_env(harness.clone()) {
+        let report = matrix.run_lua(&code);
+        assert!(report.is_success(), "test `{name}` failed:\n{report}");
+        // Shows which versions were skipped, with `--nocapture`.
+        eprint!("{report}");
+        return;
+    }
+
+    if let Err(err) = harness.run_lua(&code) {
+        panic!("test `{name}` failed: {err}");
+    }
+}
+
+/// Builds the library once per test binary, returning its path.
+fn build(
+    manifest_dir: &str,
+    crate_name: &str,
+    features: &[(&str, bool)],
+) -> &'static Path {
+    static LIBRARY: OnceLock<PathBuf> = OnceLock::new();
+
+    LIBRARY.get_or_init(|| {
+        // The test binary lives in `<target-dir>/<profile>/deps`, or in
+        // `<target-dir>/<triple>/<profile>/deps` if `--target` was passed.
+        let exe = env::current_exe().expect("couldn't get test binary");
+        let profile_dir = exe
+            .parent()
+            .and_then(Path::parent)
+            .expect("test binary is in `<target-dir>/<profile>/deps`");
+
+        let profile = profile_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("profile directory has a valid UTF-8 name");
+
+        let parent = profile_dir.parent().expect("profile has a parent dir");
+
+        // Cargo tags the root of the target directory.
+        let (target_dir, triple) = match parent.join("CACHEDIR.TAG").exists() {
+            true => (parent, None),
+            false => (
+                parent.parent().expect("target triple has a parent dir"),
+                parent.file_name(),
+            ),
+        };
+
+        let enabled = features
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(feature, _)| *feature)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let mut cargo =
+            Command::new(env::var("CARGO").unwrap_or("cargo".into()));
+        cargo.args(["build", "--lib", "--manifest-path"]);
+        cargo.arg(Path::new(manifest_dir).join("Cargo.toml"));
+        cargo.arg("--target-dir").arg(target_dir);
+        // The `dev` profile's directory is called `debug`.
+        cargo.args([
+            "--profile",
+            if profile == "debug" { "dev" } else { profile },
+        ]);
+        if let Some(triple) = triple {
+            cargo.arg("--target").arg(triple);
+        }
+        // `default` is also listed if it's enabled.
+        cargo.args(["--no-default-features", "--features", &enabled]);
+
+        let status = cargo.status().expect("couldn't spawn `cargo`");
+        assert!(status.success(), "couldn't build the library to test");
+
+        profile_dir.join(format!(
+            "{}{crate_name}{}",
+            env::consts::DLL_PREFIX,
+            env::consts::DLL_SUFFIX
+        ))
+    })
+}
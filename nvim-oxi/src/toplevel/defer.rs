@@ -0,0 +1,122 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+use crate::lua;
+use crate::macros::cstr;
+use crate::Result;
+
+/// How many times in a row the first queued mutation can hit textlock before
+/// it's dropped, so that a mutation that can never run doesn't keep the
+/// event loop busy forever.
+const MAX_ATTEMPTS: u32 = 100;
+
+type Mutation = Box<dyn FnMut() -> Result<()>>;
+
+thread_local! {
+    static QUEUE: RefCell<VecDeque<Mutation>> = RefCell::new(VecDeque::new());
+    static FLUSH_SCHEDULED: Cell<bool> = const { Cell::new(false) };
+    static ATTEMPTS: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Runs `mutation` right away if Neovim currently allows it, or queues it
+/// to be run from the event loop as soon as it does.
+///
+/// Many API functions fail while textlock is active, e.g. inside `<expr>`
+/// mappings, `on_key` callbacks or while the completion menu is being
+/// filled, and almost all of them fail inside fast events like libuv
+/// callbacks. If `mutation` fails with such an error (see
+/// [`Error::is_textlock`](crate::Error::is_textlock)) it's queued instead of
+/// returning the error. Queued mutations run in the order they were
+/// deferred in, so a mutation is also queued if others are still pending.
+///
+/// Neovim rejects a locked API call before doing anything, so a mutation
+/// that failed because of textlock hasn't changed the editor's state.
+/// However, whatever the closure does before its first failing call may run
+/// more than once.
+///
+/// The returned error is the one of `mutation` if it ran right away and
+/// failed for another reason. Errors of queued mutations are raised from
+/// the event loop.
+///
+/// # Textlock-safe bindings
+///
+/// These don't need to be deferred: reading state (the `get_*` and
+/// `list_*` bindings, `Buffer::get_lines`, `Window::get_cursor`, ...),
+/// setting variables and options (`Buffer::set_var`,
+/// `api::set_option_value`, ...), `api::notify`, `api::echo` and
+/// `api::feedkeys`. Changing the text of a buffer (`Buffer::set_lines`,
+/// `Buffer::set_text`, ...), moving to another window or buffer, and
+/// opening or closing windows are not.
+pub fn defer_mutation<F>(mut mutation: F) -> Result<()>
+where
+    F: FnMut() -> Result<()> + 'static,
+{
+    let must_wait =
+        in_fast_event() || QUEUE.with(|queue| !queue.borrow().is_empty());
+
+    if !must_wait {
+        match mutation() {
+            Err(err) if err.is_textlock() => {},
+            res => return res,
+        }
+    }
+
+    QUEUE.with(|queue| queue.borrow_mut().push_back(Box::new(mutation)));
+    schedule_flush();
+    Ok(())
+}
+
+/// Runs the queued mutations, stopping at the first one that hits textlock
+/// again.
+fn flush() -> Result<()> {
+    FLUSH_SCHEDULED.with(|scheduled| scheduled.set(false));
+
+    let mut first_err = None;
+
+    // Not holding the borrow while running a mutation, since it could defer
+    // other ones.
+    while let Some(mut mutation) =
+        QUEUE.with(|queue| queue.borrow_mut().pop_front())
+    {
+        match mutation() {
+            Err(err) if err.is_textlock() => {
+                let attempts = ATTEMPTS.with(Cell::get) + 1;
+                if attempts < MAX_ATTEMPTS {
+                    ATTEMPTS.with(|n| n.set(attempts));
+                    QUEUE
+                        .with(|queue| queue.borrow_mut().push_front(mutation));
+                    schedule_flush();
+                    break;
+                }
+                ATTEMPTS.with(|n| n.set(0));
+                first_err.get_or_insert(err);
+            },
+            res => {
+                ATTEMPTS.with(|n| n.set(0));
+                if let Err(err) = res {
+                    first_err.get_or_insert(err);
+                }
+            },
+        }
+    }
+
+    first_err.map_or(Ok(()), Err)
+}
+
+fn schedule_flush() {
+    if !FLUSH_SCHEDULED.with(|scheduled| scheduled.replace(true)) {
+        crate::schedule(|()| flush());
+    }
+}
+
+/// Binding to `vim.in_fast_event`.
+fn in_fast_event() -> bool {
+    lua::with_state(|lstate| unsafe {
+        lua::lua_getglobal(lstate, cstr!("vim"));
+        lua::lua_getfield(lstate, -1, cstr!("in_fast_event"));
+        lua::lua_call(lstate, 0, 1);
+        let in_fast_event = lua::lua_toboolean(lstate, -1) == 1;
+        lua::lua_pop(lstate, 2);
+        in_fast_event
+    })
+}
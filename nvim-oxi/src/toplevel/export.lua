@@ -0,0 +1,10 @@
+local module, version, functions = ...
+
+assert(functions.version == nil, '`version` is reserved in exported APIs')
+
+local exported = { version = version }
+for name, fun in pairs(functions) do
+  exported[name] = fun
+end
+
+package.loaded[module .. '.api'] = exported
@@ -0,0 +1,45 @@
+use nvim_types::dictionary::Dictionary;
+use nvim_types::object::Object;
+
+use crate::api;
+use crate::lua::{self, LuaTable};
+use crate::Result;
+
+/// Publishes `functions` as the API of `module`, so that other plugins can
+/// call them without going through user commands.
+///
+/// The table is stored in `package.loaded["<module>.api"]`, so Lua plugins
+/// can get it with `require("<module>.api")` and Rust plugins with
+/// [`import_api`]. Besides the functions it contains a `version` field, which
+/// callers can check before relying on them.
+///
+/// ```ignore
+/// use nvim_oxi::{self as oxi, LuaFn};
+/// use nvim_types::dictionary::Dictionary;
+///
+/// let greet = LuaFn::from(|name: String| Ok(format!("Hello {name}!")));
+/// oxi::export_api("greeter", 1, Dictionary::from_iter([("greet", greet)]))?;
+/// ```
+pub fn export_api(
+    module: &str,
+    version: u32,
+    functions: Dictionary,
+) -> Result<()> {
+    api::exec_lua::<_, ()>(
+        include_str!("./export.lua"),
+        [Object::from(module), Object::from(version), functions.into()],
+    )
+}
+
+/// Returns the API published by `module` with [`export_api`], or by a Lua
+/// plugin in its `<module>.api` module.
+///
+/// ```ignore
+/// let greeter = nvim_oxi::import_api("greeter")?;
+/// if greeter.get::<u32>("version")? >= 1 {
+///     let msg = greeter.call::<_, String>("greet", "Mars")?;
+/// }
+/// ```
+pub fn import_api(module: &str) -> Result<LuaTable> {
+    lua::require(&format!("{module}.api"))
+}
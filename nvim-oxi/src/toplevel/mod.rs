@@ -1,3 +1,15 @@
+mod defer;
+mod export;
+mod panic;
+#[allow(clippy::module_inception)]
 mod toplevel;
+mod unload;
+mod version;
 
+pub use defer::defer_mutation;
+pub use export::{export_api, import_api};
+pub use panic::setup_panic_hook;
 pub use toplevel::*;
+pub(crate) use unload::{loading, unload_module};
+pub use unload::{on_unload, register_resource, unload, Resource};
+pub use version::{has, require_version, version};
@@ -0,0 +1,27 @@
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::thread;
+
+use crate::api;
+
+/// Installs a panic hook writing the panic message, its location and a
+/// backtrace to the message area with [`api::err_writeln`], instead of to
+/// stderr where Neovim doesn't show it.
+///
+/// Panics in functions called by Lua are caught and re-raised as Lua errors
+/// whether the hook is installed or not, but the error only contains the
+/// panic message. Has to be called from the thread Neovim runs on, usually
+/// in the plugin's entrypoint. Panics in other threads are forwarded to the
+/// previously installed hook.
+pub fn setup_panic_hook() {
+    let main_thread = thread::current().id();
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info: &PanicHookInfo| {
+        if thread::current().id() != main_thread {
+            return previous(info);
+        }
+        let backtrace = Backtrace::force_capture();
+        api::err_writeln(format!("{info}\n\nstack backtrace:\n{backtrace}"));
+    }));
+}
@@ -12,12 +12,87 @@ use crate::macros::cstr;
 #[macro_export]
 macro_rules! nprint {
     ($($arg:tt)*) => {{
-        crate::print(::std::fmt::format(format_args!($($arg)*)));
+        $crate::print(::std::fmt::format(format_args!($($arg)*)));
     }}
 }
 
 pub use nprint as print;
 
+/// Same as [`print!`](crate::print), since every message is displayed on its
+/// own line anyway. Useful to port code using the `std` macros, whose
+/// output goes nowhere when the crate is loaded by Neovim.
+#[macro_export]
+macro_rules! nprintln {
+    () => {
+        $crate::print!("")
+    };
+
+    ($($arg:tt)*) => {
+        $crate::print!($($arg)*)
+    };
+}
+
+pub use nprintln as println;
+
+/// Writes to the error buffer with `api::err_write`, using the same syntax
+/// as Rust's `format!` macro. Nothing is displayed until a newline is
+/// written, e.g. by [`eprintln!`](crate::eprintln).
+#[macro_export]
+macro_rules! neprint {
+    ($($arg:tt)*) => {{
+        $crate::api::err_write(::std::fmt::format(format_args!($($arg)*)));
+    }}
+}
+
+pub use neprint as eprint;
+
+/// Displays an error message with `api::err_writeln`, using the same syntax
+/// as Rust's `format!` macro.
+#[macro_export]
+macro_rules! neprintln {
+    ($($arg:tt)*) => {{
+        $crate::api::err_writeln(::std::fmt::format(format_args!($($arg)*)));
+    }}
+}
+
+pub use neprintln as eprintln;
+
+/// Like Rust's `dbg!` macro, but prints to the Neovim message area. Returns
+/// the value of the given expression.
+///
+/// # Examples
+///
+/// ```ignore
+/// let count = nvim_oxi::dbg!(buf.line_count()?);
+/// ```
+#[macro_export]
+macro_rules! ndbg {
+    () => {
+        $crate::print!("[{}:{}]", ::std::file!(), ::std::line!())
+    };
+
+    ($val:expr $(,)?) => {
+        match $val {
+            tmp => {
+                $crate::print!(
+                    "[{}:{}] {} = {:#?}",
+                    ::std::file!(),
+                    ::std::line!(),
+                    ::std::stringify!($val),
+                    &tmp
+                );
+                tmp
+            },
+        }
+    };
+
+    ($($val:expr),+ $(,)?) => {
+        ($($crate::dbg!($val)),+,)
+    };
+}
+
+pub use ndbg as dbg;
+
 /// Prints a message to the Neovim message area.
 #[doc(hidden)]
 pub fn print(text: impl Into<String>) {
@@ -0,0 +1,214 @@
+use std::cell::{Cell, RefCell};
+
+use nvim_types::LuaRef;
+
+use crate::api::{self, types::Mode, Buffer};
+use crate::lua;
+use crate::state;
+use crate::Result;
+
+type Release = Box<dyn FnOnce() -> Result<()>>;
+
+thread_local! {
+    /// The registered functions, together with the module that was being
+    /// loaded when they were registered, if any.
+    static REGISTRY: RefCell<Vec<(Option<&'static str>, Release)>> =
+        const { RefCell::new(Vec::new()) };
+
+    /// The module whose body is currently running.
+    static LOADING: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// A resource created by the plugin that should be released when the
+/// plugin is unloaded. See [`register_resource`].
+#[derive(Clone, Debug)]
+pub enum Resource {
+    /// An autocommand group, deleted together with its autocommands.
+    Augroup(u32),
+
+    /// An autocommand.
+    Autocmd(u32),
+
+    /// A mapping, buffer-local if `buffer` is set.
+    Keymap { mode: Mode, lhs: String, buffer: Option<Buffer> },
+
+    /// A user command, buffer-local if `buffer` is set.
+    UserCommand { name: String, buffer: Option<Buffer> },
+
+    /// A value stored in the Lua registry, usually a function created from
+    /// a Rust closure. Can be built from a `LuaFn`, `LuaFnMut` or
+    /// `LuaFnOnce`.
+    LuaRef(LuaRef),
+}
+
+impl Resource {
+    fn release(self) -> Result<()> {
+        match self {
+            Self::Augroup(id) => api::del_augroup_by_id(id),
+            Self::Autocmd(id) => api::del_autocmd(id),
+            Self::Keymap { mode, lhs, buffer: Some(mut buf) } => {
+                buf.del_keymap(mode, &lhs)
+            },
+            Self::Keymap { mode, lhs, buffer: None } => {
+                api::del_keymap(mode, &lhs)
+            },
+            Self::UserCommand { name, buffer: Some(mut buf) } => {
+                buf.del_user_command(&name)
+            },
+            Self::UserCommand { name, buffer: None } => {
+                api::del_user_command(&name)
+            },
+            Self::LuaRef(luaref) => {
+                lua::with_state(|lstate| unsafe {
+                    lua::luaL_unref(lstate, lua::LUA_REGISTRYINDEX, luaref)
+                });
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Registers a resource to be released by [`unload`].
+pub fn register_resource(resource: impl Into<Resource>) {
+    let resource = resource.into();
+    on_unload(move || resource.release());
+}
+
+/// Registers a function to be called by [`unload`], e.g. to stop timers or
+/// release resources not covered by [`Resource`].
+///
+/// The `on_unload` argument of [`#[nvim_oxi::module]`](crate::module)
+/// registers one every time the module is loaded.
+///
+/// Functions registered while a module is being loaded belong to it, and
+/// are also called when that module is reloaded.
+pub fn on_unload<F>(fun: F)
+where
+    F: FnOnce() -> Result<()> + 'static,
+{
+    let module = LOADING.with(Cell::get);
+    REGISTRY
+        .with(|registry| registry.borrow_mut().push((module, Box::new(fun))));
+}
+
+/// Releases the registered resources and calls the functions registered
+/// with [`on_unload`], in the reverse order they were registered in.
+///
+/// Plugins can expose it to let users unload them.
+///
+/// Every resource is released even if some fail. The first error is
+/// returned.
+pub fn unload() -> Result<()> {
+    let releases = REGISTRY.with(|registry| registry.take());
+    release(releases.into_iter().map(|(_, release)| release))
+}
+
+/// Releases what was registered while `module` was last loaded, which is
+/// done automatically when it's loaded again, e.g. by a plugin manager
+/// reloading the plugin after its `package.loaded` entry has been cleared.
+/// This way reloading doesn't leave behind the mappings, commands and
+/// autocommands of the previous load, while the other modules of the
+/// library keep theirs.
+pub(crate) fn unload_module(module: &'static str) -> Result<()> {
+    let releases = REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let (releases, others) = registry
+            .drain(..)
+            .partition::<Vec<_>, _>(|(owner, _)| *owner == Some(module));
+        *registry = others;
+        releases
+    });
+    release(releases.into_iter().map(|(_, release)| release))
+}
+
+/// Calls `releases` in reverse order, returning the first error.
+fn release(releases: impl DoubleEndedIterator<Item = Release>) -> Result<()> {
+    let mut res = Ok(());
+    for release in releases.rev() {
+        res = res.and(release());
+    }
+    res
+}
+
+/// Runs the body of `module`, attributing what it registers with
+/// [`on_unload`] to it and making its name the namespace of
+/// [`state`](crate::state). Modules can `require` each other, so the module
+/// that was being loaded before is restored afterwards, even if `body`
+/// panics.
+pub(crate) fn loading<R>(module: &'static str, body: impl FnOnce() -> R) -> R {
+    struct Restore(Option<&'static str>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            LOADING.with(|loading| loading.set(self.0));
+
+            // The namespace is kept once the outermost module is loaded, so
+            // that its callbacks keep using it.
+            if let Some(module) = self.0 {
+                state::set_namespace(module);
+            }
+        }
+    }
+
+    let _restore =
+        Restore(LOADING.with(|loading| loading.replace(Some(module))));
+    state::set_namespace(module);
+    body()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter(usize);
+
+    #[test]
+    fn modules_have_their_own_state() {
+        let count = || state::get_or_init::<Counter>().borrow().unwrap().0;
+
+        loading("outer", || {
+            state::get_or_init::<Counter>().borrow_mut().unwrap().0 = 1;
+
+            loading("inner", || {
+                assert_eq!(count(), 0);
+                state::get_or_init::<Counter>().borrow_mut().unwrap().0 = 2;
+            });
+
+            assert_eq!(count(), 1);
+        });
+
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn reloading_releases_only_that_module() {
+        let released = Rc::new(RefCell::new(Vec::new()));
+
+        let register = |name: &'static str| {
+            let released = Rc::clone(&released);
+            on_unload(move || {
+                released.borrow_mut().push(name);
+                Ok(())
+            });
+        };
+
+        loading("myplugin", || {
+            register("first");
+            loading("myplugin.utils", || register("utils"));
+            register("second");
+        });
+        register("outside");
+
+        unload_module("myplugin").unwrap();
+        assert_eq!(*released.borrow(), ["second", "first"]);
+
+        unload().unwrap();
+        assert_eq!(
+            *released.borrow(),
+            ["second", "first", "outside", "utils"]
+        );
+    }
+}
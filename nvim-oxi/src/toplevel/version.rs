@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use nvim_types::object::Object;
+
+use crate::api;
+use crate::{Error, Result};
+
+/// The `(major, minor)` version of the oldest Neovim the enabled `neovim-*`
+/// features can be loaded in.
+const TARGETED: (u32, u32) = if cfg!(feature = "neovim-nightly") {
+    (0, 10)
+} else if cfg!(feature = "neovim-0-9") {
+    (0, 9)
+} else {
+    (0, 8)
+};
+
+thread_local! {
+    static HAS_CACHE: RefCell<HashMap<String, bool>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Checks whether the running Neovim has `feature`, e.g. `"nvim-0.10"` or
+/// `"win32"`. See `:h has()` for the available features.
+///
+/// The result is cached, since a feature can't appear or disappear while
+/// Neovim is running. Calling `has()` can fail, e.g. in a fast event, in
+/// which case `false` is returned without caching it. Consider using the
+/// [`nvim_has!`](crate::nvim_has) macro instead.
+///
+/// Versions up to the one targeted by the `neovim-*` features, e.g.
+/// `"nvim-0.9"` with `neovim-0-9`, are known to be there without asking
+/// Neovim.
+pub fn has(feature: &str) -> bool {
+    if is_targeted(feature, TARGETED) {
+        return true;
+    }
+
+    if let Some(has) =
+        HAS_CACHE.with(|cache| cache.borrow().get(feature).copied())
+    {
+        return has;
+    }
+
+    let Ok(has) = api::call_function::<_, i64>("has", [feature]) else {
+        return false;
+    };
+
+    let has = has == 1;
+    HAS_CACHE.with(|cache| cache.borrow_mut().insert(feature.to_owned(), has));
+    has
+}
+
+/// Whether `feature` is `nvim-{major}.{minor}[.{patch}]` for a version that
+/// isn't newer than `targeted`.
+fn is_targeted(feature: &str, targeted: (u32, u32)) -> bool {
+    let Some(version) = feature.strip_prefix("nvim-") else { return false };
+    let mut numbers = version.split('.').map(str::parse::<u32>);
+
+    let (Some(Ok(major)), Some(Ok(minor))) = (numbers.next(), numbers.next())
+    else {
+        return false;
+    };
+    let patch = match numbers.next() {
+        None => 0,
+        Some(Ok(patch)) => patch,
+        Some(Err(_)) => return false,
+    };
+
+    numbers.next().is_none()
+        && (major, minor, patch) <= (targeted.0, targeted.1, 0)
+}
+
+/// Returns the `(major, minor, patch)` version of the running Neovim.
+pub fn version() -> Result<(u32, u32, u32)> {
+    api::exec_lua(
+        "local v = vim.version() return { v.major, v.minor, v.patch }",
+        [] as [Object; 0],
+    )
+}
+
+/// Fails with a user-facing error if the running Neovim is older than
+/// `major.minor.patch`. Usually called through the
+/// [`require_version!`](crate::require_version) macro when the plugin is
+/// loaded.
+pub fn require_version(major: u32, minor: u32, patch: u32) -> Result<()> {
+    if has(&format!("nvim-{major}.{minor}.{patch}")) {
+        return Ok(());
+    }
+
+    let found = match version() {
+        Ok((major, minor, patch)) => format!("{major}.{minor}.{patch}"),
+        Err(_) => "an unknown version".into(),
+    };
+
+    Err(Error::VersionError {
+        required: format!("{major}.{minor}.{patch}"),
+        found,
+    })
+}
+
+/// Checks whether the running Neovim has a feature, with the result cached
+/// after the first check. Expands to a call to [`has`](crate::has), which
+/// doesn't call into Neovim for the versions targeted by the `neovim-*`
+/// features.
+///
+/// ```ignore
+/// if nvim_oxi::nvim_has!("nvim-0.10") {
+///     // Use an API added in 0.10.
+/// }
+/// ```
+#[macro_export]
+macro_rules! nvim_has {
+    ($feature:expr) => {
+        $crate::has($feature)
+    };
+}
+
+/// Returns an error if the running Neovim is older than the given version,
+/// to be propagated from the plugin's entrypoint so that users get a clear
+/// message instead of failing API calls.
+///
+/// ```ignore
+/// #[nvim_oxi::module]
+/// fn myplugin() -> nvim_oxi::Result<()> {
+///     nvim_oxi::require_version!(0, 9)?;
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! require_version {
+    ($major:expr, $minor:expr) => {
+        $crate::require_version($major, $minor, 0)
+    };
+
+    ($major:expr, $minor:expr, $patch:expr) => {
+        $crate::require_version($major, $minor, $patch)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targeted_versions() {
+        assert!(is_targeted("nvim-0.8", (0, 9)));
+        assert!(is_targeted("nvim-0.9", (0, 9)));
+        assert!(is_targeted("nvim-0.9.0", (0, 9)));
+        assert!(!is_targeted("nvim-0.9.1", (0, 9)));
+        assert!(!is_targeted("nvim-0.10", (0, 9)));
+        assert!(!is_targeted("nvim-1", (0, 9)));
+        assert!(!is_targeted("nvim-0.9.x", (0, 9)));
+        assert!(!is_targeted("win32", (0, 9)));
+    }
+}
@@ -0,0 +1,24 @@
+-- Returns the captures of a query in a buffer as plain tables.
+local buf, lang, source, start, stop = ...
+
+local parse_query = vim.treesitter.query.parse or vim.treesitter.parse_query
+local get_node_text = vim.treesitter.get_node_text
+  or vim.treesitter.query.get_node_text
+
+local tree = vim.treesitter.get_parser(buf, lang):parse()[1]
+local query = parse_query(lang, source)
+
+local captures = {}
+for id, node, _ in query:iter_captures(tree:root(), buf, start, stop) do
+  local start_row, start_col, end_row, end_col = node:range()
+  table.insert(captures, {
+    name = query.captures[id],
+    kind = node:type(),
+    text = get_node_text(node, buf),
+    start_row = start_row,
+    start_col = start_col,
+    end_row = end_row,
+    end_col = end_col,
+  })
+end
+return captures
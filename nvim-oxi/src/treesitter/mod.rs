@@ -0,0 +1,71 @@
+//! Bindings to the `vim.treesitter` Lua module.
+//!
+//! Syntax trees live on the Lua side, so queries return snapshots of the
+//! captured nodes instead of references to them.
+
+mod query;
+
+use nvim_types::object::Object;
+pub use query::*;
+
+use crate::api::{self, Buffer};
+use crate::Result;
+
+/// The parser of a buffer, see `:h vim.treesitter.get_parser()`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Parser {
+    buf: Buffer,
+    lang: String,
+}
+
+/// Returns the parser for `buf`, using the language of its filetype if
+/// `lang` is `None`. Fails if there's no parser for the language.
+pub fn get_parser(buf: Buffer, lang: Option<&str>) -> Result<Parser> {
+    let lang = api::exec_lua(
+        "return vim.treesitter.get_parser(...):lang()",
+        [Object::from(buf.0), lang.into()],
+    )?;
+    Ok(Parser { buf, lang })
+}
+
+impl Parser {
+    pub fn buf(&self) -> Buffer {
+        self.buf
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// Updates the syntax tree to the current contents of the buffer. Only
+    /// the parts of the buffer that changed since the last parse are
+    /// reparsed.
+    pub fn parse(&self) -> Result<()> {
+        api::exec_lua(
+            "vim.treesitter.get_parser(...):parse()",
+            [Object::from(self.buf.0), self.lang.as_str().into()],
+        )
+    }
+
+    /// Returns the captures of `query` in the buffer between the rows
+    /// `start` (inclusive) and `end` (exclusive), or in the whole buffer if
+    /// they're `None`.
+    pub fn captures(
+        &self,
+        query: &Query,
+        start: Option<usize>,
+        end: Option<usize>,
+    ) -> Result<impl Iterator<Item = QueryCapture>> {
+        let captures: Vec<QueryCapture> = api::exec_lua(
+            include_str!("./captures.lua"),
+            [
+                Object::from(self.buf.0),
+                self.lang.as_str().into(),
+                query.source().into(),
+                start.map(|row| row as i64).into(),
+                end.map(|row| row as i64).into(),
+            ],
+        )?;
+        Ok(captures.into_iter())
+    }
+}
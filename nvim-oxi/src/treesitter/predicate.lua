@@ -0,0 +1,29 @@
+-- Registers a query predicate implemented in Rust, which receives the
+-- predicate's arguments with the captures replaced by their text. The
+-- function is looked up in the `fns` global table on every call, so that it
+-- can be replaced or removed from Rust.
+local name, fns = ...
+
+local get_node_text = vim.treesitter.get_node_text
+  or vim.treesitter.query.get_node_text
+
+vim.treesitter.query.add_predicate(name, function(match, _, source, predicate)
+  local args = {}
+  for i = 2, #predicate do
+    local arg = predicate[i]
+    if type(arg) == "number" then
+      local node = match[arg]
+      -- Newer versions pass every node captured by a quantified capture.
+      if type(node) == "table" then
+        node = node[#node]
+      end
+      arg = node and get_node_text(node, source) or ""
+    end
+    table.insert(args, arg)
+  end
+  local fun = _G[fns] and _G[fns][name]
+  if not fun then
+    error(("the `%s` predicate was unloaded"):format(name))
+  end
+  return fun(args)
+end, true)
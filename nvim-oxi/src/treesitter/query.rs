@@ -0,0 +1,83 @@
+use serde::Deserialize;
+
+use crate::api;
+use crate::lua::{self, LuaFnMut};
+use crate::Result;
+
+/// A query validated against a language, see `:h treesitter-query`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Query {
+    lang: String,
+    source: String,
+}
+
+/// A node captured by a [`Query`]. Rows and columns are 0-indexed, with the
+/// end column being exclusive.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct QueryCapture {
+    /// The name of the capture, without the leading `@`.
+    pub name: String,
+
+    /// The type of the node, e.g. `function_item`.
+    pub kind: String,
+
+    /// The text of the node.
+    pub text: String,
+
+    pub start_row: usize,
+    pub start_col: usize,
+    pub end_row: usize,
+    pub end_col: usize,
+}
+
+/// The Lua table storing the predicates registered with [`add_predicate`].
+const PREDICATE_FNS: &str = "_nvim_oxi_predicates";
+
+/// Arguments passed to a predicate registered with [`add_predicate`], with
+/// the captures replaced by the text of their node.
+pub(crate) type PredicateArgs = Vec<String>;
+
+impl Query {
+    /// Parses a query for `lang`, failing if it's invalid.
+    pub fn parse(lang: &str, source: &str) -> Result<Self> {
+        api::exec_lua::<_, ()>(
+            "local parse = vim.treesitter.query.parse or \
+             vim.treesitter.parse_query; parse(...)",
+            [lang, source],
+        )?;
+        Ok(Self { lang: lang.to_owned(), source: source.to_owned() })
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// Sets the query of the given kind (e.g. `highlights` or `injections`)
+/// for `lang`, replacing the one in the runtime files.
+pub fn set_query(lang: &str, kind: &str, source: &str) -> Result<()> {
+    api::exec_lua(
+        "local set = vim.treesitter.query.set or vim.treesitter.set_query; \
+         set(...)",
+        [lang, kind, source],
+    )
+}
+
+/// Registers a predicate usable in any query as `#{name}`, e.g.
+/// `(#is-upper? @name)`. The predicate receives its arguments with the
+/// captures replaced by the text of their node.
+///
+/// Registering a predicate with the same name replaces it. The predicate is
+/// removed when the plugin is unloaded, after which queries using it fail.
+pub fn add_predicate<F>(name: &str, fun: F) -> Result<()>
+where
+    F: FnMut(Vec<String>) -> Result<bool> + 'static,
+{
+    let fun = LuaFnMut::<PredicateArgs, bool>::from(fun);
+    lua::replace_global_fn(PREDICATE_FNS, name, fun.0);
+    api::exec_lua(include_str!("./predicate.lua"), [name, PREDICATE_FNS])
+}
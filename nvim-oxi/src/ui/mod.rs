@@ -0,0 +1,52 @@
+//! Helpers for interacting with the user.
+
+use nvim_types::object::Object;
+
+use crate::api;
+use crate::Result;
+
+/// The outcome of [`prompt_with_timeout`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PromptResult {
+    /// The user pressed a key before the timeout expired.
+    Key(String),
+
+    /// The timeout expired, contains the default key.
+    TimedOut(String),
+}
+
+impl PromptResult {
+    /// Returns the key that was pressed, or the default one if the timeout
+    /// expired.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Key(key) | Self::TimedOut(key) => key,
+        }
+    }
+
+    pub fn timed_out(&self) -> bool {
+        matches!(self, Self::TimedOut(_))
+    }
+}
+
+/// Shows `message` followed by a countdown in the message area and waits
+/// until either a key is pressed or `timeout_ms` milliseconds have passed,
+/// in which case `default` is returned as the key.
+///
+/// This blocks the caller, but Neovim keeps processing events and redrawing
+/// the screen while waiting.
+pub fn prompt_with_timeout(
+    message: &str,
+    timeout_ms: u32,
+    default: &str,
+) -> Result<PromptResult> {
+    let key = api::exec_lua::<_, Option<String>>(
+        include_str!("./prompt.lua"),
+        [Object::from(message), timeout_ms.into()],
+    )?;
+
+    Ok(match key {
+        Some(key) => PromptResult::Key(key),
+        None => PromptResult::TimedOut(default.to_owned()),
+    })
+}
@@ -0,0 +1,34 @@
+-- Shows a message with a countdown until a key is pressed or the timeout
+-- expires, returning the key or nil. `vim.wait` keeps processing events
+-- while waiting, so the UI stays responsive.
+local message, timeout = ...
+
+local deadline = vim.loop.now() + timeout
+local key
+
+while true do
+  local remaining = deadline - vim.loop.now()
+  if remaining <= 0 then
+    break
+  end
+
+  local secs = math.ceil(remaining / 1000)
+  vim.api.nvim_echo({ { ("%s (%ds)"):format(message, secs) } }, false, {})
+  vim.cmd("redraw")
+
+  -- Only wait until the countdown needs to be updated.
+  local step = (remaining - 1) % 1000 + 1
+  local pressed = vim.wait(step, function()
+    return vim.fn.getchar(1) ~= 0
+  end, 10)
+
+  if pressed then
+    key = vim.fn.getcharstr()
+    break
+  end
+end
+
+vim.api.nvim_echo({ { "" } }, false, {})
+vim.cmd("redraw")
+
+return key
@@ -0,0 +1,152 @@
+//! Opt-in bookkeeping of the `String`s, `Array`s and `Dictionary`s whose
+//! buffers are allocated and freed on the Rust side, enabled by the
+//! `alloc-stats` feature. When the feature is disabled all the hooks
+//! compile down to nothing.
+
+use std::any::TypeId;
+#[cfg(feature = "alloc-stats")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::object::Object;
+
+#[derive(Copy, Clone)]
+pub(crate) enum Kind {
+    String,
+    Array,
+    Dictionary,
+}
+
+impl Kind {
+    /// The kind of a `Collection<T>`, which is either an `Array` or a
+    /// `Dictionary`.
+    #[inline]
+    pub(crate) fn of_collection<T: 'static>() -> Self {
+        if TypeId::of::<T>() == TypeId::of::<Object>() {
+            Self::Array
+        } else {
+            Self::Dictionary
+        }
+    }
+}
+
+/// Allocation counts for a single kind of value.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct AllocCounts {
+    pub allocated: usize,
+    pub freed: usize,
+    pub bytes_allocated: usize,
+    pub bytes_freed: usize,
+}
+
+impl AllocCounts {
+    /// The number of values allocated but not yet freed.
+    ///
+    /// Values allocated by Neovim are only counted when they're freed, so
+    /// this can be negative.
+    #[inline]
+    pub fn live(&self) -> isize {
+        self.allocated as isize - self.freed as isize
+    }
+
+    /// The number of bytes allocated but not yet freed. Can be negative for
+    /// the same reason as [`live`](AllocCounts::live).
+    #[inline]
+    pub fn live_bytes(&self) -> isize {
+        self.bytes_allocated as isize - self.bytes_freed as isize
+    }
+}
+
+/// A snapshot of the allocation counts of every kind of value.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct AllocStats {
+    pub strings: AllocCounts,
+    pub arrays: AllocCounts,
+    pub dictionaries: AllocCounts,
+}
+
+#[cfg(feature = "alloc-stats")]
+struct Counters {
+    allocated: AtomicUsize,
+    freed: AtomicUsize,
+    bytes_allocated: AtomicUsize,
+    bytes_freed: AtomicUsize,
+}
+
+#[cfg(feature = "alloc-stats")]
+impl Counters {
+    const fn new() -> Self {
+        Self {
+            allocated: AtomicUsize::new(0),
+            freed: AtomicUsize::new(0),
+            bytes_allocated: AtomicUsize::new(0),
+            bytes_freed: AtomicUsize::new(0),
+        }
+    }
+
+    fn snapshot(&self) -> AllocCounts {
+        AllocCounts {
+            allocated: self.allocated.load(Ordering::Relaxed),
+            freed: self.freed.load(Ordering::Relaxed),
+            bytes_allocated: self.bytes_allocated.load(Ordering::Relaxed),
+            bytes_freed: self.bytes_freed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+static COUNTERS: [Counters; 3] =
+    [Counters::new(), Counters::new(), Counters::new()];
+
+/// Records the allocation of a buffer of `bytes` bytes. Empty buffers
+/// aren't allocated and are not recorded.
+#[inline(always)]
+pub(crate) fn on_alloc(kind: Kind, bytes: usize) {
+    #[cfg(feature = "alloc-stats")]
+    if bytes != 0 {
+        let counters = &COUNTERS[kind as usize];
+        counters.allocated.fetch_add(1, Ordering::Relaxed);
+        counters.bytes_allocated.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    let _ = (kind, bytes);
+}
+
+/// Records the release of a buffer of `bytes` bytes.
+#[inline(always)]
+pub(crate) fn on_free(kind: Kind, bytes: usize) {
+    #[cfg(feature = "alloc-stats")]
+    if bytes != 0 {
+        let counters = &COUNTERS[kind as usize];
+        counters.freed.fetch_add(1, Ordering::Relaxed);
+        counters.bytes_freed.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    let _ = (kind, bytes);
+}
+
+/// Returns the allocation counts recorded since the library was loaded.
+#[cfg(feature = "alloc-stats")]
+pub fn alloc_stats() -> AllocStats {
+    AllocStats {
+        strings: COUNTERS[Kind::String as usize].snapshot(),
+        arrays: COUNTERS[Kind::Array as usize].snapshot(),
+        dictionaries: COUNTERS[Kind::Dictionary as usize].snapshot(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::KeyValuePair;
+
+    #[test]
+    fn collection_kinds() {
+        assert!(matches!(Kind::of_collection::<Object>(), Kind::Array));
+        assert!(matches!(
+            Kind::of_collection::<KeyValuePair>(),
+            Kind::Dictionary
+        ));
+    }
+}
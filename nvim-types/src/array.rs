@@ -1,4 +1,6 @@
 use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::slice::SliceIndex;
 use std::{fmt, ptr};
 
 use super::collection::Collection;
@@ -7,6 +9,79 @@ use super::object::Object;
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L95
 pub type Array = Collection<Object>;
 
+impl<I> Index<I> for Array
+where
+    I: SliceIndex<[Object]>,
+{
+    type Output = <I as SliceIndex<[Object]>>::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        self.deref().index(index)
+    }
+}
+
+impl Array {
+    /// Appends an object to the back of the array.
+    pub fn push<V: Into<Object>>(&mut self, value: V) {
+        let value = value.into();
+        self.with_vec(|items| items.push(value));
+    }
+
+    /// Removes the last object of the array and returns it, or `None` if
+    /// it's empty.
+    pub fn pop(&mut self) -> Option<Object> {
+        self.with_vec(Vec::pop)
+    }
+
+    /// Inserts an object at position `idx`, shifting all the objects after
+    /// it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx > len`.
+    pub fn insert<V: Into<Object>>(&mut self, idx: usize, value: V) {
+        let value = value.into();
+        self.with_vec(|items| items.insert(idx, value));
+    }
+
+    /// Removes and returns the object at position `idx`, shifting all the
+    /// objects after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn remove(&mut self, idx: usize) -> Object {
+        self.with_vec(|items| items.remove(idx))
+    }
+}
+
+impl DerefMut for Array {
+    fn deref_mut(&mut self) -> &mut [Object] {
+        self.as_mut_slice()
+    }
+}
+
+impl<I> IndexMut<I> for Array
+where
+    I: SliceIndex<[Object]>,
+{
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        self.deref_mut().index_mut(index)
+    }
+}
+
+/// Unlike collecting into an `Array`, extending one keeps the nil objects.
+impl<T> Extend<T> for Array
+where
+    Object: From<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.with_vec(|items| {
+            items.extend(iter.into_iter().map(Object::from))
+        });
+    }
+}
+
 impl fmt::Debug for Array {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
@@ -74,3 +149,38 @@ where
             .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn integers(array: &Array) -> Vec<i64> {
+        array.iter().map(|obj| i64::try_from(obj.clone()).unwrap()).collect()
+    }
+
+    #[test]
+    fn edit_in_place() {
+        let mut array = Array::from_iter([1, 2]);
+        array.push(3);
+        array.insert(0, 0);
+        assert_eq!(integers(&array), [0, 1, 2, 3]);
+
+        assert_eq!(i64::try_from(array.remove(1)).unwrap(), 1);
+        assert_eq!(i64::try_from(array.pop().unwrap()).unwrap(), 3);
+
+        for obj in array.iter_mut() {
+            *obj = (i64::try_from(obj.clone()).unwrap() * 10).into();
+        }
+        array[0] = 5.into();
+        assert_eq!(integers(&array), [5, 20]);
+    }
+
+    #[test]
+    fn extend_keeps_nils() {
+        let mut array = Array::new();
+        array.extend([Object::nil(), Object::from(1)]);
+        assert_eq!(array.len(), 2);
+        assert!(array[0].is_nil());
+        assert!(array.get(2).is_none());
+    }
+}
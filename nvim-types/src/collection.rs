@@ -1,12 +1,15 @@
 //! This module contains functionality common to both `Array`s and
 //! `Dictionary`s.
 
-use std::ops::{Deref, Index};
+use std::mem::{self, size_of};
+use std::ops::Deref;
 use std::ptr::NonNull;
-use std::slice::{self, SliceIndex};
+use std::slice;
 
 use libc::size_t;
 
+use crate::alloc::{self, Kind};
+
 #[repr(C)]
 pub struct Collection<T> {
     pub(crate) items: NonNull<T>,
@@ -27,11 +30,35 @@ impl<T> Collection<T> {
         self.size
     }
 
+    /// Returns `true` if the collection contains no items.
     #[inline]
-    pub(crate) fn as_slice(&self) -> &[T] {
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a slice containing the items of the collection.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
         unsafe { slice::from_raw_parts(self.items.as_ptr(), self.size) }
     }
 
+    #[inline]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.items.as_ptr(), self.size) }
+    }
+
+    /// Calls `fun` with the items as a `Vec`, which can be freely modified.
+    pub(crate) fn with_vec<F, R>(&mut self, fun: F) -> R
+    where
+        T: 'static,
+        F: FnOnce(&mut Vec<T>) -> R,
+    {
+        let mut vec = Vec::from(mem::take(self));
+        let res = fun(&mut vec);
+        *self = vec.into();
+        res
+    }
+
     #[inline]
     pub(crate) unsafe fn from_raw_parts(
         ptr: *mut T,
@@ -42,7 +69,14 @@ impl<T> Collection<T> {
     }
 }
 
-impl<T: Clone> Clone for Collection<T> {
+impl<T> Default for Collection<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + 'static> Clone for Collection<T> {
     fn clone(&self) -> Self {
         self.as_slice().to_owned().into()
     }
@@ -56,31 +90,25 @@ impl<T> Deref for Collection<T> {
     }
 }
 
-impl<I, T> Index<I> for Collection<T>
-where
-    I: SliceIndex<[T]>,
-{
-    type Output = <I as SliceIndex<[T]>>::Output;
-
-    fn index(&self, index: I) -> &Self::Output {
-        self.deref().index(index)
-    }
-}
-
-impl<T> From<Vec<T>> for Collection<T> {
+impl<T: 'static> From<Vec<T>> for Collection<T> {
     #[inline]
     fn from(vec: Vec<T>) -> Self {
         let size = vec.len();
         let capacity = vec.capacity();
+        alloc::on_alloc(Kind::of_collection::<T>(), capacity * size_of::<T>());
         let ptr = vec.leak() as *mut [T] as *mut T;
 
         unsafe { Self::from_raw_parts(ptr, size, capacity) }
     }
 }
 
-impl<T> From<Collection<T>> for Vec<T> {
+impl<T: 'static> From<Collection<T>> for Vec<T> {
     #[inline]
     fn from(coll: Collection<T>) -> Self {
+        alloc::on_free(
+            Kind::of_collection::<T>(),
+            coll.capacity * size_of::<T>(),
+        );
         unsafe {
             Vec::from_raw_parts(coll.items.as_ptr(), coll.size, coll.capacity)
         }
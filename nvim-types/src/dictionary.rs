@@ -1,5 +1,6 @@
 use std::collections::HashMap as StdHashMap;
-use std::mem::ManuallyDrop;
+use std::mem::{self, ManuallyDrop};
+use std::ops::{Deref, Index};
 use std::{fmt, ptr};
 
 use super::collection::Collection;
@@ -13,8 +14,8 @@ pub type Dictionary = Collection<KeyValuePair>;
 #[derive(Clone)]
 #[repr(C)]
 pub struct KeyValuePair {
-    key: String,
-    value: Object,
+    pub(crate) key: String,
+    pub(crate) value: Object,
 }
 
 impl fmt::Debug for KeyValuePair {
@@ -36,6 +37,130 @@ where
     }
 }
 
+impl KeyValuePair {
+    #[inline]
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    #[inline]
+    pub fn value(&self) -> &Object {
+        &self.value
+    }
+
+    #[inline]
+    pub fn value_mut(&mut self) -> &mut Object {
+        &mut self.value
+    }
+}
+
+impl Dictionary {
+    /// Returns a reference to the value of `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&Object> {
+        self.position(key).map(|idx| &self.as_slice()[idx].value)
+    }
+
+    /// Returns a mutable reference to the value of `key`, if present.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Object> {
+        self.position(key).map(|idx| &mut self.as_mut_slice()[idx].value)
+    }
+
+    /// Returns `true` if the dictionary contains `key`.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Sets the value of `key`, returning its previous value if it was
+    /// already present. New keys are appended at the end.
+    pub fn insert<K, V>(&mut self, key: K, value: V) -> Option<Object>
+    where
+        K: Into<String>,
+        V: Into<Object>,
+    {
+        let pair = KeyValuePair::from((key, value));
+
+        match self.position_bytes(pair.key.as_bytes()) {
+            Some(idx) => {
+                Some(mem::replace(&mut self.as_mut_slice()[idx], pair).value)
+            },
+            None => {
+                self.with_vec(|pairs| pairs.push(pair));
+                None
+            },
+        }
+    }
+
+    /// Removes `key` from the dictionary, returning its value if it was
+    /// present. The order of the other keys is preserved.
+    pub fn remove(&mut self, key: &str) -> Option<Object> {
+        let idx = self.position(key)?;
+        Some(self.with_vec(|pairs| pairs.remove(idx)).value)
+    }
+
+    /// Returns a mutable reference to the value of `key`, inserting the
+    /// value returned by `default` first if the key is missing.
+    pub fn get_or_insert_with<V, F>(
+        &mut self,
+        key: &str,
+        default: F,
+    ) -> &mut Object
+    where
+        V: Into<Object>,
+        F: FnOnce() -> V,
+    {
+        let idx = match self.position(key) {
+            Some(idx) => idx,
+            None => {
+                self.insert(key, default());
+                self.len() - 1
+            },
+        };
+        &mut self.as_mut_slice()[idx].value
+    }
+
+    /// An iterator over the keys of the dictionary, in order.
+    pub fn keys(&self) -> impl Iterator<Item = &String> + '_ {
+        self.iter().map(KeyValuePair::key)
+    }
+
+    /// An iterator over the values of the dictionary, in order.
+    pub fn values(&self) -> impl Iterator<Item = &Object> + '_ {
+        self.iter().map(KeyValuePair::value)
+    }
+
+    #[inline]
+    fn position(&self, key: &str) -> Option<usize> {
+        self.position_bytes(key.as_bytes())
+    }
+
+    fn position_bytes(&self, key: &[u8]) -> Option<usize> {
+        self.iter().position(|pair| pair.key.as_bytes() == key)
+    }
+}
+
+impl Index<usize> for Dictionary {
+    type Output = KeyValuePair;
+
+    /// Returns the key-value pair at position `idx`.
+    fn index(&self, idx: usize) -> &KeyValuePair {
+        &self.deref()[idx]
+    }
+}
+
+impl Index<&str> for Dictionary {
+    type Output = Object;
+
+    /// Returns the value of `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the dictionary.
+    fn index(&self, key: &str) -> &Object {
+        self.get(key).unwrap_or_else(|| panic!("key {key:?} not found"))
+    }
+}
+
 impl fmt::Debug for Dictionary {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_map()
@@ -118,3 +243,44 @@ where
         hashmap.into_iter().collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn integer(obj: &Object) -> i64 {
+        i64::try_from(obj.clone()).unwrap()
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut dict = Dictionary::from_iter([("a", 1), ("b", 2)]);
+
+        assert!(dict.insert("c", 3).is_none());
+        assert_eq!(integer(&dict.insert("a", 4).unwrap()), 1);
+        assert_eq!(integer(&dict["a"]), 4);
+        assert_eq!(dict.len(), 3);
+
+        *dict.get_mut("b").unwrap() = 5.into();
+        assert_eq!(integer(dict.get("b").unwrap()), 5);
+
+        assert_eq!(integer(&dict.remove("a").unwrap()), 4);
+        assert!(!dict.contains_key("a"));
+        assert_eq!(dict.keys().collect::<Vec<_>>(), ["b", "c"]);
+    }
+
+    #[test]
+    fn index_by_position() {
+        let dict = Dictionary::from_iter([("a", 1), ("b", 2)]);
+        assert_eq!(dict[1].key(), "b");
+        assert_eq!(integer(dict[0].value()), 1);
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut dict = Dictionary::new();
+        *dict.get_or_insert_with("count", || 0) = 1.into();
+        dict.get_or_insert_with("count", || 2);
+        assert_eq!(integer(&dict["count"]), 1);
+    }
+}
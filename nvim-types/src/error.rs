@@ -74,7 +74,7 @@ impl Error {
         Err: StdError + From<self::Error>,
         F: FnOnce() -> StdResult<Ok, Err>,
     {
-        (!self.is_err()).then(f).ok_or_else(|| self.into())?
+        (!self.is_err()).then(f).ok_or_else(|| Err::from(self))?
     }
 
     #[inline]
@@ -1,10 +1,18 @@
+pub mod alloc;
 pub mod array;
 pub mod collection;
 pub mod dictionary;
 pub mod error;
+#[cfg(feature = "mlua")]
+mod mlua_impls;
 pub mod object;
+#[cfg(feature = "serde")]
+mod serde_impls;
 pub mod string;
 
+#[cfg(feature = "serde")]
+pub use serde_impls::{LUAREF_KEY, OBJECT_NEWTYPE};
+
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L67
 pub type Boolean = bool;
 
@@ -0,0 +1,186 @@
+//! `IntoLua` and `FromLua` implementations converting `Object`s to and from
+//! [`mlua::Value`]s.
+//!
+//! Lua references are indices into the registry of Neovim's Lua state, which
+//! `mlua` doesn't expose, so they're resolved by small C functions running
+//! on that state.
+
+use libc::c_int;
+use mlua::{
+    ffi,
+    Error as LuaError,
+    FromLua,
+    IntoLua,
+    Lua,
+    Result as LuaResult,
+    Value,
+};
+
+use crate::{
+    array::Array,
+    dictionary::Dictionary,
+    object::{Object, ObjectData, ObjectType},
+    string::String as NvimString,
+};
+
+impl<'lua> IntoLua<'lua> for Object {
+    /// Lua references are converted into the values they point to.
+    fn into_lua(self, lua: &'lua Lua) -> LuaResult<Value<'lua>> {
+        use ObjectType::*;
+
+        let value = match self.r#type {
+            kObjectTypeNil => Value::Nil,
+
+            kObjectTypeBoolean => Value::Boolean(unsafe { self.data.boolean }),
+
+            kObjectTypeInteger => {
+                Value::Integer(unsafe { self.data.integer } as _)
+            },
+
+            kObjectTypeFloat => Value::Number(unsafe { self.data.float }),
+
+            kObjectTypeString => {
+                let string = NvimString::try_from(self).expect("checked type");
+                Value::String(lua.create_string(string.as_bytes())?)
+            },
+
+            kObjectTypeArray => {
+                let array = Array::try_from(self).expect("checked type");
+                let table = lua.create_table_with_capacity(array.len(), 0)?;
+                for obj in array {
+                    table.raw_push(obj.into_lua(lua)?)?;
+                }
+                Value::Table(table)
+            },
+
+            kObjectTypeDictionary => {
+                let dict = Dictionary::try_from(self).expect("checked type");
+                let table = lua.create_table_with_capacity(0, dict.len())?;
+                for (key, obj) in dict {
+                    let key = lua.create_string(key.as_bytes())?;
+                    table.raw_set(key, obj.into_lua(lua)?)?;
+                }
+                Value::Table(table)
+            },
+
+            kObjectTypeLuaRef => {
+                unsafe extern "C-unwind" fn get_ref(
+                    lstate: *mut ffi::lua_State,
+                ) -> c_int {
+                    let luaref = ffi::lua_tointeger(lstate, 1);
+                    ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, luaref);
+                    1
+                }
+
+                let luaref = unsafe { self.into_luaref_unchecked() };
+                let get_ref = unsafe { lua.create_c_function(get_ref)? };
+                get_ref.call(luaref)?
+            },
+        };
+
+        Ok(value)
+    }
+}
+
+impl<'lua> FromLua<'lua> for Object {
+    /// Functions are stored in the registry and converted into Lua
+    /// references, while tables are converted into arrays if their keys are
+    /// exactly the integers from 1 to their length and into dictionaries
+    /// otherwise.
+    fn from_lua(value: Value<'lua>, lua: &'lua Lua) -> LuaResult<Self> {
+        let obj = match value {
+            Value::Nil => Object::nil(),
+
+            Value::Boolean(b) => b.into(),
+
+            Value::Integer(n) => n.into(),
+
+            Value::Number(n) => n.into(),
+
+            Value::String(s) => {
+                NvimString::from_bytes(s.as_bytes().to_vec()).into()
+            },
+
+            Value::Table(table) => {
+                let len = table.raw_len();
+                let pairs = table
+                    .pairs::<Value, Value>()
+                    .collect::<LuaResult<Vec<_>>>()?;
+
+                // Keys are unique, so if they're all in `1..=len` and there
+                // are `len` of them the table is a sequence.
+                let is_sequence = pairs.len() == len
+                    && pairs.iter().all(|(key, _)| {
+                        array_index(key).is_some_and(|idx| idx <= len)
+                    });
+
+                if is_sequence {
+                    let mut items =
+                        (0..len).map(|_| Object::nil()).collect::<Vec<_>>();
+                    for (key, value) in pairs {
+                        let idx = array_index(&key).expect("checked above");
+                        items[idx - 1] = Object::from_lua(value, lua)?;
+                    }
+                    Array::from_iter(items).into()
+                } else {
+                    pairs
+                        .into_iter()
+                        .map(|(key, value)| {
+                            let key = match key {
+                                Value::String(s) => NvimString::from_bytes(
+                                    s.as_bytes().to_vec(),
+                                ),
+                                other => {
+                                    return Err(conversion_error(
+                                        &other,
+                                        "dictionary keys must be strings",
+                                    ))
+                                },
+                            };
+                            Ok((key, Object::from_lua(value, lua)?))
+                        })
+                        .collect::<LuaResult<Dictionary>>()?
+                        .into()
+                }
+            },
+
+            Value::Function(fun) => {
+                unsafe extern "C-unwind" fn make_ref(
+                    lstate: *mut ffi::lua_State,
+                ) -> c_int {
+                    let luaref = ffi::luaL_ref(lstate, ffi::LUA_REGISTRYINDEX);
+                    ffi::lua_pushinteger(lstate, luaref as _);
+                    1
+                }
+
+                let make_ref = unsafe { lua.create_c_function(make_ref)? };
+                Object {
+                    r#type: ObjectType::kObjectTypeLuaRef,
+                    data: ObjectData { luaref: make_ref.call(fun)? },
+                }
+            },
+
+            other => {
+                return Err(conversion_error(&other, "unsupported Lua type"))
+            },
+        };
+
+        Ok(obj)
+    }
+}
+
+/// Returns the 1-based index `key` refers to if it's a positive integer.
+fn array_index(key: &Value<'_>) -> Option<usize> {
+    match *key {
+        Value::Integer(n) => usize::try_from(n).ok().filter(|&n| n > 0),
+        _ => None,
+    }
+}
+
+fn conversion_error(value: &Value<'_>, message: &str) -> LuaError {
+    LuaError::FromLuaConversionError {
+        from: value.type_name(),
+        to: "Object",
+        message: Some(message.to_owned()),
+    }
+}
@@ -68,6 +68,30 @@ impl Object {
     pub const fn is_some(&self) -> bool {
         !self.is_nil()
     }
+
+    /// Returns the reference to the Lua function stored in the object, or
+    /// an error if the object isn't of type `kObjectTypeLuaRef`.
+    #[inline]
+    pub fn into_luaref(self) -> StdResult<LuaRef, FromObjectError> {
+        match self.r#type {
+            ObjectType::kObjectTypeLuaRef => Ok(unsafe { self.data.luaref }),
+            actual => Err(FromObjectError::Primitive {
+                expected: ObjectType::kObjectTypeLuaRef,
+                actual,
+            }),
+        }
+    }
+
+    /// Returns the reference to the Lua function stored in the object
+    /// without checking its type.
+    ///
+    /// # Safety
+    ///
+    /// The object must be of type `kObjectTypeLuaRef`.
+    #[inline]
+    pub unsafe fn into_luaref_unchecked(self) -> LuaRef {
+        self.data.luaref
+    }
 }
 
 impl fmt::Debug for Object {
@@ -245,7 +269,7 @@ impl From<StdString> for Object {
     }
 }
 
-impl<'a> From<&'a str> for Object {
+impl From<&str> for Object {
     #[inline(always)]
     fn from(s: &str) -> Self {
         NvimString::from(s).into()
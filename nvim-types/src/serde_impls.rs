@@ -0,0 +1,313 @@
+//! `Serialize` and `Deserialize` implementations for `Object`s and the
+//! types they contain.
+//!
+//! Lua references don't have a serde equivalent, so they go through a
+//! newtype struct with a reserved name that serializers and deserializers
+//! of `Object`s can recognize, while other formats see a plain integer.
+
+use std::fmt;
+use std::string::String as StdString;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{
+    array::Array,
+    dictionary::{Dictionary, KeyValuePair},
+    object::{Object, ObjectData, ObjectType},
+    string::String as NvimString,
+    LuaRef,
+};
+
+/// The name of the newtype struct an `Object` is deserialized through, and
+/// that a Lua reference is serialized as.
+#[doc(hidden)]
+pub const OBJECT_NEWTYPE: &str = "$nvim_oxi::Object";
+
+/// The only key of the map a deserializer of `Object`s hands to the visitor
+/// of an `Object` to deserialize a Lua reference.
+#[doc(hidden)]
+pub const LUAREF_KEY: &str = "$nvim_oxi::LuaRef";
+
+impl Serialize for NvimString {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Ok(str) => serializer.serialize_str(str),
+            Err(_) => serializer.serialize_bytes(self.as_bytes()),
+        }
+    }
+}
+
+impl Serialize for Object {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let data = &self.data;
+
+        use ObjectType::*;
+        match self.r#type {
+            kObjectTypeNil => serializer.serialize_unit(),
+            kObjectTypeBoolean => {
+                serializer.serialize_bool(unsafe { data.boolean })
+            },
+            kObjectTypeInteger => {
+                serializer.serialize_i64(unsafe { data.integer })
+            },
+            kObjectTypeFloat => {
+                serializer.serialize_f64(unsafe { data.float })
+            },
+            kObjectTypeString => unsafe { &data.string }.serialize(serializer),
+            kObjectTypeArray => unsafe { &data.array }.serialize(serializer),
+            kObjectTypeDictionary => {
+                unsafe { &data.dictionary }.serialize(serializer)
+            },
+            kObjectTypeLuaRef => serializer
+                .serialize_newtype_struct(OBJECT_NEWTYPE, unsafe {
+                    &data.luaref
+                }),
+        }
+    }
+}
+
+impl Serialize for Array {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl Serialize for Dictionary {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer
+            .collect_map(self.iter().map(|pair| (&pair.key, &pair.value)))
+    }
+}
+
+struct StringVisitor;
+
+impl<'de> de::Visitor<'de> for StringVisitor {
+    type Value = NvimString;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_string<E: de::Error>(
+        self,
+        value: StdString,
+    ) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_bytes<E: de::Error>(
+        self,
+        value: &[u8],
+    ) -> Result<Self::Value, E> {
+        Ok(NvimString::from_bytes(value.to_owned()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(
+        self,
+        value: Vec<u8>,
+    ) -> Result<Self::Value, E> {
+        Ok(NvimString::from_bytes(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for NvimString {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_string(StringVisitor)
+    }
+}
+
+struct ObjectVisitor;
+
+impl<'de> de::Visitor<'de> for ObjectVisitor {
+    type Value = Object;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value representable by a Neovim object")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Object::nil())
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Object::nil())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        Object::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        i64::try_from(value).map(Object::from).map_err(|_| {
+            E::invalid_value(de::Unexpected::Unsigned(value), &"an i64")
+        })
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        StringVisitor.visit_str(value).map(Object::from)
+    }
+
+    fn visit_string<E: de::Error>(
+        self,
+        value: StdString,
+    ) -> Result<Self::Value, E> {
+        StringVisitor.visit_string(value).map(Object::from)
+    }
+
+    fn visit_bytes<E: de::Error>(
+        self,
+        value: &[u8],
+    ) -> Result<Self::Value, E> {
+        StringVisitor.visit_bytes(value).map(Object::from)
+    }
+
+    fn visit_byte_buf<E: de::Error>(
+        self,
+        value: Vec<u8>,
+    ) -> Result<Self::Value, E> {
+        StringVisitor.visit_byte_buf(value).map(Object::from)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        ArrayVisitor.visit_seq(seq).map(Object::from)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> Result<Self::Value, A::Error> {
+        let Some(key) = map.next_key::<NvimString>()? else {
+            return Ok(Dictionary::new().into());
+        };
+
+        if key == LUAREF_KEY {
+            let luaref = map.next_value::<LuaRef>()?;
+            return Ok(Object {
+                r#type: ObjectType::kObjectTypeLuaRef,
+                data: ObjectData { luaref },
+            });
+        }
+
+        let value = map.next_value::<Object>()?;
+        let mut pairs = vec![KeyValuePair { key, value }];
+        pairs.extend(collect_pairs(map)?);
+        Ok(Dictionary::from(pairs).into())
+    }
+}
+
+impl<'de> Deserialize<'de> for Object {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_newtype_struct(OBJECT_NEWTYPE, ObjectVisitor)
+    }
+}
+
+struct ArrayVisitor;
+
+impl<'de> de::Visitor<'de> for ArrayVisitor {
+    type Value = Array;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element::<Object>()? {
+            items.push(item);
+        }
+        // Not collecting into the `Array` since that would filter out nils.
+        Ok(Array::from(items))
+    }
+}
+
+impl<'de> Deserialize<'de> for Array {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ArrayVisitor)
+    }
+}
+
+struct DictionaryVisitor;
+
+impl<'de> de::Visitor<'de> for DictionaryVisitor {
+    type Value = Dictionary;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map with string keys")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        map: A,
+    ) -> Result<Self::Value, A::Error> {
+        collect_pairs(map).map(Dictionary::from)
+    }
+}
+
+impl<'de> Deserialize<'de> for Dictionary {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(DictionaryVisitor)
+    }
+}
+
+fn collect_pairs<'de, A: MapAccess<'de>>(
+    mut map: A,
+) -> Result<Vec<KeyValuePair>, A::Error> {
+    let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or(0));
+    while let Some((key, value)) = map.next_entry::<NvimString, Object>()? {
+        pairs.push(KeyValuePair { key, value });
+    }
+    Ok(pairs)
+}
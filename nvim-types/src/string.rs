@@ -1,12 +1,15 @@
-use std::borrow::Cow;
-use std::ffi::OsStr;
-use std::os::unix::ffi::OsStrExt;
+use std::borrow::{Borrow, Cow};
+use std::ffi::{OsStr, OsString};
+use std::hash::{Hash, Hasher};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
 use std::path::PathBuf;
 use std::string::{self, String as StdString};
-use std::{fmt, slice, str};
+use std::{fmt, mem, slice, str};
 
 use libc::{c_char, size_t};
 
+use crate::alloc::{self, Kind};
+
 // https://github.com/neovim/neovim/blob/master/src/nvim/api/private/defs.h#L77
 #[derive(Eq)]
 #[repr(C)]
@@ -20,10 +23,85 @@ impl String {
     #[inline]
     pub fn from_bytes(vec: Vec<u8>) -> Self {
         let size = vec.len();
-        let data = vec.leak().as_mut_ptr() as *mut c_char;
+        alloc::on_alloc(Kind::String, size);
+        // The capacity isn't stored, since the layout has to match Neovim's
+        // `String`, so `into_bytes` rebuilds the `Vec` with a capacity equal
+        // to the length. `Vec::leak` would keep any spare capacity, and
+        // deallocating with a different capacity than the buffer was
+        // allocated with is undefined behavior. Shrinking is a no-op if the
+        // capacity already matches, e.g. for strings grown by `push_bytes`.
+        let data =
+            Box::leak(vec.into_boxed_slice()).as_mut_ptr() as *mut c_char;
         Self { data, size }
     }
 
+    /// Converts `bytes` into a string, replacing invalid UTF-8 sequences
+    /// with `U+FFFD`. Unlike `std::string::String::from_utf8_lossy` the
+    /// allocation is reused if the bytes are valid UTF-8.
+    pub fn from_utf8_lossy_owned(bytes: Vec<u8>) -> Self {
+        match StdString::from_utf8(bytes) {
+            Ok(string) => string.into(),
+            Err(err) => {
+                StdString::from_utf8_lossy(err.as_bytes()).into_owned().into()
+            },
+        }
+    }
+
+    /// Creates a string from formatting arguments, e.g.
+    /// `NvimString::from_fmt(format_args!("{n} matches"))`.
+    #[inline]
+    pub fn from_fmt(args: fmt::Arguments) -> Self {
+        fmt::format(args).into()
+    }
+
+    /// Concatenates `parts` into a new string with a single allocation.
+    pub fn concat<I, S>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let parts = parts.into_iter().collect::<Vec<S>>();
+        let len = parts.iter().map(|part| part.as_ref().len()).sum();
+        let mut bytes = Vec::with_capacity(len);
+        for part in &parts {
+            bytes.extend_from_slice(part.as_ref());
+        }
+        Self::from_bytes(bytes)
+    }
+
+    /// Appends `str` to the end of the string.
+    pub fn push_str(&mut self, str: &str) {
+        self.push_bytes(str.as_bytes());
+    }
+
+    /// Appends `bytes` to the end of the string.
+    ///
+    /// The buffer is grown to the exact new length with a single
+    /// reallocation, which the allocator can often do in place. There's no
+    /// spare capacity to amortize repeated calls though, so use an
+    /// [`NvimStringBuilder`] to build a string out of many pieces.
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        let mut vec = mem::take(self).into_bytes();
+        vec.reserve_exact(bytes.len());
+        vec.extend_from_slice(bytes);
+        *self = Self::from_bytes(vec);
+    }
+
+    /// Returns `true` if the string starts with `prefix`.
+    #[inline]
+    pub fn starts_with<P: AsRef<[u8]>>(&self, prefix: P) -> bool {
+        self.as_bytes().starts_with(prefix.as_ref())
+    }
+
+    /// Returns `true` if the string ends with `suffix`.
+    #[inline]
+    pub fn ends_with<S: AsRef<[u8]>>(&self, suffix: S) -> bool {
+        self.as_bytes().ends_with(suffix.as_ref())
+    }
+
     /// TODO: docs
     #[inline]
     pub const fn is_empty(&self) -> bool {
@@ -57,6 +135,7 @@ impl String {
     /// Converts an `NvimString` into a byte vector, consuming the string.
     #[inline]
     pub fn into_bytes(self) -> Vec<u8> {
+        alloc::on_free(Kind::String, self.size);
         unsafe {
             Vec::from_raw_parts(self.data.cast::<u8>(), self.size, self.size)
         }
@@ -70,6 +149,66 @@ impl String {
     }
 }
 
+/// A growable buffer to build a [`String`] out of many pieces, e.g. with
+/// `write!`, reallocating only when it runs out of capacity.
+#[derive(Clone, Debug, Default)]
+pub struct NvimStringBuilder {
+    bytes: Vec<u8>,
+}
+
+impl NvimStringBuilder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { bytes: Vec::with_capacity(capacity) }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    #[inline]
+    pub fn push_str(&mut self, str: &str) {
+        self.bytes.extend_from_slice(str.as_bytes());
+    }
+
+    #[inline]
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Consumes the builder, returning the built string.
+    #[inline]
+    pub fn build(self) -> String {
+        String::from_bytes(self.bytes)
+    }
+}
+
+impl fmt::Write for NvimStringBuilder {
+    #[inline]
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        self.push_str(str);
+        Ok(())
+    }
+}
+
+impl From<NvimStringBuilder> for String {
+    #[inline]
+    fn from(builder: NvimStringBuilder) -> Self {
+        builder.build()
+    }
+}
+
 impl fmt::Debug for String {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("NvimString")
@@ -79,6 +218,42 @@ impl fmt::Debug for String {
     }
 }
 
+impl Default for String {
+    #[inline]
+    fn default() -> Self {
+        Self::from_bytes(Vec::new())
+    }
+}
+
+impl Hash for String {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+impl AsRef<[u8]> for String {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Borrow<[u8]> for String {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Write for String {
+    #[inline]
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        self.push_str(str);
+        Ok(())
+    }
+}
+
 impl Clone for String {
     fn clone(&self) -> Self {
         Self::from_bytes(self.as_bytes().to_owned())
@@ -102,7 +277,23 @@ impl<'a> From<&'a str> for String {
 impl From<char> for String {
     #[inline]
     fn from(ch: char) -> Self {
-        Self { data: *(Box::new(ch as c_char)) as *mut c_char, size: 1 }
+        Self::from(ch.encode_utf8(&mut [0; 4]) as &str)
+    }
+}
+
+#[cfg(not(windows))]
+impl From<OsString> for String {
+    #[inline]
+    fn from(os_str: OsString) -> Self {
+        Self::from_bytes(os_str.into_vec())
+    }
+}
+
+#[cfg(windows)]
+impl From<OsString> for String {
+    #[inline]
+    fn from(os_str: OsString) -> Self {
+        os_str.to_string_lossy().into_owned().into()
     }
 }
 
@@ -195,10 +386,79 @@ mod tests {
         assert_eq!(lhs, rhs);
     }
 
+    #[test]
+    fn from_char() {
+        assert_eq!(String::from('a'), "a");
+        assert_eq!(String::from('€'), "€");
+    }
+
     #[test]
     fn to_bytes() {
         let s = String::from("hello");
         let bytes = s.into_bytes();
         assert_eq!(&[104, 101, 108, 108, 111][..], &bytes[..]);
     }
+
+    #[test]
+    fn push_and_concat() {
+        let mut string = String::from("foo");
+        string.push_str(" bar");
+        assert_eq!(string, "foo bar");
+        assert!(string.starts_with("foo") && string.ends_with(b"bar"));
+
+        let concat = String::concat([string.as_bytes(), b" ", b"baz"]);
+        assert_eq!(concat, "foo bar baz");
+    }
+
+    #[test]
+    fn capacity_matches_length() {
+        let mut string = String::default();
+        for _ in 0..10 {
+            string.push_bytes(b"abc");
+        }
+        let bytes = string.into_bytes();
+        assert_eq!(bytes.len(), 30);
+        assert_eq!(bytes.capacity(), bytes.len());
+
+        let mut vec = Vec::with_capacity(64);
+        vec.extend_from_slice(b"spare");
+        assert_eq!(String::from_bytes(vec).into_bytes().capacity(), 5);
+    }
+
+    #[test]
+    fn from_fmt() {
+        use std::fmt::Write;
+
+        let mut string = String::from_fmt(format_args!("{} matches", 3));
+        let file = "foo";
+        write!(string, " in {file}").unwrap();
+        assert_eq!(string, "3 matches in foo");
+    }
+
+    #[test]
+    fn hash_as_bytes() {
+        let set = std::collections::HashSet::from([String::from("foo")]);
+        assert!(set.contains(b"foo".as_slice()));
+    }
+
+    #[test]
+    fn builder() {
+        use std::fmt::Write;
+
+        let mut builder = NvimStringBuilder::with_capacity(16);
+        for n in 1..=3 {
+            write!(builder, "{n},").unwrap();
+        }
+        builder.push_bytes(b"end");
+        assert_eq!(builder.build(), "1,2,3,end");
+    }
+
+    #[test]
+    fn from_utf8_lossy_owned() {
+        assert_eq!(String::from_utf8_lossy_owned(b"foo".to_vec()), "foo");
+        assert_eq!(
+            String::from_utf8_lossy_owned(b"a\xffb".to_vec()),
+            "a\u{FFFD}b"
+        );
+    }
 }
@@ -1,5 +1,16 @@
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, DeriveInput};
+use quote::quote;
+use syn::{
+    parse_macro_input,
+    DeriveInput,
+    FnArg,
+    GenericArgument,
+    ItemFn,
+    Pat,
+    PathArguments,
+    ReturnType,
+    Type,
+};
 
 #[proc_macro_derive(ToObject)]
 pub fn derive_to_object(input: TokenStream) -> TokenStream {
@@ -7,3 +18,452 @@ pub fn derive_to_object(input: TokenStream) -> TokenStream {
     eprintln!("{ast:?}");
     TokenStream::new()
 }
+
+/// Implements `LuaPoppable` for a type implementing `Deserialize`, so that
+/// it can be received from Lua as a table.
+#[proc_macro_derive(LuaPoppable)]
+pub fn derive_lua_poppable(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        ast.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::nvim_oxi::lua::LuaPoppable
+            for #name #ty_generics #where_clause
+        {
+            unsafe fn pop(
+                lstate: *mut ::nvim_oxi::lua::lua_State,
+            ) -> ::nvim_oxi::Result<Self> {
+                ::nvim_oxi::lua::__pop_deserialize(lstate)
+            }
+        }
+    }
+    .into()
+}
+
+/// Marks the entrypoint of a plugin, generating the `luaopen_<name>`
+/// function that Lua calls on `require("<name>")`, where `<name>` is the name
+/// of the annotated function.
+///
+/// The function takes no arguments and returns a `nvim_oxi::Result<T>`,
+/// where `T` can be any value that can be pushed to Lua, usually a
+/// `Dictionary` of functions. It becomes the return value of `require`.
+///
+/// With `#[nvim_oxi::module(lazy)]`, `require` returns a proxy table right
+/// away and the function only runs the first time a field of the table is
+/// looked up, e.g. when calling `require("<name>").setup()`. Anything the
+/// function does besides building the table, like creating commands, is
+/// deferred as well. The function has to return a table, whose fields are
+/// then copied into the proxy.
+///
+/// The functions the module exports can be listed, as in
+/// `#[nvim_oxi::module(lazy(setup, open))]`, to register a stub for each of
+/// them in the proxy up front. Stubs run the function when they're first
+/// called and forward the call, so they can be passed around (e.g. as
+/// callbacks) and found by `pairs()` before the module is loaded.
+///
+/// With `#[nvim_oxi::module(on_unload = cleanup)]`, `cleanup` is registered
+/// with `nvim_oxi::on_unload` every time the function runs, and is called
+/// before the module is loaded again or when `nvim_oxi::unload` is called.
+/// It takes no arguments and returns a `nvim_oxi::Result<()>`. Both
+/// arguments can be combined, e.g. `#[nvim_oxi::module(lazy, on_unload =
+/// cleanup)]`.
+///
+/// With `#[nvim_oxi::module(name = "myplugin.utils")]`, the module is
+/// required as `myplugin.utils` instead of by the name of the function.
+/// This lets a single library expose several modules, each with its own
+/// entrypoint, as long as the library is named after the first component
+/// (`myplugin.so` here). Lua looks up submodules in it through their
+/// `luaopen_myplugin_utils` function.
+#[proc_macro_attribute]
+pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ModuleAttr { lazy, stubs, on_unload, name: module } =
+        match parse_module_attr(attr) {
+            Ok(attr) => attr,
+            Err(err) => return err.into_compile_error().into(),
+        };
+
+    let item = parse_macro_input!(item as ItemFn);
+
+    if !item.sig.inputs.is_empty() {
+        return syn::Error::new_spanned(
+            &item.sig.inputs,
+            "the module entrypoint can't take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let name = &item.sig.ident;
+    let module = match module {
+        Some(module) => module.value(),
+        None => name.to_string(),
+    };
+    let luaopen = quote::format_ident!("luaopen_{}", module.replace('.', "_"));
+
+    let body = match on_unload {
+        Some(on_unload) => quote! {
+            || {
+                ::nvim_oxi::on_unload(#on_unload);
+                #name()
+            }
+        },
+        None => quote!(#name),
+    };
+
+    let entrypoint = if lazy {
+        let stubs = stubs.iter().map(ToString::to_string);
+        quote!(__lazy_entrypoint(lstate, #module, &[#(#stubs),*], #body))
+    } else {
+        quote!(__entrypoint(lstate, #module, #body))
+    };
+
+    quote! {
+        #[no_mangle]
+        unsafe extern "C" fn #luaopen(
+            lstate: *mut ::nvim_oxi::lua::lua_State,
+        ) -> ::std::os::raw::c_int {
+            #item
+            ::nvim_oxi::__private::#entrypoint
+        }
+    }
+    .into()
+}
+
+/// The arguments of `#[module]`.
+#[derive(Default)]
+struct ModuleAttr {
+    lazy: bool,
+    stubs: Vec<syn::Ident>,
+    on_unload: Option<syn::Path>,
+    name: Option<syn::LitStr>,
+}
+
+/// Parses the arguments of `#[module]`, a comma-separated list of `lazy`
+/// (optionally followed by the stubs to register, e.g. `lazy(setup)`),
+/// `on_unload = <path>` and `name = "<module>"`.
+fn parse_module_attr(attr: TokenStream) -> syn::Result<ModuleAttr> {
+    use syn::parse::Parser;
+
+    let mut module_attr = ModuleAttr::default();
+
+    let parser = |input: syn::parse::ParseStream| {
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+
+            if ident == "lazy" {
+                module_attr.lazy = true;
+                if input.peek(syn::token::Paren) {
+                    let stubs;
+                    syn::parenthesized!(stubs in input);
+                    module_attr.stubs = stubs
+                        .parse_terminated::<_, syn::Token![,]>(
+                            <syn::Ident as syn::parse::Parse>::parse,
+                        )?
+                        .into_iter()
+                        .collect();
+                }
+            } else if ident == "on_unload" {
+                input.parse::<syn::Token![=]>()?;
+                module_attr.on_unload = Some(input.parse()?);
+            } else if ident == "name" {
+                input.parse::<syn::Token![=]>()?;
+                let name = input.parse::<syn::LitStr>()?;
+                if !is_module_name(&name.value()) {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        "expected a dot-separated list of identifiers",
+                    ));
+                }
+                module_attr.name = Some(name);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "expected `lazy`, `on_unload` or `name`",
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(())
+    };
+
+    parser.parse(attr)?;
+    Ok(module_attr)
+}
+
+/// Whether `name` can be part of the name of a `luaopen_*` function once
+/// its dots are replaced by underscores.
+fn is_module_name(name: &str) -> bool {
+    name.split('.').all(|part| {
+        !part.is_empty()
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Turns a function taking arguments that implement `Deserialize` and
+/// returning a `nvim_oxi::Result<T>` into one with the same name and
+/// visibility returning a `LuaFn`, ready to be put in the module table.
+///
+/// Each argument is converted from the corresponding Lua value, failing with
+/// an error naming the argument if that's not possible. Errors returned by
+/// the function are raised as Lua errors.
+///
+/// ```ignore
+/// #[nvim_oxi::function]
+/// fn add(a: i32, b: i32) -> nvim_oxi::Result<i32> {
+///     Ok(a + b)
+/// }
+///
+/// #[nvim_oxi::module]
+/// fn calc() -> nvim_oxi::Result<Dictionary> {
+///     Ok(Dictionary::from_iter([("add", add())]))
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn function(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return syn::Error::new_spanned(attr, "expected no arguments")
+            .into_compile_error()
+            .into();
+    }
+
+    let item = parse_macro_input!(item as ItemFn);
+
+    match expand_function(item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn expand_function(mut item: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &item.sig;
+
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "exported functions can't be generic",
+        ));
+    }
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "exported functions can't be async",
+        ));
+    }
+
+    let ret = result_type(&sig.output)?;
+
+    let mut args = Vec::new();
+    let mut names = Vec::new();
+
+    for (idx, input) in sig.inputs.iter().enumerate() {
+        let FnArg::Typed(pat_type) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "exported functions can't take `self`",
+            ));
+        };
+
+        let name = match &*pat_type.pat {
+            Pat::Ident(pat) => pat.ident.to_string(),
+            _ => format!("arg{}", idx + 1),
+        };
+
+        args.push(quote::format_ident!("__arg{}", idx));
+        names.push(name);
+    }
+
+    let objects = args.iter().map(|_| quote!(::nvim_oxi::__private::Object));
+    let objects = quote!((#(#objects,)*));
+
+    let attrs = std::mem::take(&mut item.attrs);
+    let vis = std::mem::replace(&mut item.vis, syn::Visibility::Inherited);
+    let name = &item.sig.ident;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #name() -> ::nvim_oxi::LuaFn<#objects, #ret> {
+            #item
+
+            ::nvim_oxi::LuaFn::from(|(#(#args,)*): #objects| {
+                #(
+                    let #args =
+                        ::nvim_oxi::__private::arg(#args, #names)?;
+                )*
+                #name(#(#args),*)
+            })
+        }
+    })
+}
+
+/// Extracts `T` from a return type of the form `Result<T>`.
+fn result_type(output: &ReturnType) -> syn::Result<&Type> {
+    let err = || {
+        syn::Error::new_spanned(
+            output,
+            "exported functions have to return a `nvim_oxi::Result<T>`",
+        )
+    };
+
+    let ReturnType::Type(_, ty) = output else { return Err(err()) };
+    let Type::Path(path) = &**ty else { return Err(err()) };
+    let segment = path.path.segments.last().ok_or_else(err)?;
+
+    if segment.ident != "Result" {
+        return Err(err());
+    }
+
+    let PathArguments::AngleBracketed(generics) = &segment.arguments else {
+        return Err(err());
+    };
+
+    match generics.args.first() {
+        Some(GenericArgument::Type(ty)) => Ok(ty),
+        _ => Err(err()),
+    }
+}
+
+/// Defines an integration test that runs inside Neovim.
+///
+/// The annotated function takes no arguments and returns a
+/// `nvim_oxi::Result<()>`. Running `cargo test` builds the library, loads
+/// it in a headless Neovim (which has to be in `$PATH`) and calls the
+/// function, failing the test if it returns an error or panics. It needs the
+/// `test` feature of `nvim-oxi`.
+///
+/// ```ignore
+/// #[nvim_oxi::test]
+/// fn set_get_var() -> nvim_oxi::Result<()> {
+///     api::set_var("foo", 42)?;
+///     assert_eq!(api::get_var::<u32>("foo")?, 42);
+///     Ok(())
+/// }
+/// ```
+///
+/// Like the entrypoint of [`module`](macro@module), the function has to be
+/// compiled into the library itself, so test modules shouldn't be gated
+/// behind `#[cfg(test)]`.
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return syn::Error::new_spanned(attr, "expected no arguments")
+            .into_compile_error()
+            .into();
+    }
+
+    let item = parse_macro_input!(item as ItemFn);
+
+    if !item.sig.inputs.is_empty() {
+        return syn::Error::new_spanned(
+            &item.sig.inputs,
+            "tests can't take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let features = match manifest_features() {
+        Ok(features) => features,
+        Err(msg) => {
+            return syn::Error::new(proc_macro2::Span::call_site(), msg)
+                .into_compile_error()
+                .into()
+        },
+    };
+
+    let name = &item.sig.ident;
+    let luaopen = quote::format_ident!("luaopen___nvim_oxi_test_{}", name);
+
+    // The full path avoids resolving to this macro if it's imported as
+    // `test`.
+    quote! {
+        #[::core::prelude::v1::test]
+        fn #name() {
+            ::nvim_oxi::__private::run_test(
+                env!("CARGO_MANIFEST_DIR"),
+                env!("CARGO_CRATE_NAME"),
+                stringify!(#name),
+                &[#((#features, cfg!(feature = #features))),*],
+            )
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn #luaopen(
+            lstate: *mut ::nvim_oxi::lua::lua_State,
+        ) -> ::std::os::raw::c_int {
+            #item
+            ::nvim_oxi::__private::__entrypoint(
+                lstate,
+                concat!("__nvim_oxi_test_", stringify!(#name)),
+                #name,
+            )
+        }
+    }
+    .into()
+}
+
+/// Returns the features declared in the manifest of the crate being
+/// compiled, including the ones implied by optional dependencies, so that
+/// the test runner can build the library with the same ones enabled.
+fn manifest_features() -> Result<Vec<String>, String> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| "`CARGO_MANIFEST_DIR` isn't set".to_owned())?;
+
+    let path = std::path::Path::new(&dir).join("Cargo.toml");
+
+    let manifest = std::fs::read_to_string(&path)
+        .map_err(|err| format!("couldn't read {}: {err}", path.display()))?
+        .parse::<toml::Table>()
+        .map_err(|err| format!("couldn't parse {}: {err}", path.display()))?;
+
+    let declared = manifest.get("features").and_then(toml::Value::as_table);
+
+    let mut features = declared
+        .map(|table| table.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    // An optional dependency implies a feature with the same name unless
+    // it's only ever referred to as `dep:<name>`.
+    let is_dep_only = |name: &str| {
+        let dep = format!("dep:{name}");
+        declared.is_some_and(|table| {
+            table.values().filter_map(toml::Value::as_array).any(|enables| {
+                enables.iter().any(|f| f.as_str() == Some(dep.as_str()))
+            })
+        })
+    };
+
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(|t| t.as_table()));
+
+    for table in std::iter::once(&manifest).chain(targets) {
+        let Some(deps) =
+            table.get("dependencies").and_then(toml::Value::as_table)
+        else {
+            continue;
+        };
+
+        for (name, dep) in deps {
+            let optional = dep
+                .get("optional")
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(false);
+
+            if optional && !is_dep_only(name) && !features.contains(name) {
+                features.push(name.clone());
+            }
+        }
+    }
+
+    Ok(features)
+}
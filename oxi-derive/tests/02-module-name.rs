@@ -0,0 +1,43 @@
+// `#[module]` expands to paths under `::nvim_oxi`, which this crate stands
+// in for so that the test doesn't need a Neovim to link against.
+extern crate self as nvim_oxi;
+
+use std::cell::Cell;
+use std::os::raw::c_int;
+
+pub type Result<T> = std::result::Result<T, ()>;
+
+pub mod lua {
+    #[allow(non_camel_case_types)]
+    pub enum lua_State {}
+}
+
+pub mod __private {
+    use super::*;
+
+    thread_local! {
+        pub static LOADED: Cell<Option<&'static str>> = Cell::new(None);
+    }
+
+    pub unsafe fn __entrypoint<R, F>(
+        _lstate: *mut lua::lua_State,
+        module: &'static str,
+        body: F,
+    ) -> c_int
+    where
+        F: FnOnce() -> Result<R>,
+    {
+        LOADED.with(|loaded| loaded.set(Some(module)));
+        body().map_or(1, |_| 0)
+    }
+}
+
+#[oxi_derive::module(name = "myplugin.utils")]
+fn utils() -> Result<()> {
+    Ok(())
+}
+
+fn main() {
+    assert_eq!(unsafe { luaopen_myplugin_utils(std::ptr::null_mut()) }, 0);
+    assert_eq!(__private::LOADED.with(Cell::get), Some("myplugin.utils"));
+}
@@ -0,0 +1,11 @@
+#[oxi_derive::module(name = "myplugin..utils")]
+fn empty_component() -> Result<(), ()> {
+    Ok(())
+}
+
+#[oxi_derive::module(name = "my-plugin")]
+fn dash() -> Result<(), ()> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+// `#[module]` expands to paths under `::nvim_oxi`, which this crate stands
+// in for so that the test doesn't need a Neovim to link against.
+extern crate self as nvim_oxi;
+
+use std::cell::RefCell;
+use std::os::raw::c_int;
+
+pub type Result<T> = std::result::Result<T, ()>;
+
+thread_local! {
+    static CALLS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+}
+
+fn record(call: &'static str) {
+    CALLS.with(|calls| calls.borrow_mut().push(call));
+}
+
+pub fn on_unload(fun: fn() -> Result<()>) {
+    record("on_unload");
+    fun().unwrap();
+}
+
+pub mod lua {
+    #[allow(non_camel_case_types)]
+    pub enum lua_State {}
+}
+
+pub mod __private {
+    use super::*;
+
+    pub unsafe fn __lazy_entrypoint<R, F>(
+        _lstate: *mut lua::lua_State,
+        module: &'static str,
+        stubs: &'static [&'static str],
+        body: F,
+    ) -> c_int
+    where
+        F: FnOnce() -> Result<R> + 'static,
+    {
+        record(module);
+        stubs.iter().for_each(|stub| record(stub));
+        body().map_or(1, |_| 0)
+    }
+}
+
+fn cleanup() -> Result<()> {
+    record("cleanup");
+    Ok(())
+}
+
+#[oxi_derive::module(lazy(setup, open), on_unload = cleanup)]
+fn myplugin() -> Result<()> {
+    record("body");
+    Ok(())
+}
+
+fn main() {
+    assert_eq!(unsafe { luaopen_myplugin(std::ptr::null_mut()) }, 0);
+
+    let calls = CALLS.with(|calls| calls.take());
+    assert_eq!(calls, ["myplugin", "setup", "open", "on_unload", "cleanup", "body"]);
+}
@@ -0,0 +1,21 @@
+#[oxi_derive::module]
+fn with_args(_lazy: bool) -> Result<(), ()> {
+    Ok(())
+}
+
+#[oxi_derive::module(eager)]
+fn unknown_arg() -> Result<(), ()> {
+    Ok(())
+}
+
+#[oxi_derive::module(on_unload)]
+fn missing_value() -> Result<(), ()> {
+    Ok(())
+}
+
+#[oxi_derive::module(lazy("setup"))]
+fn string_stub() -> Result<(), ()> {
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+// `#[function]` expands to paths under `::nvim_oxi`, which this crate
+// stands in for so that the test doesn't need a Neovim to link against.
+extern crate self as nvim_oxi;
+
+pub type Result<T> = std::result::Result<T, String>;
+
+pub struct LuaFn<A, R>(Box<dyn Fn(A) -> Result<R>>);
+
+impl<A, R, F> From<F> for LuaFn<A, R>
+where
+    F: Fn(A) -> Result<R> + 'static,
+{
+    fn from(fun: F) -> Self {
+        Self(Box::new(fun))
+    }
+}
+
+pub mod __private {
+    use super::*;
+
+    pub type Object = i64;
+
+    pub fn arg<T: TryFrom<i64>>(obj: Object, name: &str) -> Result<T> {
+        T::try_from(obj).map_err(|_| format!("invalid `{name}`"))
+    }
+}
+
+/// Adds two numbers.
+#[oxi_derive::function]
+pub fn add(a: u8, b: u8) -> Result<u16> {
+    Ok(u16::from(a) + u16::from(b))
+}
+
+#[oxi_derive::function]
+fn answer() -> crate::Result<&'static str> {
+    Ok("42")
+}
+
+fn main() {
+    let LuaFn(add) = add();
+    assert_eq!(add((1, 2)), Ok(3));
+    assert_eq!(add((1, -2)), Err("invalid `b`".to_owned()));
+
+    let LuaFn(answer) = answer();
+    assert_eq!(answer(()), Ok("42"));
+}
@@ -0,0 +1,30 @@
+#[oxi_derive::function(name = "add")]
+fn with_args() -> Result<(), ()> {
+    Ok(())
+}
+
+#[oxi_derive::function]
+fn generic<T>(_t: T) -> Result<(), ()> {
+    Ok(())
+}
+
+#[oxi_derive::function]
+async fn asynchronous() -> Result<(), ()> {
+    Ok(())
+}
+
+#[oxi_derive::function]
+fn no_result() -> u32 {
+    42
+}
+
+struct Plugin;
+
+impl Plugin {
+    #[oxi_derive::function]
+    fn method(&self) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+fn main() {}
@@ -2,4 +2,10 @@
 fn tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/01-parse.rs");
+    t.pass("tests/02-module-name.rs");
+    t.compile_fail("tests/03-module-invalid-name.rs");
+    t.pass("tests/04-module-lazy-on-unload.rs");
+    t.compile_fail("tests/05-module-errors.rs");
+    t.pass("tests/06-function.rs");
+    t.compile_fail("tests/07-function-errors.rs");
 }